@@ -0,0 +1,83 @@
+//! Counts the heap allocations performed while parsing a large generated configuration with many
+//! repeated identifiers.
+//!
+//! Compare the output with and without identifier interning:
+//!
+//! ```sh
+//! cargo bench -p benchmarks --bench allocations
+//! cargo bench -p benchmarks --bench allocations --features perf
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn generate_input(blocks: usize) -> String {
+    let mut input = String::new();
+
+    for i in 0..blocks {
+        input.push_str(&format!(
+            r#"
+resource "aws_security_group_rule" "rule_{i}" {{
+  security_group_id        = aws_security_group.default_security_group.id
+  source_security_group_id = aws_security_group.source_security_group.id
+  type                     = "ingress"
+  from_port                = {i}
+  to_port                  = {i}
+}}
+"#
+        ));
+    }
+
+    input
+}
+
+fn measure<T>(name: &str, f: impl FnOnce() -> T) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+
+    black_box(f());
+
+    println!(
+        "{name:<10} {:>10} allocations {:>12} bytes",
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    );
+}
+
+fn main() {
+    let input = generate_input(10_000);
+
+    println!(
+        "parsing {} bytes (perf feature {})",
+        input.len(),
+        if cfg!(feature = "perf") {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+
+    measure("hcl-edit", || hcl_edit::parser::parse_body(&input).unwrap());
+    measure("hcl-rs", || hcl::parse(&input).unwrap());
+}
@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use hcl::format::{Format, Formatter};
+use hcl::{Expression, Value};
+
+fn large_arrays() -> Vec<(&'static str, Value)> {
+    let numbers: Value = (0..10_000u64).map(|n| n * 7919 % 10_007).collect();
+    let floats: Value = (0..10_000u32).map(|n| f64::from(n) / 7.0).collect();
+    let strings: Value = (0..10_000).map(|n| format!("item-{n}")).collect();
+    let table: Value = (0..100)
+        .map(|row| (0..100u64).map(|col| row * col).collect::<Value>())
+        .collect();
+
+    // Baselines for the scalar array fast path: the same arrays with a trailing string element,
+    // which forces the generic per-element formatting path.
+    let numbers_baseline = with_trailing_string(&numbers);
+    let floats_baseline = with_trailing_string(&floats);
+    let table_baseline: Value = table
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(with_trailing_string)
+        .collect();
+
+    vec![
+        ("numbers", numbers),
+        ("numbers/baseline", numbers_baseline),
+        ("floats", floats),
+        ("floats/baseline", floats_baseline),
+        ("strings", strings),
+        ("table", table),
+        ("table/baseline", table_baseline),
+    ]
+}
+
+fn with_trailing_string(array: &Value) -> Value {
+    let mut array = array.clone();
+    array.as_array_mut().unwrap().push(Value::from("end"));
+    array
+}
+
+fn format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format");
+
+    for (name, value) in large_arrays() {
+        let expr = Expression::from(value.clone());
+
+        for compact in [false, true] {
+            let id = if compact {
+                format!("{name}/compact")
+            } else {
+                name.to_owned()
+            };
+
+            let mut formatter = Formatter::builder().compact_arrays(compact).build_vec();
+
+            group.bench_function(BenchmarkId::new("value", &id), |b| {
+                b.iter(|| black_box(value.format_vec(&mut formatter).unwrap()));
+            });
+
+            group.bench_function(BenchmarkId::new("expr", &id), |b| {
+                b.iter(|| black_box(expr.format_vec(&mut formatter).unwrap()));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, format);
+criterion_main!(benches);
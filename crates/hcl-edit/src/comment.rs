@@ -0,0 +1,353 @@
+//! Scanning and removal of comments in decor.
+
+use crate::expr::{
+    Array, Expression, ForCond, ForExpr, ForIntro, FuncArgs, Null, Object, ObjectKeyMut, Splat,
+    Traversal,
+};
+use crate::structure::{AttributeMut, Block, Body};
+use crate::template::{
+    ElseTemplateExpr, EndforTemplateExpr, EndifTemplateExpr, ForTemplateExpr, IfTemplateExpr,
+};
+use crate::visit_mut::{
+    visit_array_mut, visit_attr_mut, visit_block_mut, visit_body_mut, visit_expr_mut,
+    visit_for_cond_mut, visit_for_expr_mut, visit_for_intro_mut, visit_for_template_expr_mut,
+    visit_func_args_mut, visit_if_template_expr_mut, visit_object_mut, visit_traversal_mut,
+    VisitMut,
+};
+use crate::{Decor, Decorate, Decorated, Formatted, Ident, Number, RawString};
+use std::borrow::Cow;
+
+pub(crate) fn strip_comments(body: &mut Body) {
+    CommentStripper.visit_body_mut(body);
+}
+
+struct CommentStripper;
+
+macro_rules! strip_decorated_leaves {
+    ($($name: ident => $t: ty),+ $(,)?) => {
+        $(
+            fn $name(&mut self, node: &mut $t) {
+                strip_decor(node.decor_mut());
+            }
+        )+
+    };
+}
+
+impl VisitMut for CommentStripper {
+    strip_decorated_leaves! {
+        visit_ident_mut => Decorated<Ident>,
+        visit_null_mut => Decorated<Null>,
+        visit_bool_mut => Decorated<bool>,
+        visit_u64_mut => Decorated<u64>,
+        visit_number_mut => Formatted<Number>,
+        visit_string_mut => Decorated<String>,
+        visit_splat_mut => Decorated<Splat>,
+    }
+
+    fn visit_body_mut(&mut self, node: &mut Body) {
+        // The suffix of a body follows the newline after its last structure.
+        strip_decor_impl(node.decor_mut(), false, true);
+        visit_body_mut(self, node);
+    }
+
+    fn visit_attr_mut(&mut self, mut node: AttributeMut) {
+        strip_structure_decor(node.decor_mut());
+        strip_decor(node.key_decor_mut());
+        visit_attr_mut(self, node);
+    }
+
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        strip_structure_decor(node.decor_mut());
+        visit_block_mut(self, node);
+    }
+
+    fn visit_expr_mut(&mut self, node: &mut Expression) {
+        strip_decor(node.decor_mut());
+        visit_expr_mut(self, node);
+    }
+
+    fn visit_array_mut(&mut self, node: &mut Array) {
+        if let Some(trailing) = strip_raw(node.trailing(), false) {
+            node.set_trailing(trailing);
+        }
+
+        visit_array_mut(self, node);
+    }
+
+    fn visit_object_mut(&mut self, node: &mut Object) {
+        if let Some(trailing) = strip_raw(node.trailing(), false) {
+            node.set_trailing(trailing);
+        }
+
+        visit_object_mut(self, node);
+    }
+
+    fn visit_object_key_mut(&mut self, mut node: ObjectKeyMut) {
+        strip_decor(node.decor_mut());
+    }
+
+    fn visit_traversal_mut(&mut self, node: &mut Traversal) {
+        for operator in &mut node.operators {
+            strip_decor(operator.decor_mut());
+        }
+
+        visit_traversal_mut(self, node);
+    }
+
+    fn visit_func_args_mut(&mut self, node: &mut FuncArgs) {
+        strip_decor(node.decor_mut());
+
+        if let Some(trailing) = strip_raw(node.trailing(), false) {
+            node.set_trailing(trailing);
+        }
+
+        visit_func_args_mut(self, node);
+    }
+
+    fn visit_for_expr_mut(&mut self, node: &mut ForExpr) {
+        if let Some(trailing) = strip_raw(node.trailing(), false) {
+            node.set_trailing(trailing);
+        }
+
+        visit_for_expr_mut(self, node);
+    }
+
+    fn visit_for_intro_mut(&mut self, node: &mut ForIntro) {
+        strip_decor(node.decor_mut());
+        visit_for_intro_mut(self, node);
+    }
+
+    fn visit_for_cond_mut(&mut self, node: &mut ForCond) {
+        strip_decor(node.decor_mut());
+        visit_for_cond_mut(self, node);
+    }
+
+    fn visit_if_template_expr_mut(&mut self, node: &mut IfTemplateExpr) {
+        if let Some(preamble) = strip_raw(node.preamble(), false) {
+            node.set_preamble(preamble);
+        }
+
+        visit_if_template_expr_mut(self, node);
+    }
+
+    fn visit_else_template_expr_mut(&mut self, node: &mut ElseTemplateExpr) {
+        if let Some(preamble) = strip_raw(node.preamble(), false) {
+            node.set_preamble(preamble);
+        }
+
+        if let Some(trailing) = strip_raw(node.trailing(), false) {
+            node.set_trailing(trailing);
+        }
+
+        self.visit_template_mut(&mut node.template);
+    }
+
+    fn visit_endif_template_expr_mut(&mut self, node: &mut EndifTemplateExpr) {
+        if let Some(preamble) = strip_raw(node.preamble(), false) {
+            node.set_preamble(preamble);
+        }
+
+        if let Some(trailing) = strip_raw(node.trailing(), false) {
+            node.set_trailing(trailing);
+        }
+    }
+
+    fn visit_for_template_expr_mut(&mut self, node: &mut ForTemplateExpr) {
+        if let Some(preamble) = strip_raw(node.preamble(), false) {
+            node.set_preamble(preamble);
+        }
+
+        visit_for_template_expr_mut(self, node);
+    }
+
+    fn visit_endfor_template_expr_mut(&mut self, node: &mut EndforTemplateExpr) {
+        if let Some(preamble) = strip_raw(node.preamble(), false) {
+            node.set_preamble(preamble);
+        }
+
+        if let Some(trailing) = strip_raw(node.trailing(), false) {
+            node.set_trailing(trailing);
+        }
+    }
+}
+
+// Strips the decor of a node which may be preceded by other content on the same line.
+fn strip_decor(decor: &mut Decor) {
+    strip_decor_impl(decor, false, false);
+}
+
+// Strips the decor of an attribute or block, whose prefix always starts at the beginning of a
+// line.
+fn strip_structure_decor(decor: &mut Decor) {
+    strip_decor_impl(decor, true, false);
+}
+
+fn strip_decor_impl(decor: &mut Decor, prefix_line_start: bool, suffix_line_start: bool) {
+    if let Some(prefix) = decor
+        .prefix()
+        .and_then(|prefix| strip_raw(prefix, prefix_line_start))
+    {
+        decor.set_prefix(prefix);
+    }
+
+    if let Some(suffix) = decor
+        .suffix()
+        .and_then(|suffix| strip_raw(suffix, suffix_line_start))
+    {
+        decor.set_suffix(suffix);
+    }
+}
+
+// Returns the comment-free version of `raw`, or `None` if it does not contain any comments.
+fn strip_raw(raw: &RawString, line_start: bool) -> Option<String> {
+    match strip_comment_text(raw, line_start) {
+        Cow::Borrowed(_) => None,
+        Cow::Owned(stripped) => Some(stripped),
+    }
+}
+
+// Returns an iterator over the comments in `raw`, which is expected to only consist of whitespace
+// and comments. Each item holds the byte offset of a comment and the comment itself, including
+// its markers. Line comments extend up to, but not including the newline. Unterminated inline
+// comments extend up to the end of `raw`.
+pub(crate) fn comments(raw: &str) -> impl Iterator<Item = (usize, &str)> + '_ {
+    let mut offset = 0;
+
+    std::iter::from_fn(move || loop {
+        let start = offset + raw[offset..].find(['#', '/'])?;
+        let rest = &raw[start..];
+
+        let len = if rest.starts_with('#') || rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if let Some(inline) = rest.strip_prefix("/*") {
+            inline.find("*/").map_or(rest.len(), |end| end + 4)
+        } else {
+            offset = start + 1;
+            continue;
+        };
+
+        offset = start + len;
+        return Some((start, &rest[..len]));
+    })
+}
+
+// Returns `true` if `comment`, as returned by `comments`, is an inline comment.
+pub(crate) fn is_inline_comment(comment: &str) -> bool {
+    comment.starts_with("/*")
+}
+
+// Removes all comments from `raw`, which is expected to only consist of whitespace and comments.
+// `line_start` indicates whether `raw` starts at the beginning of a line.
+//
+// Lines which only contained comments are removed entirely, while blank lines and the
+// indentation of the remaining lines are preserved. The first line is only removed if
+// `line_start` is `true`, otherwise just its comments are, because it might end a line with
+// other content. Whitespace preceding a comment which ends a line is removed, as is whitespace
+// following an inline comment which is followed by other content on the same line.
+fn strip_comment_text(raw: &str, line_start: bool) -> Cow<'_, str> {
+    let mut comments = comments(raw).peekable();
+
+    if comments.peek().is_none() {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut stripped = String::with_capacity(raw.len());
+    let mut line = String::new();
+    let mut line_start = line_start;
+    let mut had_comment = false;
+    let mut pos = 0;
+
+    loop {
+        let next = comments.next();
+        let text = next.map_or(&raw[pos..], |(start, _)| &raw[pos..start]);
+
+        for ch in text.chars() {
+            line.push(ch);
+
+            if ch == '\n' {
+                if !(line_start && had_comment && line.trim().is_empty()) {
+                    stripped.push_str(&line);
+                }
+
+                line.clear();
+                line_start = true;
+                had_comment = false;
+            }
+        }
+
+        let Some((start, comment)) = next else {
+            break;
+        };
+
+        pos = start + comment.len();
+        had_comment = true;
+
+        if is_inline_comment(comment) {
+            let rest = &raw[pos..];
+            let after = rest.trim_start_matches([' ', '\t']);
+
+            if !rest.is_empty() && !after.starts_with(['\r', '\n']) {
+                pos = raw.len() - after.len();
+                continue;
+            }
+        }
+
+        line.truncate(line.trim_end_matches([' ', '\t']).len());
+    }
+
+    // The last line is never removed because it holds the indentation of whatever follows.
+    stripped.push_str(&line);
+    Cow::Owned(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn strip_comment_text() {
+        let tests = [
+            ("", false, ""),
+            ("  \n\n  ", true, "  \n\n  "),
+            (" # comment", false, ""),
+            (" // comment\n", false, "\n"),
+            (" // comment\n", true, ""),
+            ("\n  # comment\n\n  // comment\n  ", false, "\n\n  "),
+            ("\r\n  # comment\r\n  ", false, "\r\n  "),
+            (" /* inline */ ", false, " "),
+            ("  /* inline */ ", true, "  "),
+            (" /* inline */", false, ""),
+            ("\n  /* multi\n     line */\n  ", false, "\n  "),
+            ("  /* a */ # b\n  ", false, "\n  "),
+            ("  /* a */ # b\n  ", true, "  "),
+            ("/*/ still a comment */", false, ""),
+            ("  /* a */ // b\n  ", true, "  "),
+        ];
+
+        for (input, line_start, expected) in tests {
+            assert_eq!(
+                super::strip_comment_text(input, line_start),
+                expected,
+                "input: {input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn comments() {
+        let tests: [(&str, &[(usize, &str)]); 6] = [
+            ("", &[]),
+            ("  \n  ", &[]),
+            (" # a\n// b", &[(1, "# a"), (5, "// b")]),
+            (" /* a */ // b\n", &[(1, "/* a */"), (9, "// b")]),
+            ("/* a\n # b */ #c", &[(0, "/* a\n # b */"), (13, "#c")]),
+            ("/* unterminated", &[(0, "/* unterminated")]),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(
+                super::comments(input).collect::<Vec<_>>(),
+                expected,
+                "input: {input:?}"
+            );
+        }
+    }
+}
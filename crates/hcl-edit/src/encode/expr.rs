@@ -136,6 +136,7 @@ impl Encode for ForExpr {
             if let Some(cond) = &self.cond {
                 cond.encode_decorated(buf, LEADING_SPACE_DECOR)?;
             }
+            self.trailing().encode_with_default(buf, "")?;
             buf.write_char('}')
         } else {
             // list expr
@@ -145,6 +146,7 @@ impl Encode for ForExpr {
             if let Some(cond) = &self.cond {
                 cond.encode_decorated(buf, LEADING_SPACE_DECOR)?;
             }
+            self.trailing().encode_with_default(buf, "")?;
             buf.write_char(']')
         }
     }
@@ -157,7 +159,7 @@ impl Encode for ForIntro {
             key_var.encode_decorated(buf, LEADING_SPACE_DECOR)?;
             buf.write_char(',')?;
         }
-        self.value_var.encode_decorated(buf, LEADING_SPACE_DECOR)?;
+        self.value_var.encode_decorated(buf, BOTH_SPACE_DECOR)?;
         buf.write_str("in")?;
         self.collection_expr
             .encode_decorated(buf, BOTH_SPACE_DECOR)?;
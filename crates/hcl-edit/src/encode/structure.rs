@@ -30,7 +30,14 @@ impl EncodeDecorated for Structure {
 
 impl Encode for Attribute {
     fn encode(&self, buf: &mut EncodeState) -> fmt::Result {
-        self.key.encode_decorated(buf, TRAILING_SPACE_DECOR)?;
+        if self.quoted_key() {
+            encode_decorated(&self.key, buf, TRAILING_SPACE_DECOR, |buf| {
+                encode_quoted_string(buf, &self.key)
+            })?;
+        } else {
+            self.key.encode_decorated(buf, TRAILING_SPACE_DECOR)?;
+        }
+
         buf.write_char('=')?;
         self.value.encode_decorated(buf, LEADING_SPACE_DECOR)
     }
@@ -7,7 +7,7 @@ use crate::template::{
     ForTemplateExpr, HeredocTemplate, IfDirective, IfTemplateExpr, Interpolation, StringTemplate,
     Strip, Template,
 };
-use crate::util::indent_by;
+use crate::util::indent_with;
 use hcl_primitives::template::escape_markers;
 use std::fmt::{self, Write};
 
@@ -38,12 +38,12 @@ impl Encode for HeredocTemplate {
 
         writeln!(buf, "{}", self.delimiter.as_str())?;
 
-        match self.indent() {
-            Some(n) => {
+        match self.indent_str() {
+            Some(indent) => {
                 let mut indent_buf = String::new();
                 let mut indent_state = EncodeState::new(&mut indent_buf);
                 self.template.encode(&mut indent_state)?;
-                let indented = indent_by(&indent_buf, n, false);
+                let indented = indent_with(&indent_buf, indent, false);
                 buf.write_str(&indented)?;
             }
             None => self.template.encode(buf)?,
@@ -1,5 +1,6 @@
 use crate::expr::Expression;
-use crate::Decor;
+use crate::{Decor, Decorate};
+use std::mem;
 use std::ops::Range;
 
 /// The conditional operator allows selecting from one of two expressions based on the outcome of a
@@ -34,6 +35,74 @@ impl Conditional {
         }
     }
 
+    /// Creates a new [`ConditionalBuilder`] to start building a new `Conditional` with the
+    /// provided condition expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::expr::{Conditional, Expression};
+    /// use hcl_edit::Ident;
+    ///
+    /// let cond = Conditional::builder(Expression::Variable(Ident::new("enabled").into()))
+    ///     .true_expr("on")
+    ///     .false_expr("off")
+    ///     .build();
+    ///
+    /// assert_eq!(cond, Conditional::new(Ident::new("enabled"), "on", "off"));
+    /// ```
+    pub fn builder(cond_expr: impl Into<Expression>) -> ConditionalBuilder {
+        ConditionalBuilder::new(cond_expr.into())
+    }
+
+    /// Replaces the condition expression and returns the previous one.
+    ///
+    /// The decor of the previous expression is transferred to the new one, so that surrounding
+    /// whitespace and comments are preserved.
+    pub fn set_cond_expr(&mut self, expr: impl Into<Expression>) -> Expression {
+        replace_preserving_decor(&mut self.cond_expr, expr.into())
+    }
+
+    /// Replaces the expression returned if the condition evaluates to `true` and returns the
+    /// previous one.
+    ///
+    /// The decor of the previous expression is transferred to the new one, so that surrounding
+    /// whitespace and comments are preserved.
+    pub fn set_true_expr(&mut self, expr: impl Into<Expression>) -> Expression {
+        replace_preserving_decor(&mut self.true_expr, expr.into())
+    }
+
+    /// Replaces the expression returned if the condition evaluates to `false` and returns the
+    /// previous one.
+    ///
+    /// The decor of the previous expression is transferred to the new one, so that surrounding
+    /// whitespace and comments are preserved.
+    pub fn set_false_expr(&mut self, expr: impl Into<Expression>) -> Expression {
+        replace_preserving_decor(&mut self.false_expr, expr.into())
+    }
+
+    /// Swaps the expressions of the `true` and `false` branches.
+    ///
+    /// The decor stays in place, i.e. whitespace and comments surrounding the branches are not
+    /// swapped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::expr::Expression;
+    ///
+    /// let mut expr: Expression = "var.enabled ?  \"on\" /* yes */ : \"off\"".parse().unwrap();
+    /// let cond = expr.as_conditional_mut().unwrap();
+    ///
+    /// cond.swap_branches();
+    ///
+    /// assert_eq!(expr.to_string(), "var.enabled ?  \"off\" /* yes */ : \"on\"");
+    /// ```
+    pub fn swap_branches(&mut self) {
+        mem::swap(&mut self.true_expr, &mut self.false_expr);
+        mem::swap(self.true_expr.decor_mut(), self.false_expr.decor_mut());
+    }
+
     pub(crate) fn despan(&mut self, input: &str) {
         self.decor.despan(input);
         self.cond_expr.despan(input);
@@ -52,3 +121,66 @@ impl PartialEq for Conditional {
 
 decorate_impl!(Conditional);
 span_impl!(Conditional);
+
+fn replace_preserving_decor(dest: &mut Expression, mut expr: Expression) -> Expression {
+    mem::swap(expr.decor_mut(), dest.decor_mut());
+    mem::replace(dest, expr)
+}
+
+/// `ConditionalBuilder` builds an HCL [`Conditional`].
+///
+/// Branches which are not set explicitly default to `null`. A call to
+/// [`.build()`](ConditionalBuilder::build) produces the final `Conditional`.
+#[derive(Debug)]
+pub struct ConditionalBuilder {
+    cond_expr: Expression,
+    true_expr: Expression,
+    false_expr: Expression,
+    decor: Decor,
+}
+
+impl ConditionalBuilder {
+    fn new(cond_expr: Expression) -> ConditionalBuilder {
+        ConditionalBuilder {
+            cond_expr,
+            true_expr: Expression::null(),
+            false_expr: Expression::null(),
+            decor: Decor::default(),
+        }
+    }
+
+    /// Sets the expression returned if the condition evaluates to `true`.
+    ///
+    /// Consumes `self` and returns a new `ConditionalBuilder`.
+    #[inline]
+    pub fn true_expr(mut self, expr: impl Into<Expression>) -> ConditionalBuilder {
+        self.true_expr = expr.into();
+        self
+    }
+
+    /// Sets the expression returned if the condition evaluates to `false`.
+    ///
+    /// Consumes `self` and returns a new `ConditionalBuilder`.
+    #[inline]
+    pub fn false_expr(mut self, expr: impl Into<Expression>) -> ConditionalBuilder {
+        self.false_expr = expr.into();
+        self
+    }
+
+    /// Sets the decor of the `Conditional`.
+    ///
+    /// Consumes `self` and returns a new `ConditionalBuilder`.
+    #[inline]
+    pub fn decor(mut self, decor: impl Into<Decor>) -> ConditionalBuilder {
+        self.decor = decor.into();
+        self
+    }
+
+    /// Consumes `self` and builds the [`Conditional`] from the components set via the builder
+    /// methods.
+    pub fn build(self) -> Conditional {
+        let mut cond = Conditional::new(self.cond_expr, self.true_expr, self.false_expr);
+        cond.decor = self.decor;
+        cond
+    }
+}
@@ -9,12 +9,12 @@ mod operation;
 mod traversal;
 
 pub use self::array::{Array, IntoIter, Iter, IterMut};
-pub use self::conditional::Conditional;
+pub use self::conditional::{Conditional, ConditionalBuilder};
 pub use self::for_expr::{ForCond, ForExpr, ForIntro};
 pub use self::func_call::{FuncArgs, FuncCall, FuncName};
 pub use self::object::{
-    Object, ObjectIntoIter, ObjectIter, ObjectIterMut, ObjectKey, ObjectKeyMut, ObjectValue,
-    ObjectValueAssignment, ObjectValueTerminator,
+    Object, ObjectEntry, ObjectIntoIter, ObjectIter, ObjectIterMut, ObjectKey, ObjectKeyMut,
+    ObjectValue, ObjectValueAssignment, ObjectValueTerminator,
 };
 pub use self::operation::{BinaryOp, BinaryOperator, UnaryOp, UnaryOperator};
 pub use self::traversal::{Splat, Traversal, TraversalOperator};
@@ -240,6 +240,14 @@ impl Expression {
         }
     }
 
+    /// If the expression is a conditional, returns a mutable reference to it, otherwise `None`.
+    pub fn as_conditional_mut(&mut self) -> Option<&mut Conditional> {
+        match self {
+            Expression::Conditional(value) => Some(value),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if the expression is a function call.
     pub fn is_func_call(&self) -> bool {
         self.as_func_call().is_some()
@@ -1,7 +1,7 @@
 use crate::expr::Expression;
 use crate::{Decor, Decorate, Decorated, Ident, RawString, Span};
 use std::ops::{self, Range};
-use vecmap::map::{MutableKeys, VecMap};
+use vecmap::map::{Entry, MutableKeys, VecMap};
 
 /// An owning iterator over the entries of an `Object`.
 ///
@@ -28,6 +28,14 @@ pub type ObjectIter<'a> = Box<dyn Iterator<Item = (&'a ObjectKey, &'a ObjectValu
 /// [`iter_mut`]: Object::iter_mut
 pub type ObjectIterMut<'a> = Box<dyn Iterator<Item = (ObjectKeyMut<'a>, &'a mut ObjectValue)> + 'a>;
 
+/// A view into a single entry in an `Object`, which may either be vacant or occupied.
+///
+/// Values of this type are created by the [`entry`] method on [`Object`]. See its documentation
+/// for more.
+///
+/// [`entry`]: Object::entry
+pub type ObjectEntry<'a> = Entry<'a, ObjectKey, ObjectValue>;
+
 /// Type representing a HCL object.
 #[derive(Debug, Clone, Eq, Default)]
 pub struct Object {
@@ -123,6 +131,39 @@ impl Object {
         self.items.insert(key.into(), value.into())
     }
 
+    /// Get the given key's corresponding entry in the object for insertion and/or in-place
+    /// manipulation.
+    ///
+    /// Vacant entries are inserted last in order, occupied entries retain their place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::expr::{Expression, ObjectValue};
+    /// use hcl_edit::Ident;
+    ///
+    /// let mut expr: Expression = "{ foo = 1 }".parse().unwrap();
+    /// let object = expr.as_object_mut().unwrap();
+    ///
+    /// // `foo` is already present, so its value is not replaced.
+    /// let foo = object.entry(Ident::new("foo")).or_insert(ObjectValue::new(2));
+    /// assert_eq!(foo.expr().as_number().unwrap().as_u64(), Some(1));
+    ///
+    /// object.entry(Ident::new("bar")).or_insert(ObjectValue::new(3));
+    ///
+    /// let keys: Vec<_> = object
+    ///     .iter()
+    ///     .filter_map(|(key, _)| key.as_ident())
+    ///     .map(|ident| ident.as_str())
+    ///     .collect();
+    ///
+    /// assert_eq!(keys, ["foo", "bar"]);
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: impl Into<ObjectKey>) -> ObjectEntry<'_> {
+        self.items.entry(key.into())
+    }
+
     /// Remove the key-value pair equivalent to `key` and return its value.
     ///
     /// Like `Vec::remove`, the pair is removed by shifting all of the elements that follow it,
@@ -159,6 +200,36 @@ impl Object {
         )
     }
 
+    /// Sets the terminator of all object values.
+    ///
+    /// Since object values must be separated from each other, [`ObjectValueTerminator::None`] is
+    /// only applied to the last value. All other values are terminated by a comma in this case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::expr::{Expression, ObjectValueTerminator};
+    ///
+    /// let mut expr: Expression = "{foo = 1, bar = 2}".parse().unwrap();
+    ///
+    /// expr.as_object_mut()
+    ///     .unwrap()
+    ///     .set_terminators(ObjectValueTerminator::Newline);
+    ///
+    /// assert_eq!(expr.to_string(), "{foo = 1\n bar = 2\n}");
+    /// ```
+    pub fn set_terminators(&mut self, terminator: ObjectValueTerminator) {
+        let len = self.len();
+
+        for (i, (_, value)) in self.iter_mut().enumerate() {
+            if terminator == ObjectValueTerminator::None && i + 1 < len {
+                value.set_terminator(ObjectValueTerminator::Comma);
+            } else {
+                value.set_terminator(terminator);
+            }
+        }
+    }
+
     /// Return a reference to raw trailing decor before the object's closing `}`.
     #[inline]
     pub fn trailing(&self) -> &RawString {
@@ -379,9 +450,13 @@ pub enum ObjectValueAssignment {
 }
 
 /// Represents the character that terminates an object value.
+///
+/// The terminator is written directly after the object value's expression when the object is
+/// encoded.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum ObjectValueTerminator {
-    /// No terminator.
+    /// No terminator. This is only valid for the last value of an object, because object values
+    /// must be separated by a comma or a newline.
     None,
     /// Newline terminated.
     Newline,
@@ -443,6 +518,14 @@ impl ObjectValue {
         self.terminator = terminator;
     }
 
+    /// Sets the object value terminator and returns the modified `ObjectValue`.
+    ///
+    /// See [`ObjectValueTerminator`] for the available terminators.
+    pub fn with_terminator(mut self, terminator: ObjectValueTerminator) -> ObjectValue {
+        self.set_terminator(terminator);
+        self
+    }
+
     pub(crate) fn despan(&mut self, input: &str) {
         self.expr.despan(input);
     }
@@ -506,4 +589,42 @@ mod tests {
         assert_eq!(key.decor().prefix(), Some(&RawString::from("/* prefix */")));
         assert_eq!(key.decor().suffix(), Some(&RawString::from("/* suffix */")));
     }
+
+    #[test]
+    fn object_terminators() {
+        let mut expr: Expression = "{foo = 1, bar = 2}".parse().unwrap();
+        let object = expr.as_object_mut().unwrap();
+
+        assert_eq!(
+            object
+                .iter()
+                .map(|(_, value)| value.terminator())
+                .collect::<Vec<_>>(),
+            [ObjectValueTerminator::Comma, ObjectValueTerminator::None]
+        );
+
+        object.set_terminators(ObjectValueTerminator::Newline);
+        assert_eq!(expr.to_string(), "{foo = 1\n bar = 2\n}");
+
+        // The result can be parsed again and keeps the terminators.
+        let mut reparsed: Expression = expr.to_string().parse().unwrap();
+        assert_eq!(reparsed.to_string(), expr.to_string());
+
+        // `None` is only applied to the last value.
+        let object = reparsed.as_object_mut().unwrap();
+        object.set_terminators(ObjectValueTerminator::None);
+        assert_eq!(reparsed.to_string(), "{foo = 1, bar = 2}");
+
+        // Terminators of individual values.
+        let mut object = Object::new();
+        object.insert(
+            Ident::new("foo"),
+            ObjectValue::new(1).with_terminator(ObjectValueTerminator::Newline),
+        );
+        object.insert(
+            Ident::new("bar"),
+            ObjectValue::new(2).with_terminator(ObjectValueTerminator::None),
+        );
+        assert_eq!(Expression::from(object).to_string(), "{foo = 1\nbar = 2}");
+    }
 }
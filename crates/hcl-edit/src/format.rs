@@ -0,0 +1,452 @@
+//! Reformat HCL documents while preserving comments.
+//!
+//! The [`Formatter`] normalizes the whitespace surrounding the structures of a [`Body`] in
+//! place:
+//!
+//! - structures and the comment lines preceding them are indented according to their nesting
+//!   level,
+//! - consecutive blank lines are collapsed into a single one,
+//! - trailing comments are separated from the structure by a single space,
+//! - attribute keys, block identifiers and block labels are separated by single spaces.
+//!
+//! Optionally, the [`FormatterBuilder::comment_style`] setting rewrites line comments to use
+//! either `#` or `//` consistently.
+//!
+//! Expressions, including any comments within them, are left untouched.
+//!
+//! # Disabling the formatter
+//!
+//! Regions of a body can be excluded from reformatting by surrounding them with
+//! `# hcl-fmt: off` and `# hcl-fmt: on` comment lines (`//`-style comments are recognized
+//! as well). Every structure following an `off` marker is preserved verbatim, including any
+//! nested blocks, until a structure preceded by an `on` marker is encountered. Regions are
+//! scoped to the body they appear in: a region which is not closed extends to the end of its
+//! body.
+//!
+//! # Example
+//!
+//! ```
+//! use hcl_edit::format;
+//! use hcl_edit::structure::Body;
+//!
+//! let input = r#"
+//! resource   "aws_s3_bucket"    "bucket" {
+//!       // The bucket name.
+//!     bucket =   "my-bucket"   // Must be unique.
+//!
+//!
+//!   // hcl-fmt: off
+//!   tags   =   {}
+//!   // hcl-fmt: on
+//!       acl = "private"
+//!     }
+//! "#;
+//!
+//! let expected = r#"
+//! resource "aws_s3_bucket" "bucket" {
+//!   // The bucket name.
+//!   bucket = "my-bucket" // Must be unique.
+//!
+//!   // hcl-fmt: off
+//!   tags   =   {}
+//!   // hcl-fmt: on
+//!   acl = "private"
+//! }
+//! "#;
+//!
+//! let mut body: Body = input.parse().unwrap();
+//! format::format_body(&mut body);
+//! assert_eq!(body.to_string(), expected);
+//! ```
+
+use crate::comment::{comments, is_inline_comment};
+use crate::structure::{Attribute, Block, Body, Structure};
+use crate::{Decor, Decorate, RawString};
+use std::borrow::Cow;
+
+const FMT_OFF: &str = "hcl-fmt: off";
+const FMT_ON: &str = "hcl-fmt: on";
+
+/// Reformats an HCL [`Body`] in place using the default [`Formatter`].
+///
+/// See the [module level documentation](crate::format) for the formatting rules.
+pub fn format_body(body: &mut Body) {
+    Formatter::default().format_body(body);
+}
+
+/// A formatter for normalizing the whitespace within HCL documents while preserving comments.
+///
+/// Use [`Formatter::builder`] to customize the formatter.
+#[derive(Debug, Clone)]
+pub struct Formatter<'a> {
+    indent: &'a str,
+    comment_style: Option<CommentStyle>,
+}
+
+impl Default for Formatter<'_> {
+    fn default() -> Self {
+        Formatter::builder().build()
+    }
+}
+
+impl<'a> Formatter<'a> {
+    /// Creates a new [`FormatterBuilder`] to start building a new `Formatter`.
+    pub fn builder() -> FormatterBuilder<'a> {
+        FormatterBuilder {
+            indent: "  ",
+            comment_style: None,
+        }
+    }
+
+    /// Reformats the structures of a [`Body`] in place.
+    ///
+    /// See the [module level documentation](crate::format) for the formatting rules.
+    pub fn format_body(&self, body: &mut Body) {
+        self.format_structures(body, "");
+
+        if let Some(suffix) = body.decor().suffix() {
+            let suffix = self.format_lines(suffix, "", "");
+            body.decor_mut().set_suffix(suffix);
+        }
+    }
+
+    fn format_structures(&self, body: &mut Body, indent: &str) {
+        let mut enabled = true;
+
+        for mut structure in body.iter_mut() {
+            let was_enabled = enabled;
+            let raw_prefix = structure.decor().prefix().map_or("", |prefix| prefix);
+            let mut split = 0;
+
+            for line in raw_prefix.split_inclusive('\n') {
+                if let Some(on) = fmt_directive(line) {
+                    enabled = on;
+                    split = line.as_ptr() as usize - raw_prefix.as_ptr() as usize + line.len();
+                }
+            }
+
+            // The comment lines up to and including the last directive belong to the region that
+            // was active before the directive, the remaining ones to the region that follows it.
+            let (head, tail) = raw_prefix.split_at(split);
+
+            let prefix = match (was_enabled, enabled) {
+                (false, false) => continue,
+                (true, false) => format!("{}{tail}", self.format_lines(head, indent, "")),
+                (false, true) => format!("{head}{}", self.format_lines(tail, indent, indent)),
+                (true, true) => self.format_lines(raw_prefix, indent, indent),
+            };
+
+            let decor = structure.decor_mut();
+            decor.set_prefix(prefix);
+
+            if !enabled {
+                continue;
+            }
+
+            let suffix = self.format_trailing(decor.suffix());
+            decor.set_suffix(suffix);
+
+            if let Some(mut attr) = structure.as_attribute_mut() {
+                format_key_decor(attr.key_decor_mut());
+                self.format_value_decor(attr.value_mut().decor_mut());
+            } else if let Some(block) = structure.as_block_mut() {
+                self.format_block(block, indent);
+            }
+        }
+    }
+
+    // Formats the decor within a block which is indented by `indent`. The decor prefix of the
+    // block itself is left untouched.
+    pub(crate) fn format_block(&self, block: &mut Block, indent: &str) {
+        format_key_decor(block.ident.decor_mut());
+
+        for label in &mut block.labels {
+            format_key_decor(label.decor_mut());
+        }
+
+        let body = &mut block.body;
+
+        if body.prefer_oneline() && (body.is_empty() || body.has_single_attribute()) {
+            if let Some(Structure::Attribute(attr)) = body.get_mut(0) {
+                self.format_oneline_attribute(attr);
+            } else {
+                let decor = body.decor_mut();
+                let prefix = self.format_trailing(decor.prefix());
+                let suffix = self.format_trailing(decor.suffix());
+                decor.set_prefix(prefix);
+                decor.set_suffix(suffix);
+            }
+
+            return;
+        }
+
+        let inner_indent = format!("{indent}{}", self.indent);
+        let decor = body.decor_mut();
+        let prefix = self.format_trailing(decor.prefix());
+        let suffix = self.format_lines(
+            decor.suffix().map_or("", |suffix| suffix),
+            &inner_indent,
+            indent,
+        );
+        decor.set_prefix(prefix);
+        decor.set_suffix(suffix);
+
+        self.format_structures(body, &inner_indent);
+    }
+
+    // Like `format_lines`, but additionally applies the configured comment style.
+    fn format_lines(&self, raw: &str, indent: &str, last_indent: &str) -> String {
+        self.apply_comment_style(format_lines(raw, indent, last_indent))
+    }
+
+    // Like `format_trailing`, but additionally applies the configured comment style.
+    fn format_trailing(&self, raw: Option<&RawString>) -> String {
+        self.apply_comment_style(format_trailing(raw))
+    }
+
+    fn format_value_decor(&self, decor: &mut Decor) {
+        let prefix = format_inline(decor.prefix().map_or("", |prefix| prefix));
+        let suffix = self.format_trailing(decor.suffix());
+        decor.set_prefix(prefix);
+        decor.set_suffix(suffix);
+    }
+
+    fn format_oneline_attribute(&self, attr: &mut Attribute) {
+        format_key_decor(attr.key.decor_mut());
+        self.format_value_decor(attr.value.decor_mut());
+
+        let decor = attr.decor_mut();
+        let prefix = format_inline(decor.prefix().map_or("", |prefix| prefix));
+        let suffix = format_inline(decor.suffix().map_or("", |suffix| suffix));
+        decor.set_prefix(prefix);
+        decor.set_suffix(suffix);
+    }
+
+    fn apply_comment_style(&self, formatted: String) -> String {
+        match self.comment_style {
+            Some(style) => match set_comment_style(&formatted, style) {
+                Cow::Borrowed(_) => formatted,
+                Cow::Owned(restyled) => restyled,
+            },
+            None => formatted,
+        }
+    }
+}
+
+/// The style of line comments.
+///
+/// See [`FormatterBuilder::comment_style`] for usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// Line comments starting with `#`. This is the style used by `terraform fmt`.
+    Hash,
+    /// Line comments starting with `//`.
+    DoubleSlash,
+}
+
+impl CommentStyle {
+    fn marker(self) -> &'static str {
+        match self {
+            CommentStyle::Hash => "#",
+            CommentStyle::DoubleSlash => "//",
+        }
+    }
+}
+
+/// A builder to create a [`Formatter`].
+///
+/// See the documentation of [`Formatter::builder`] for a usage example.
+#[derive(Debug, Clone)]
+pub struct FormatterBuilder<'a> {
+    indent: &'a str,
+    comment_style: Option<CommentStyle>,
+}
+
+impl<'a> FormatterBuilder<'a> {
+    /// Set the indent for indenting nested HCL structures.
+    ///
+    /// The default indentation is two spaces.
+    pub fn indent(mut self, indent: &'a str) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Rewrite all line comments to use the given comment style.
+    ///
+    /// Only the comment marker is replaced, the comment text and its position are preserved,
+    /// e.g. `// comment` becomes `# comment` with [`CommentStyle::Hash`]. Multi-line comments
+    /// (`/* ... */`), comments within expressions and comments within regions where the formatter
+    /// is disabled are left untouched.
+    ///
+    /// By default, line comments are kept as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::format::{CommentStyle, Formatter};
+    /// use hcl_edit::structure::Body;
+    ///
+    /// let mut body: Body = "// The region.\nregion = \"eu-west-1\" // Must support S3.\n"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// Formatter::builder()
+    ///     .comment_style(CommentStyle::Hash)
+    ///     .build()
+    ///     .format_body(&mut body);
+    ///
+    /// assert_eq!(
+    ///     body.to_string(),
+    ///     "# The region.\nregion = \"eu-west-1\" # Must support S3.\n"
+    /// );
+    /// ```
+    pub fn comment_style(mut self, style: CommentStyle) -> Self {
+        self.comment_style = Some(style);
+        self
+    }
+
+    /// Consumes the `FormatterBuilder` and turns it into a `Formatter`.
+    pub fn build(self) -> Formatter<'a> {
+        Formatter {
+            indent: self.indent,
+            comment_style: self.comment_style,
+        }
+    }
+}
+
+// Returns `Some(false)` for an `hcl-fmt: off` comment line, `Some(true)` for an `hcl-fmt: on`
+// comment line and `None` for anything else.
+fn fmt_directive(line: &str) -> Option<bool> {
+    let line = line.trim();
+    let comment = line.strip_prefix('#').or_else(|| line.strip_prefix("//"))?;
+
+    match comment.trim() {
+        FMT_OFF => Some(false),
+        FMT_ON => Some(true),
+        _ => None,
+    }
+}
+
+// Re-indents the comment lines contained in `raw` using `indent` and collapses consecutive blank
+// lines. The whitespace following the last newline is replaced with `last_indent`. Continuation
+// lines of multi-line comments are preserved verbatim.
+fn format_lines(raw: &str, indent: &str, last_indent: &str) -> String {
+    let mut lines: Vec<&str> = raw.split('\n').collect();
+    let last = lines.pop().unwrap_or_default();
+    let mut formatted = String::with_capacity(raw.len());
+    let mut prev_blank = false;
+
+    // The spans of multi-line comments, ordered by their position in `raw`.
+    let mut multiline_comments = comments(raw)
+        .filter(|(_, comment)| is_inline_comment(comment) && comment.contains('\n'))
+        .map(|(start, comment)| (start, start + comment.len()))
+        .peekable();
+
+    // Returns `true` if the line starting at `offset` is a continuation line of a multi-line
+    // comment. Must be called with increasing offsets.
+    let mut is_continuation = |offset: usize| {
+        while multiline_comments
+            .next_if(|&(_, end)| end < offset)
+            .is_some()
+        {}
+
+        multiline_comments
+            .peek()
+            .is_some_and(|&(start, _)| start < offset)
+    };
+
+    let mut offset = 0;
+
+    for line in lines {
+        let continuation = is_continuation(offset);
+        offset += line.len() + 1;
+
+        if continuation {
+            formatted.push_str(line.trim_end());
+            formatted.push('\n');
+            continue;
+        }
+
+        let line = line.trim();
+
+        if line.is_empty() {
+            if !prev_blank {
+                formatted.push('\n');
+            }
+
+            prev_blank = true;
+            continue;
+        }
+
+        prev_blank = false;
+        formatted.push_str(indent);
+        formatted.push_str(line);
+        formatted.push('\n');
+    }
+
+    if is_continuation(offset) {
+        formatted.push_str(last);
+    } else {
+        formatted.push_str(last_indent);
+        formatted.push_str(last.trim_start());
+    }
+
+    formatted
+}
+
+// Separates a trailing comment from the preceding item by a single space and strips trailing
+// whitespace.
+fn format_trailing(raw: Option<&RawString>) -> String {
+    match raw.map(|raw| raw.trim()) {
+        Some(comment) if !comment.is_empty() => format!(" {comment}"),
+        _ => String::new(),
+    }
+}
+
+// Surrounds inline comments with single spaces, or replaces the raw string with a single space
+// if it only contains whitespace.
+fn format_inline(raw: &str) -> String {
+    let comment = raw.trim();
+
+    if comment.is_empty() {
+        String::from(" ")
+    } else {
+        format!(" {comment} ")
+    }
+}
+
+fn format_key_decor(decor: &mut Decor) {
+    let suffix = format_inline(decor.suffix().map_or("", |suffix| suffix));
+    decor.set_suffix(suffix);
+}
+
+// Replaces the marker of all line comments in `formatted` with the marker of `style`.
+// `formatted` is expected to only consist of whitespace and comments.
+fn set_comment_style(formatted: &str, style: CommentStyle) -> Cow<'_, str> {
+    let marker = style.marker();
+    let mut restyled = String::new();
+    let mut pos = 0;
+
+    for (start, comment) in comments(formatted) {
+        if is_inline_comment(comment) || comment.starts_with(marker) {
+            continue;
+        }
+
+        let text = comment
+            .strip_prefix("//")
+            .or_else(|| comment.strip_prefix('#'))
+            .unwrap_or(comment);
+
+        restyled.push_str(&formatted[pos..start]);
+        restyled.push_str(marker);
+        restyled.push_str(text);
+        pos = start + comment.len();
+    }
+
+    if pos == 0 {
+        Cow::Borrowed(formatted)
+    } else {
+        restyled.push_str(&formatted[pos..]);
+        Cow::Owned(restyled)
+    }
+}
@@ -17,8 +17,12 @@ extern crate alloc;
 #[macro_use]
 mod macros;
 
+mod comment;
 mod encode;
 pub mod expr;
+pub mod format;
+mod literal;
+mod node;
 pub mod parser;
 mod raw_string;
 #[doc(hidden)]
@@ -29,6 +33,8 @@ mod util;
 pub mod visit;
 pub mod visit_mut;
 
+pub use self::literal::StringLiteral;
+pub use self::node::NodeRef;
 pub use self::raw_string::RawString;
 use self::repr::SetSpan;
 pub use self::repr::{Decor, Decorate, Decorated, Formatted, Span, Spanned};
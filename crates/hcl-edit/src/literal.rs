@@ -0,0 +1,96 @@
+//! Extraction of string literals.
+
+use crate::expr::{Array, Expression, ObjectKey, ObjectValue};
+use crate::structure::{Attribute, Block, Body};
+use crate::visit::Visit;
+use crate::{Decorated, Span, Spanned};
+use std::ops::Range;
+
+/// A string literal found in a [`Body`].
+///
+/// Values of this type are returned by [`Body::string_literals`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringLiteral {
+    /// The path of the nearest enclosing attribute, block or collection element.
+    ///
+    /// Path segments are block identifiers and labels, attribute keys, array indices and object
+    /// keys. Object keys which are neither identifiers nor string literals are represented by
+    /// their encoded expression.
+    pub path: Vec<String>,
+    /// The value of the string literal.
+    pub value: String,
+    /// The span of the string literal in the original input, if available.
+    pub span: Option<Range<usize>>,
+}
+
+pub(crate) fn string_literals(body: &Body) -> Vec<StringLiteral> {
+    let mut collector = StringLiteralCollector::default();
+    collector.visit_body(body);
+    collector.literals
+}
+
+#[derive(Default)]
+struct StringLiteralCollector {
+    path: Vec<String>,
+    literals: Vec<StringLiteral>,
+}
+
+impl StringLiteralCollector {
+    fn push_literal(&mut self, value: &str, span: Option<Range<usize>>) {
+        self.literals.push(StringLiteral {
+            path: self.path.clone(),
+            value: value.to_owned(),
+            span,
+        });
+    }
+
+    fn with_segments<F>(&mut self, segments: impl IntoIterator<Item = String>, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let len = self.path.len();
+        self.path.extend(segments);
+        f(self);
+        self.path.truncate(len);
+    }
+}
+
+impl Visit for StringLiteralCollector {
+    fn visit_attr(&mut self, node: &Attribute) {
+        let segment = node.key.as_str().to_owned();
+        self.with_segments([segment], |this| this.visit_expr(&node.value));
+    }
+
+    fn visit_block(&mut self, node: &Block) {
+        let segments = std::iter::once(node.ident.as_str().to_owned())
+            .chain(node.labels.iter().map(|label| label.as_str().to_owned()));
+
+        // Block labels are part of the path and not reported as literals.
+        self.with_segments(segments, |this| this.visit_body(&node.body));
+    }
+
+    fn visit_array(&mut self, node: &Array) {
+        for (index, expr) in node.iter().enumerate() {
+            self.with_segments([index.to_string()], |this| this.visit_expr(expr));
+        }
+    }
+
+    fn visit_object_item(&mut self, key: &ObjectKey, value: &ObjectValue) {
+        let segment = match key {
+            ObjectKey::Ident(ident) => ident.as_str().to_owned(),
+            ObjectKey::Expression(Expression::String(string)) => string.as_str().to_owned(),
+            ObjectKey::Expression(expr) => expr.to_string().trim().to_owned(),
+        };
+
+        // Object keys are part of the path and not reported as literals.
+        self.with_segments([segment], |this| this.visit_expr(value.expr()));
+    }
+
+    fn visit_string(&mut self, node: &Decorated<String>) {
+        self.push_literal(node.as_str(), node.span());
+    }
+
+    fn visit_literal(&mut self, node: &Spanned<String>) {
+        self.push_literal(node.as_str(), node.span());
+    }
+}
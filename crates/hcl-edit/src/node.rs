@@ -0,0 +1,189 @@
+//! Spatial lookup of language items by byte offset.
+
+use crate::expr::{Expression, ObjectKey, TraversalOperator};
+use crate::structure::{Attribute, Block, BlockLabel, Body, Structure};
+use crate::template::{Directive, Element, Template};
+use crate::{Decorated, Ident, Span};
+use std::ops::Range;
+
+/// A reference to a node of the HCL syntax tree.
+///
+/// Values of this type are returned by [`Body::node_at`] to identify the innermost node which
+/// covers a given byte offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeRef<'a> {
+    /// A reference to an attribute.
+    Attribute(&'a Attribute),
+    /// A reference to a block.
+    Block(&'a Block),
+    /// A reference to a block label.
+    BlockLabel(&'a BlockLabel),
+    /// A reference to an expression.
+    Expression(&'a Expression),
+    /// A reference to an identifier which is not an expression on its own, e.g. an attribute key,
+    /// a block identifier, a function name, an object key or a traversal attribute name.
+    Ident(&'a Decorated<Ident>),
+}
+
+impl<'a> NodeRef<'a> {
+    /// Returns the child node which covers `offset`, if any.
+    ///
+    /// Template literals and operators are not represented as nodes, the innermost node
+    /// covering them is the enclosing expression.
+    fn child_at(&self, offset: usize) -> Option<NodeRef<'a>> {
+        let mut children = Vec::new();
+
+        match *self {
+            NodeRef::Attribute(attr) => {
+                children.push(NodeRef::Ident(&attr.key));
+                children.push(NodeRef::Expression(&attr.value));
+            }
+            NodeRef::Block(block) => {
+                children.push(NodeRef::Ident(&block.ident));
+                children.extend(block.labels.iter().map(NodeRef::BlockLabel));
+                children.extend(block.body.iter().map(structure_ref));
+            }
+            NodeRef::Expression(expr) => push_expr_children(&mut children, expr),
+            NodeRef::BlockLabel(_) | NodeRef::Ident(_) => {}
+        }
+
+        children
+            .into_iter()
+            .find(|child| contains(child.span(), offset))
+    }
+}
+
+impl Span for NodeRef<'_> {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            NodeRef::Attribute(attr) => attr.span(),
+            NodeRef::Block(block) => block.span(),
+            NodeRef::BlockLabel(label) => label.span(),
+            NodeRef::Expression(expr) => expr.span(),
+            NodeRef::Ident(ident) => ident.span(),
+        }
+    }
+}
+
+pub(crate) fn node_at(body: &Body, offset: usize) -> Option<NodeRef<'_>> {
+    let mut node = body
+        .iter()
+        .map(structure_ref)
+        .find(|node| contains(node.span(), offset))?;
+
+    while let Some(child) = node.child_at(offset) {
+        node = child;
+    }
+
+    Some(node)
+}
+
+fn contains(span: Option<Range<usize>>, offset: usize) -> bool {
+    span.is_some_and(|span| span.contains(&offset))
+}
+
+fn structure_ref(structure: &Structure) -> NodeRef<'_> {
+    match structure {
+        Structure::Attribute(attr) => NodeRef::Attribute(attr),
+        Structure::Block(block) => NodeRef::Block(block),
+    }
+}
+
+fn push_expr_children<'a>(children: &mut Vec<NodeRef<'a>>, expr: &'a Expression) {
+    match expr {
+        Expression::Null(_)
+        | Expression::Bool(_)
+        | Expression::Number(_)
+        | Expression::String(_)
+        | Expression::Variable(_) => {}
+        Expression::Array(array) => children.extend(array.iter().map(NodeRef::Expression)),
+        Expression::Object(object) => {
+            for (key, value) in object {
+                children.push(match key {
+                    ObjectKey::Ident(ident) => NodeRef::Ident(ident),
+                    ObjectKey::Expression(expr) => NodeRef::Expression(expr),
+                });
+                children.push(NodeRef::Expression(value.expr()));
+            }
+        }
+        Expression::StringTemplate(template) => {
+            for element in template {
+                push_element_children(children, element);
+            }
+        }
+        Expression::HeredocTemplate(heredoc) => {
+            push_template_children(children, &heredoc.template);
+        }
+        Expression::Parenthesis(parens) => children.push(NodeRef::Expression(parens.inner())),
+        Expression::ForExpr(for_expr) => {
+            let intro = &for_expr.intro;
+            children.extend(intro.key_var.as_ref().map(NodeRef::Ident));
+            children.push(NodeRef::Ident(&intro.value_var));
+            children.push(NodeRef::Expression(&intro.collection_expr));
+            children.extend(for_expr.key_expr.as_ref().map(NodeRef::Expression));
+            children.push(NodeRef::Expression(&for_expr.value_expr));
+            children.extend(
+                for_expr
+                    .cond
+                    .as_ref()
+                    .map(|cond| NodeRef::Expression(&cond.expr)),
+            );
+        }
+        Expression::Conditional(cond) => {
+            children.push(NodeRef::Expression(&cond.cond_expr));
+            children.push(NodeRef::Expression(&cond.true_expr));
+            children.push(NodeRef::Expression(&cond.false_expr));
+        }
+        Expression::FuncCall(func_call) => {
+            children.extend(func_call.name.namespace.iter().map(NodeRef::Ident));
+            children.push(NodeRef::Ident(&func_call.name.name));
+            children.extend(func_call.args.iter().map(NodeRef::Expression));
+        }
+        Expression::UnaryOp(op) => children.push(NodeRef::Expression(&op.expr)),
+        Expression::BinaryOp(op) => {
+            children.push(NodeRef::Expression(&op.lhs_expr));
+            children.push(NodeRef::Expression(&op.rhs_expr));
+        }
+        Expression::Traversal(traversal) => {
+            children.push(NodeRef::Expression(&traversal.expr));
+
+            for operator in &traversal.operators {
+                match operator.value() {
+                    TraversalOperator::GetAttr(ident) => children.push(NodeRef::Ident(ident)),
+                    TraversalOperator::Index(expr) => children.push(NodeRef::Expression(expr)),
+                    TraversalOperator::AttrSplat(_)
+                    | TraversalOperator::FullSplat(_)
+                    | TraversalOperator::LegacyIndex(_) => {}
+                }
+            }
+        }
+    }
+}
+
+fn push_template_children<'a>(children: &mut Vec<NodeRef<'a>>, template: &'a Template) {
+    for element in template {
+        push_element_children(children, element);
+    }
+}
+
+fn push_element_children<'a>(children: &mut Vec<NodeRef<'a>>, element: &'a Element) {
+    match element {
+        Element::Literal(_) => {}
+        Element::Interpolation(interp) => children.push(NodeRef::Expression(&interp.expr)),
+        Element::Directive(Directive::If(dir)) => {
+            children.push(NodeRef::Expression(&dir.if_expr.cond_expr));
+            push_template_children(children, &dir.if_expr.template);
+
+            if let Some(else_expr) = &dir.else_expr {
+                push_template_children(children, &else_expr.template);
+            }
+        }
+        Element::Directive(Directive::For(dir)) => {
+            let for_expr = &dir.for_expr;
+            children.extend(for_expr.key_var.as_ref().map(NodeRef::Ident));
+            children.push(NodeRef::Ident(&for_expr.value_var));
+            children.push(NodeRef::Expression(&for_expr.collection_expr));
+            push_template_children(children, &for_expr.template);
+        }
+    }
+}
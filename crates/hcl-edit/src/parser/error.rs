@@ -37,6 +37,27 @@ impl Error {
     pub fn location(&self) -> &Location {
         &self.inner.location
     }
+
+    /// Returns the tokens or syntax elements that the parser expected at the error location.
+    ///
+    /// Literal tokens are returned as is, e.g. `}`, other syntax elements by their description,
+    /// e.g. `identifier` or `newline`. The list is empty if the parser did not expect anything
+    /// specific, e.g. for unterminated heredocs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::parser::parse_body;
+    ///
+    /// let err = parse_body("block {").unwrap_err();
+    ///
+    /// assert_eq!(err.expected(), ["}", "newline", "identifier"]);
+    /// assert_eq!(err.location().line(), 1);
+    /// assert_eq!(err.location().column(), 8);
+    /// ```
+    pub fn expected(&self) -> &[&str] {
+        &self.inner.expected
+    }
 }
 
 impl std::error::Error for Error {}
@@ -52,16 +73,26 @@ struct ErrorInner {
     message: String,
     line: String,
     location: Location,
+    expected: Vec<&'static str>,
 }
 
 impl ErrorInner {
     fn from_parse_error(err: &ParseError<Input, ContextError>) -> ErrorInner {
         let (line, location) = locate_error(err);
+        let expected = err
+            .inner()
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Expected(c) => Some(c),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
 
         ErrorInner {
-            message: format_context_error(err.inner()),
+            message: format_context_error(err.inner(), &expected),
             line: String::from_utf8_lossy(line).to_string(),
             location,
+            expected: expected.into_iter().filter_map(expected_str).collect(),
         }
     }
 
@@ -117,7 +148,7 @@ impl Location {
 
 fn locate_error<'a>(err: &'a ParseError<Input<'a>, ContextError>) -> (&'a [u8], Location) {
     let input = err.input().as_bytes();
-    let offset = err.offset().min(input.len() - 1);
+    let offset = err.offset().min(input.len().saturating_sub(1));
     let column_offset = err.offset() - offset;
 
     // Find the start of the line containing the error.
@@ -140,9 +171,12 @@ fn locate_error<'a>(err: &'a ParseError<Input<'a>, ContextError>) -> (&'a [u8],
     let line = input[..line_begin].iter().filter(|&&b| b == b'\n').count() + 1;
 
     // The (1-indexed) column number is the offset of the remaining input into that line.
-    // This also takes multi-byte unicode characters into account.
-    let column = std::str::from_utf8(&input[line_begin..=offset])
-        .map_or_else(|_| offset - line_begin + 1, |s| s.chars().count())
+    // This also takes multi-byte unicode characters into account. The range is out of bounds if
+    // the input is empty.
+    let column = input
+        .get(line_begin..=offset)
+        .and_then(|line| std::str::from_utf8(line).ok())
+        .map_or_else(|| offset - line_begin + 1, |s| s.chars().count())
         + column_offset;
 
     (
@@ -155,10 +189,93 @@ fn locate_error<'a>(err: &'a ParseError<Input<'a>, ContextError>) -> (&'a [u8],
     )
 }
 
+/// The cause of a parse error for heredocs which are missing their opening delimiter.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct MissingHeredocDelimiter {
+    indented: bool,
+}
+
+impl MissingHeredocDelimiter {
+    pub(super) fn new(indented: bool) -> MissingHeredocDelimiter {
+        MissingHeredocDelimiter { indented }
+    }
+}
+
+impl std::error::Error for MissingHeredocDelimiter {}
+
+impl fmt::Display for MissingHeredocDelimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = if self.indented { "<<-" } else { "<<" };
+
+        write!(
+            f,
+            "missing heredoc delimiter (`{start}` must be directly followed by an identifier, \
+             e.g. `{start}EOT`)"
+        )
+    }
+}
+
+/// The cause of a parse error for quoted attribute keys which are empty or contain template
+/// interpolations or directives.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct InvalidQuotedAttrKey;
+
+impl std::error::Error for InvalidQuotedAttrKey {}
+
+impl fmt::Display for InvalidQuotedAttrKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("quoted attribute key must be a non-empty literal string")
+    }
+}
+
+/// The cause of a parse error for heredocs which are missing their closing delimiter.
+#[derive(Debug, Clone)]
+pub(super) struct UnterminatedHeredoc {
+    delim: String,
+    line: usize,
+    indented: bool,
+}
+
+impl UnterminatedHeredoc {
+    pub(super) fn new(delim: &str, line: usize, indented: bool) -> UnterminatedHeredoc {
+        UnterminatedHeredoc {
+            delim: delim.to_owned(),
+            line,
+            indented,
+        }
+    }
+}
+
+impl std::error::Error for UnterminatedHeredoc {}
+
+impl fmt::Display for UnterminatedHeredoc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unterminated heredoc started at line {} with delimiter `{}`",
+            self.line, self.delim
+        )?;
+
+        if self.indented {
+            write!(
+                f,
+                " (the closing `{}` may be indented, but must be on a line of its own)",
+                self.delim
+            )
+        } else {
+            write!(
+                f,
+                " (the closing `{}` must be on a line of its own)",
+                self.delim
+            )
+        }
+    }
+}
+
 // This is almost identical to `ContextError::to_string` but produces a slightly different format
 // which does not contain line breaks and emits "unexpected token" when there was no expectation in
 // the context.
-fn format_context_error(err: &ContextError) -> String {
+fn format_context_error(err: &ContextError, expected: &[&StrContextValue]) -> String {
     let mut buf = String::new();
 
     let label = err.context().find_map(|c| match c {
@@ -166,20 +283,15 @@ fn format_context_error(err: &ContextError) -> String {
         _ => None,
     });
 
-    let expected = err
-        .context()
-        .filter_map(|c| match c {
-            StrContext::Expected(c) => Some(c),
-            _ => None,
-        })
-        .collect::<Vec<_>>();
-
     if let Some(label) = label {
         _ = write!(buf, "invalid {label}; ");
     }
 
     if expected.is_empty() {
-        _ = buf.write_str("unexpected token");
+        // The cause is more descriptive on its own.
+        if err.cause().is_none() {
+            _ = buf.write_str("unexpected token");
+        }
     } else {
         _ = write!(buf, "expected ");
 
@@ -203,8 +315,30 @@ fn format_context_error(err: &ContextError) -> String {
     }
 
     if let Some(cause) = err.cause() {
-        _ = write!(buf, "; {cause}");
+        if !expected.is_empty() {
+            _ = buf.write_str("; ");
+        }
+
+        _ = write!(buf, "{cause}");
     }
 
     buf
 }
+
+// Returns the unformatted token or description of an expected syntax element.
+fn expected_str(value: &StrContextValue) -> Option<&'static str> {
+    // The parser only expects printable ASCII characters apart from newlines. They are sliced
+    // from this table because a `char` cannot be borrowed as `&'static str` otherwise.
+    const PRINTABLE_ASCII: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@\
+                                   ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+    match *value {
+        StrContextValue::CharLiteral('\n') => Some("newline"),
+        StrContextValue::CharLiteral(c @ ' '..='~') => {
+            let index = c as usize - ' ' as usize;
+            PRINTABLE_ASCII.get(index..=index)
+        }
+        StrContextValue::StringLiteral(s) | StrContextValue::Description(s) => Some(s),
+        _ => None,
+    }
+}
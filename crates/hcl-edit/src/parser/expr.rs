@@ -1,5 +1,7 @@
 use super::prelude::*;
 
+use super::error::{MissingHeredocDelimiter, UnterminatedHeredoc};
+
 use super::number::number as num;
 use super::repr::{decorated, prefix_decorated, spanned, suffix_decorated};
 use super::state::ExprParseState;
@@ -22,6 +24,7 @@ use winnow::combinator::{
     alt, cut_err, delimited, empty, fail, not, opt, peek, preceded, repeat, separated,
     separated_pair, terminated,
 };
+use winnow::stream::Location;
 use winnow::token::{any, none_of, one_of, take};
 
 fn ws_or_sp<'i>(state: &RefCell<ExprParseState>) -> impl Parser<Input<'i>, (), ContextError> + '_ {
@@ -401,7 +404,7 @@ fn for_object_expr<'i>(
                     if let Some(ref mut cond) = expr.cond {
                         cond.decor_mut().set_prefix(trailing);
                     } else {
-                        expr.decor_mut().set_suffix(trailing);
+                        expr.set_trailing(trailing);
                     }
                 }
 
@@ -601,18 +604,21 @@ fn parenthesis<'i>(
 
 fn heredoc<'i>(state: &RefCell<ExprParseState>) -> impl Parser<Input<'i>, (), ContextError> + '_ {
     move |input: &mut Input<'i>| {
+        let start = input.checkpoint();
         let (indented, delim) = heredoc_start(input)?;
 
-        let (template, trailing) = (
-            spanned(heredoc_template(delim)),
-            terminated(
-                raw_string(space0),
-                cut_err(delim).context(StrContext::Expected(StrContextValue::Description(
-                    "heredoc end delimiter",
-                ))),
-            ),
-        )
-            .parse_next(input)?;
+        let (template, trailing) =
+            (spanned(heredoc_template(delim)), raw_string(space0)).parse_next(input)?;
+
+        if opt(delim).parse_next(input)?.is_none() {
+            // Report the error at the start of the heredoc instead of the end of the input, which
+            // is usually far away from the actual mistake.
+            input.reset(&start);
+
+            let err = UnterminatedHeredoc::new(delim, line_number(input), indented);
+
+            return cut_err(empty.try_map(move |()| Err::<(), _>(err.clone()))).parse_next(input);
+        }
 
         let mut heredoc = HeredocTemplate::new(Ident::new_unchecked(delim), template);
 
@@ -629,17 +635,29 @@ fn heredoc<'i>(state: &RefCell<ExprParseState>) -> impl Parser<Input<'i>, (), Co
     }
 }
 
+// Returns the (one-based) line number of the current input location.
+fn line_number(input: &Input) -> usize {
+    let offset = input.location();
+    let mut full = input.input;
+    full.reset_to_start();
+    full[..offset].matches('\n').count() + 1
+}
+
 fn heredoc_start<'a>(input: &mut Input<'a>) -> PResult<(bool, &'a str)> {
-    terminated(
-        (
-            preceded("<<", opt('-')).map(|indent| indent.is_some()),
-            cut_err(str_ident).context(StrContext::Expected(StrContextValue::Description(
-                "identifier",
-            ))),
-        ),
-        cut_err(line_ending).context(StrContext::Expected(StrContextValue::CharLiteral('\n'))),
-    )
-    .parse_next(input)
+    let indented = preceded("<<", opt('-'))
+        .map(|indent| indent.is_some())
+        .parse_next(input)?;
+
+    let Some(delim) = opt(str_ident).parse_next(input)? else {
+        let err = MissingHeredocDelimiter::new(indented);
+        return cut_err(empty.try_map(move |()| Err::<(bool, &str), _>(err))).parse_next(input);
+    };
+
+    cut_err(line_ending)
+        .context(StrContext::Expected(StrContextValue::CharLiteral('\n')))
+        .parse_next(input)?;
+
+    Ok((indented, delim))
 }
 
 fn identlike<'i>(state: &RefCell<ExprParseState>) -> impl Parser<Input<'i>, (), ContextError> + '_ {
@@ -656,7 +674,7 @@ fn identlike<'i>(state: &RefCell<ExprParseState>) -> impl Parser<Input<'i>, (),
             Ok(peeked) if peeked == "::" || peeked.starts_with('(') => {
                 // This is a function call: parsed identifier starts a function namespace, or function
                 // arguments follow.
-                let mut ident = Decorated::new(Ident::new_unchecked(ident));
+                let mut ident = Decorated::new(input.state.ident(ident));
                 ident.decor_mut().set_suffix(RawString::from_span(suffix));
                 ident.set_span(span);
 
@@ -686,7 +704,7 @@ fn identlike<'i>(state: &RefCell<ExprParseState>) -> impl Parser<Input<'i>, (),
                     "null" => Expression::null(),
                     "true" => Expression::from(true),
                     "false" => Expression::from(false),
-                    var => Expression::from(Ident::new_unchecked(var)),
+                    var => Expression::from(input.state.ident(var)),
                 }
             }
         };
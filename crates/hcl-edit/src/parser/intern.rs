@@ -0,0 +1,38 @@
+use hcl_primitives::InternalString;
+#[cfg(feature = "perf")]
+use std::cell::RefCell;
+#[cfg(feature = "perf")]
+use std::collections::HashSet;
+
+/// Deduplicates the identifiers encountered during a single parse.
+///
+/// With the `perf` feature enabled, identifiers which occur repeatedly share the same storage:
+/// short identifiers are stored inline anyways, and longer ones are reference counted, so that
+/// interning them saves an allocation for every repetition. Without the `perf` feature, every
+/// `InternalString` owns its data and interning is a no-op.
+#[derive(Debug, Default)]
+pub(super) struct Interner {
+    #[cfg(feature = "perf")]
+    strings: RefCell<HashSet<InternalString>>,
+}
+
+impl Interner {
+    #[cfg(feature = "perf")]
+    pub(super) fn intern(&self, s: &str) -> InternalString {
+        let mut strings = self.strings.borrow_mut();
+
+        if let Some(interned) = strings.get(s) {
+            return interned.clone();
+        }
+
+        let interned = InternalString::from(s);
+        strings.insert(interned.clone());
+        interned
+    }
+
+    #[cfg(not(feature = "perf"))]
+    #[allow(clippy::unused_self)]
+    pub(super) fn intern(&self, s: &str) -> InternalString {
+        InternalString::from(s)
+    }
+}
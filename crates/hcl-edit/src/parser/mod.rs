@@ -2,6 +2,7 @@
 
 mod error;
 mod expr;
+mod intern;
 mod number;
 mod repr;
 mod state;
@@ -14,62 +15,301 @@ mod trivia;
 
 pub use self::error::{Error, Location};
 use self::expr::expr;
-use self::structure::body;
+use self::intern::Interner;
+use self::structure::{attribute, block, body};
 use self::template::template;
 use crate::expr::Expression;
-use crate::structure::Body;
+use crate::structure::{Attribute, Block, Body};
 use crate::template::Template;
+use crate::Ident;
+use std::rc::Rc;
 
 mod prelude {
     pub(super) use winnow::error::{ContextError, StrContext, StrContextValue};
     pub(super) use winnow::stream::Stream;
     pub(super) use winnow::{dispatch, PResult, Parser};
 
-    pub(super) type Input<'a> = winnow::stream::Located<&'a str>;
+    pub(super) type Input<'a> =
+        winnow::stream::Stateful<winnow::stream::Located<&'a str>, super::InputState<'a>>;
+
+    #[cfg(test)]
+    pub(super) fn new_input(input: &str) -> Input<'_> {
+        super::new_input(input, &super::DEFAULT_OPTIONS)
+    }
 }
 
 use self::prelude::*;
 
+static DEFAULT_OPTIONS: ParseOptions = ParseOptions::new();
+
 /// Parse an input into a [`Body`].
 ///
+/// This uses the default [`ParseOptions`] which only accept strict HCL.
+///
 /// # Errors
 ///
 /// Returns an error if the input does not resemble a valid HCL body.
 pub fn parse_body(input: &str) -> Result<Body, Error> {
-    let mut body = parse_complete(input, body)?;
-    body.despan(input);
-    Ok(body)
+    DEFAULT_OPTIONS.parse_body(input)
+}
+
+/// Parse an input consisting of exactly one attribute into an [`Attribute`].
+///
+/// This uses the default [`ParseOptions`] which only accept strict HCL.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a single valid HCL attribute. This includes inputs
+/// containing more than one structure.
+pub fn parse_attribute(input: &str) -> Result<Attribute, Error> {
+    DEFAULT_OPTIONS.parse_attribute(input)
+}
+
+/// Parse an input consisting of exactly one block into a [`Block`].
+///
+/// This uses the default [`ParseOptions`] which only accept strict HCL.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a single valid HCL block. This includes inputs
+/// containing more than one structure.
+pub fn parse_block(input: &str) -> Result<Block, Error> {
+    DEFAULT_OPTIONS.parse_block(input)
 }
 
 /// Parse an input into an [`Expression`].
 ///
+/// This uses the default [`ParseOptions`] which only accept strict HCL.
+///
 /// # Errors
 ///
 /// Returns an error if the input does not resemble a valid HCL expression.
 pub fn parse_expr(input: &str) -> Result<Expression, Error> {
-    let mut expr = parse_complete(input, expr)?;
-    expr.despan(input);
-    Ok(expr)
+    DEFAULT_OPTIONS.parse_expr(input)
 }
 
 /// Parse an input into a [`Template`].
 ///
+/// This uses the default [`ParseOptions`] which only accept strict HCL.
+///
 /// # Errors
 ///
 /// Returns an error if the input does not resemble a valid HCL template.
 pub fn parse_template(input: &str) -> Result<Template, Error> {
-    let mut template = parse_complete(input, template)?;
-    template.despan(input);
-    Ok(template)
+    DEFAULT_OPTIONS.parse_template(input)
+}
+
+/// Options to customize the HCL parser for dialects which deviate from the HCL specification.
+///
+/// The default options only accept strict HCL.
+///
+/// # Extended identifier characters
+///
+/// HCL identifiers start with a letter or `_` and may continue with letters, digits, `-` and `_`.
+/// Some configuration dialects allow additional characters within identifiers, e.g. `:` or `.`.
+/// [`ParseOptions::extra_ident_chars`] adds such characters to the set of characters which may
+/// continue an identifier. The first character of an identifier is unaffected.
+///
+/// Extending the identifier character set changes how otherwise valid HCL is parsed, because the
+/// parser greedily consumes every allowed character into the identifier:
+///
+/// - With `.` enabled, `var.name` is parsed as a single variable instead of a traversal.
+/// - With `:` enabled, `cond ? a:b` no longer parses as a conditional because `a:b` is an
+///   identifier, and function namespaces like `provider::func()` cannot be recognized anymore.
+///
+/// Only enable characters that the dialect actually needs and surround the ambiguous operators
+/// with whitespace where both are used.
+///
+/// # Quoted attribute keys
+///
+/// The HCL specification only permits identifiers as attribute keys. Some configurations which
+/// were converted from other formats contain quoted attribute keys like `"region" = "eu-west-1"`
+/// instead. [`ParseOptions::quoted_attr_keys`] makes the parser accept these.
+///
+/// The quoted string must not contain template interpolations or directives. The unescaped
+/// string is used as attribute key, so `"region" = 1` and `region = 1` define the same attribute
+/// and are rejected as redefinition when used within the same body. Keys do not need to be valid
+/// identifiers in this mode. Attributes with quoted keys are marked via
+/// [`Attribute::set_quoted_key`], so that their keys are emitted quoted again when the body is
+/// converted back into a string.
+///
+/// # Example
+///
+/// ```
+/// use hcl_edit::parser::ParseOptions;
+///
+/// let input = "aws:region = \"eu-west-1\"";
+///
+/// assert!(hcl_edit::parser::parse_body(input).is_err());
+///
+/// let body = ParseOptions::new()
+///     .extra_ident_chars([':'])
+///     .parse_body(input)
+///     .unwrap();
+///
+/// assert!(body.has_attribute("aws:region"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    extra_ident_chars: Vec<char>,
+    quoted_attr_keys: bool,
+}
+
+impl ParseOptions {
+    /// Creates the default `ParseOptions` which only accept strict HCL.
+    pub const fn new() -> ParseOptions {
+        ParseOptions {
+            extra_ident_chars: Vec::new(),
+            quoted_attr_keys: false,
+        }
+    }
+
+    /// Adds characters which are allowed to continue an identifier in addition to the ones
+    /// permitted by the HCL specification.
+    ///
+    /// See the [type level documentation](ParseOptions#extended-identifier-characters) for the
+    /// ambiguities this may introduce.
+    pub fn extra_ident_chars<I>(mut self, chars: I) -> ParseOptions
+    where
+        I: IntoIterator<Item = char>,
+    {
+        self.extra_ident_chars.extend(chars);
+        self
+    }
+
+    /// Configures whether attribute keys may be quoted strings, e.g. `"region" = "eu-west-1"`.
+    ///
+    /// This deviates from the HCL specification and is disabled by default. See the
+    /// [type level documentation](ParseOptions#quoted-attribute-keys) for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::parser::ParseOptions;
+    ///
+    /// let input = "\"aws:region\" = \"eu-west-1\"\n";
+    ///
+    /// assert!(hcl_edit::parser::parse_body(input).is_err());
+    ///
+    /// let body = ParseOptions::new()
+    ///     .quoted_attr_keys(true)
+    ///     .parse_body(input)
+    ///     .unwrap();
+    ///
+    /// assert!(body.has_attribute("aws:region"));
+    /// assert_eq!(body.to_string(), input);
+    /// ```
+    pub fn quoted_attr_keys(mut self, yes: bool) -> ParseOptions {
+        self.quoted_attr_keys = yes;
+        self
+    }
+
+    /// Parse an input into a [`Body`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input does not resemble a valid HCL body.
+    pub fn parse_body(&self, input: &str) -> Result<Body, Error> {
+        let mut body = parse_complete_with(input, self, body)?;
+        body.despan(input);
+        Ok(body)
+    }
+
+    /// Parse an input consisting of exactly one attribute into an [`Attribute`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a single valid HCL attribute.
+    pub fn parse_attribute(&self, input: &str) -> Result<Attribute, Error> {
+        let mut attr = parse_complete_with(input, self, attribute)?;
+        attr.despan(input);
+        Ok(attr)
+    }
+
+    /// Parse an input consisting of exactly one block into a [`Block`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a single valid HCL block.
+    pub fn parse_block(&self, input: &str) -> Result<Block, Error> {
+        let mut block = parse_complete_with(input, self, block)?;
+        block.despan(input);
+        Ok(block)
+    }
+
+    /// Parse an input into an [`Expression`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input does not resemble a valid HCL expression.
+    pub fn parse_expr(&self, input: &str) -> Result<Expression, Error> {
+        let mut expr = parse_complete_with(input, self, expr)?;
+        expr.despan(input);
+        Ok(expr)
+    }
+
+    /// Parse an input into a [`Template`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input does not resemble a valid HCL template.
+    pub fn parse_template(&self, input: &str) -> Result<Template, Error> {
+        let mut template = parse_complete_with(input, self, template)?;
+        template.despan(input);
+        Ok(template)
+    }
+
+    pub(super) fn is_extra_ident_char(&self, ch: char) -> bool {
+        self.extra_ident_chars.contains(&ch)
+    }
 }
 
-fn parse_complete<'a, P, O>(input: &'a str, mut parser: P) -> Result<O, Error>
+#[cfg(test)]
+fn parse_complete<'a, P, O>(input: &'a str, parser: P) -> Result<O, Error>
 where
     P: Parser<Input<'a>, O, ContextError>,
 {
-    let input = Input::new(input);
+    parse_complete_with(input, &DEFAULT_OPTIONS, parser)
+}
 
+fn parse_complete_with<'a, P, O>(
+    input: &'a str,
+    options: &'a ParseOptions,
+    mut parser: P,
+) -> Result<O, Error>
+where
+    P: Parser<Input<'a>, O, ContextError>,
+{
     parser
-        .parse(input)
+        .parse(new_input(input, options))
         .map_err(|err| Error::from_parse_error(&err))
 }
+
+fn new_input<'a>(input: &'a str, options: &'a ParseOptions) -> Input<'a> {
+    Input {
+        input: winnow::stream::Located::new(input),
+        state: InputState {
+            options,
+            interner: Rc::default(),
+        },
+    }
+}
+
+// State which is shared by all parsers during a single parse.
+#[derive(Debug, Clone)]
+pub(super) struct InputState<'a> {
+    options: &'a ParseOptions,
+    interner: Rc<Interner>,
+}
+
+impl<'a> InputState<'a> {
+    fn options(&self) -> &'a ParseOptions {
+        self.options
+    }
+
+    // Creates an identifier, reusing the storage of previously encountered identical identifiers
+    // if possible.
+    fn ident(&self, ident: &str) -> Ident {
+        Ident::new_unchecked(self.interner.intern(ident))
+    }
+}
@@ -5,11 +5,12 @@ use crate::expr::{
 use crate::structure::{Body, Structure};
 use crate::{Decorate, Decorated, RawString, SetSpan, Spanned};
 use fnv::FnvHashSet;
+use std::borrow::Cow;
 use std::ops::Range;
 
 #[derive(Debug, Default)]
 pub(super) struct BodyParseState<'a> {
-    attribute_keys: FnvHashSet<&'a str>,
+    attribute_keys: FnvHashSet<Cow<'a, str>>,
     current: Option<Structure>,
     structures: Vec<Structure>,
     ws: Option<Range<usize>>,
@@ -17,8 +18,8 @@ pub(super) struct BodyParseState<'a> {
 }
 
 impl<'a> BodyParseState<'a> {
-    pub(super) fn is_redefined(&mut self, key: &'a str) -> bool {
-        !self.attribute_keys.insert(key)
+    pub(super) fn is_redefined(&mut self, key: impl Into<Cow<'a, str>>) -> bool {
+        !self.attribute_keys.insert(key.into())
     }
 
     pub(super) fn on_ws(&mut self, span: Range<usize>) {
@@ -179,13 +179,19 @@ where
 }
 
 pub(super) fn ident(input: &mut Input) -> PResult<Decorated<Ident>> {
-    str_ident
-        .map(|ident| Decorated::new(Ident::new_unchecked(ident)))
-        .parse_next(input)
+    let ident = str_ident.parse_next(input)?;
+    Ok(Decorated::new(input.state.ident(ident)))
 }
 
 pub(super) fn str_ident<'a>(input: &mut Input<'a>) -> PResult<&'a str> {
-    (one_of(is_id_start), take_while(0.., is_id_continue))
+    let options = input.state.options();
+
+    (
+        one_of(is_id_start),
+        take_while(0.., |ch| {
+            is_id_continue(ch) || options.is_extra_ident_char(ch)
+        }),
+    )
         .take()
         .parse_next(input)
 }
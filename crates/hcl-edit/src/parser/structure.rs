@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+use super::error::InvalidQuotedAttrKey;
 use super::expr::expr;
 use super::repr::{decorated, prefix_decorated, suffix_decorated};
 use super::state::BodyParseState;
@@ -8,13 +9,13 @@ use super::trivia::{line_comment, sp, void, ws};
 
 use crate::expr::Expression;
 use crate::structure::{Attribute, Block, BlockLabel, Body, Structure};
-use crate::{Decorate, Decorated, Ident, SetSpan};
+use crate::{Decorate, Decorated, Ident, SetSpan, Span};
 
 use hcl_primitives::ident::is_id_start;
 use std::cell::RefCell;
 use winnow::ascii::line_ending;
 use winnow::combinator::{
-    alt, cut_err, delimited, eof, fail, opt, peek, preceded, repeat, terminated,
+    alt, cut_err, delimited, empty, eof, fail, opt, peek, preceded, repeat, terminated,
 };
 use winnow::stream::Location;
 use winnow::token::{any, one_of};
@@ -54,12 +55,66 @@ pub(super) fn body(input: &mut Input) -> PResult<Body> {
     Ok(body)
 }
 
+pub(super) fn attribute(input: &mut Input) -> PResult<Attribute> {
+    single_structure(input, "attribute", Structure::into_attribute)
+}
+
+pub(super) fn block(input: &mut Input) -> PResult<Block> {
+    single_structure(input, "block", Structure::into_block)
+}
+
+// Parses a body which must consist of exactly one structure of the kind that `f` accepts.
+fn single_structure<O>(
+    input: &mut Input,
+    expected: &'static str,
+    f: fn(Structure) -> Result<O, Structure>,
+) -> PResult<O> {
+    let checkpoint = input.checkpoint();
+    let start = input.location();
+    let mut structures = body(input)?.into_iter();
+
+    let (offset, expected) = match (structures.next(), structures.next()) {
+        (Some(structure), None) => match f(structure) {
+            Ok(output) => return Ok(output),
+            Err(structure) => (structure.span().map_or(start, |span| span.start), expected),
+        },
+        (Some(_), Some(next)) => (next.span().map_or(start, |span| span.start), "end of input"),
+        (None, _) => (input.location(), expected),
+    };
+
+    // Move the input to the location of the offending structure for better error reporting.
+    input.reset(&checkpoint);
+    input.next_slice(offset - start);
+
+    cut_err(fail)
+        .context(StrContext::Expected(StrContextValue::Description(expected)))
+        .parse_next(input)
+}
+
 fn structure<'i, 's>(
     state: &'s RefCell<BodyParseState<'i>>,
 ) -> impl Parser<Input<'i>, (), ContextError> + 's {
     move |input: &mut Input<'i>| {
         let start = input.location();
         let checkpoint = input.checkpoint();
+
+        if let Some(mut key) = opt(quoted_attr_key).parse_next(input)? {
+            if state.borrow_mut().is_redefined(key.to_string()) {
+                input.reset(&checkpoint);
+                return redefined_attribute(input);
+            }
+
+            key.decor_mut()
+                .set_suffix(raw_string(sp).parse_next(input)?);
+            let mut attr = Attribute::new(key, attribute_expr(input)?);
+            attr.quote_key();
+
+            let mut structure = Structure::Attribute(attr);
+            structure.set_span(start..input.location());
+            state.borrow_mut().on_structure(structure);
+            return Ok(());
+        }
+
         peek(one_of(is_id_start)).parse_next(input)?;
         let (ident, ident_span) = cut_str_ident.with_span().parse_next(input)?;
         let suffix = raw_string(sp).parse_next(input)?;
@@ -68,16 +123,11 @@ fn structure<'i, 's>(
             '=' => {
                 if state.borrow_mut().is_redefined(ident) {
                     input.reset(&checkpoint);
-                    return cut_err(fail)
-                        .context(StrContext::Label("attribute"))
-                        .context(StrContext::Expected(StrContextValue::Description(
-                            "unique attribute key; found redefined attribute",
-                        )))
-                        .parse_next(input);
+                    return redefined_attribute(input);
                 }
 
                 let expr = attribute_expr(input)?;
-                let mut ident = Decorated::new(Ident::new_unchecked(ident));
+                let mut ident = Decorated::new(input.state.ident(ident));
                 ident.decor_mut().set_suffix(suffix);
                 ident.set_span(ident_span);
                 let attr = Attribute::new(ident, expr);
@@ -85,7 +135,7 @@ fn structure<'i, 's>(
             }
             '{' => {
                 let body = block_body(input)?;
-                let mut ident = Decorated::new(Ident::new_unchecked(ident));
+                let mut ident = Decorated::new(input.state.ident(ident));
                 ident.decor_mut().set_suffix(suffix);
                 ident.set_span(ident_span);
                 let mut block = Block::new(ident);
@@ -94,8 +144,19 @@ fn structure<'i, 's>(
             }
             ch if ch == '"' || is_id_start(ch) => {
                 let labels = block_labels(input)?;
+
+                if peek(opt('{')).parse_next(input)?.is_none() {
+                    return cut_err(fail)
+                        .context(StrContext::Label("block"))
+                        .context(StrContext::Expected(StrContextValue::Description(
+                            "block label (string or identifier)",
+                        )))
+                        .context(StrContext::Expected(StrContextValue::CharLiteral('{')))
+                        .parse_next(input);
+                }
+
                 let body = block_body(input)?;
-                let mut ident = Decorated::new(Ident::new_unchecked(ident));
+                let mut ident = Decorated::new(input.state.ident(ident));
                 ident.decor_mut().set_suffix(suffix);
                 ident.set_span(ident_span);
                 let mut block = Block::new(ident);
@@ -123,6 +184,43 @@ fn structure<'i, 's>(
     }
 }
 
+fn redefined_attribute(input: &mut Input) -> PResult<()> {
+    cut_err(fail)
+        .context(StrContext::Label("attribute"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "unique attribute key; found redefined attribute",
+        )))
+        .parse_next(input)
+}
+
+// Parses a quoted attribute key. This is only permitted if enabled via
+// `ParseOptions::quoted_attr_keys` and fails with a backtrack error otherwise.
+//
+// The key is not required to be a valid identifier. Attributes with such keys can't be unquoted
+// via `Attribute::set_quoted_key`, so they are always encoded as quoted strings. Empty keys and
+// keys containing template interpolations or directives are rejected.
+fn quoted_attr_key(input: &mut Input) -> PResult<Decorated<Ident>> {
+    if !input.state.options().quoted_attr_keys {
+        return fail.parse_next(input);
+    }
+
+    let checkpoint = input.checkpoint();
+    peek('"').parse_next(input)?;
+
+    match opt(string.with_span()).parse_next(input)? {
+        Some((key, span)) if !key.is_empty() => {
+            let mut key = Decorated::new(Ident::new_unchecked(key));
+            key.set_span(span);
+            Ok(key)
+        }
+        _ => {
+            input.reset(&checkpoint);
+            cut_err(empty.try_map(|()| Err::<Decorated<Ident>, _>(InvalidQuotedAttrKey)))
+                .parse_next(input)
+        }
+    }
+}
+
 fn attribute_expr(input: &mut Input) -> PResult<Expression> {
     preceded(
         cut_char('=').context(StrContext::Label("attribute")),
@@ -135,17 +233,28 @@ fn block_labels(input: &mut Input) -> PResult<Vec<BlockLabel>> {
     repeat(0.., suffix_decorated(block_label, sp)).parse_next(input)
 }
 
+// Block labels are either quoted strings or identifiers. Template interpolations are not
+// evaluated in labels, and other expressions are not allowed at all.
 fn block_label(input: &mut Input) -> PResult<BlockLabel> {
-    alt((
-        string.map(|string| BlockLabel::String(Decorated::new(string))),
-        ident.map(BlockLabel::Ident),
-    ))
+    dispatch! {peek(any);
+        '"' => cut_err(string)
+            .context(StrContext::Label("block label"))
+            .context(StrContext::Expected(StrContextValue::CharLiteral('"')))
+            .map(|string| BlockLabel::String(Decorated::new(string))),
+        _ => ident.map(BlockLabel::Ident),
+    }
     .parse_next(input)
 }
 
 fn block_body(input: &mut Input) -> PResult<Body> {
-    let attribute =
-        (suffix_decorated(ident, sp), attribute_expr).map(|(key, expr)| Attribute::new(key, expr));
+    let attribute = alt((
+        (suffix_decorated(quoted_attr_key, sp), attribute_expr).map(|(key, expr)| {
+            let mut attr = Attribute::new(key, expr);
+            attr.quote_key();
+            attr
+        }),
+        (suffix_decorated(ident, sp), attribute_expr).map(|(key, expr)| Attribute::new(key, expr)),
+    ));
 
     delimited(
         cut_char('{'),
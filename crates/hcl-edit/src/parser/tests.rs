@@ -2,7 +2,9 @@ use super::expr::expr;
 use super::parse_complete;
 use super::structure::body;
 use super::template::template;
+use crate::template::{Directive, Element};
 use crate::{expr::Expression, Formatted, Number};
+use hcl_primitives::template::Strip;
 use indoc::indoc;
 use pretty_assertions::assert_eq;
 
@@ -21,6 +23,30 @@ fn number_expr() {
     assert_eq!(parsed, expected);
 }
 
+#[test]
+fn comparison_operators() {
+    use crate::expr::BinaryOperator::*;
+
+    let cases = [
+        ("==", Eq),
+        ("!=", NotEq),
+        ("<", Less),
+        ("<=", LessEq),
+        (">", Greater),
+        (">=", GreaterEq),
+    ];
+
+    for (op, expected) in cases {
+        for input in [format!("a {op} b"), format!("a{op}b"), format!("1{op}-1")] {
+            let Expression::BinaryOp(binary_op) = parse_complete(&input, expr).unwrap() else {
+                panic!("expected binary operation for `{input}`");
+            };
+
+            assert_eq!(*binary_op.operator.value(), expected, "input: `{input}`");
+        }
+    }
+}
+
 #[test]
 fn roundtrip_expr() {
     let inputs = [
@@ -131,6 +157,55 @@ fn roundtrip_template() {
     }
 }
 
+#[test]
+fn roundtrip_template_strip_markers() {
+    let combinations = [
+        ("", "", Strip::None),
+        ("~", "", Strip::Start),
+        ("", "~", Strip::End),
+        ("~", "~", Strip::Both),
+    ];
+
+    for (start, end, strip) in combinations {
+        for input in [
+            format!("a ${{{start} x {end}}} b"),
+            format!("a ${{{start}x{end}}} b"),
+        ] {
+            assert_roundtrip!(input.as_str(), template);
+
+            let parsed = parse_complete(&input, template).unwrap();
+            let Some(Element::Interpolation(interp)) = parsed.iter().nth(1) else {
+                panic!("expected interpolation in `{input}`");
+            };
+            assert_eq!(interp.strip, strip, "{input}");
+        }
+
+        for input in [
+            format!(
+                "a %{{{start} if x {end}}} y %{{{start} else {end}}} n %{{{start} endif {end}}} b"
+            ),
+            format!("a %{{{start}if x{end}}} y %{{{start}else{end}}} n %{{{start}endif{end}}} b"),
+        ] {
+            assert_roundtrip!(input.as_str(), template);
+
+            let parsed = parse_complete(&input, template).unwrap();
+            let Some(Element::Directive(directive)) = parsed.iter().nth(1) else {
+                panic!("expected directive in `{input}`");
+            };
+            let Directive::If(dir) = directive else {
+                panic!("expected if directive in `{input}`");
+            };
+            assert_eq!(dir.if_expr.strip, strip, "{input}");
+            assert_eq!(dir.else_expr.as_ref().unwrap().strip, strip, "{input}");
+            assert_eq!(dir.endif_expr.strip, strip, "{input}");
+        }
+    }
+
+    // Strip markers on the individual parts of a directive are independent of each other.
+    assert_roundtrip!("%{~ if x }y%{ else ~}n%{~endif}", template);
+    assert_roundtrip!("%{ for k, v in xs ~}${~ v }%{~ endfor }", template);
+}
+
 #[test]
 fn invalid_exprs() {
     let inputs = [
@@ -148,3 +223,75 @@ fn invalid_exprs() {
         );
     }
 }
+
+#[test]
+fn roundtrip_for_exprs() {
+    let inputs = [
+        "[for x in xs : x]",
+        "[ for i, x in xs : x if x != null ]",
+        "{for k, v in m : k => v}",
+        "{ for k, v in m : v => k... }",
+        "{for k, v in m : v => k ...}",
+        "{ for k, v in m : v => k... if k != \"\" }",
+        "{ for k, v in m : v => k... /* grouped */ }",
+        "{\n  for k, v in m :\n  v => k...\n  if v\n}",
+    ];
+
+    for input in inputs {
+        assert_roundtrip!(input, expr);
+        assert_roundtrip!(&format!("attr = {input}\n"), body);
+    }
+
+    let parsed = parse_complete("[for x in xs : x]", expr).unwrap();
+    let Expression::ForExpr(for_expr) = parsed else {
+        panic!("expected for expression");
+    };
+    assert!(for_expr.key_expr.is_none());
+
+    let parsed = parse_complete("{for k, v in m : v => k...}", expr).unwrap();
+    let Expression::ForExpr(for_expr) = parsed else {
+        panic!("expected for expression");
+    };
+    assert!(for_expr.key_expr.is_some());
+    assert!(for_expr.grouping);
+}
+
+#[test]
+#[cfg(feature = "perf")]
+fn interned_identifiers() {
+    let input = indoc! {r#"
+        a_very_long_block_identifier "x" {
+          a_very_long_attribute_name = 1
+        }
+        a_very_long_block_identifier "y" {
+          a_very_long_attribute_name = a_very_long_attribute_name
+        }
+    "#};
+
+    let body = parse_complete(input, body).unwrap();
+    let blocks: Vec<_> = body.get_blocks("a_very_long_block_identifier").collect();
+    let attrs: Vec<_> = blocks
+        .iter()
+        .map(|block| {
+            block
+                .body
+                .get_attribute("a_very_long_attribute_name")
+                .unwrap()
+        })
+        .collect();
+
+    // Repeated identifiers share the same storage.
+    assert_eq!(
+        blocks[0].ident.as_str().as_ptr(),
+        blocks[1].ident.as_str().as_ptr()
+    );
+    assert_eq!(
+        attrs[0].key.as_str().as_ptr(),
+        attrs[1].key.as_str().as_ptr()
+    );
+
+    let Expression::Variable(var) = attrs[1].value.clone() else {
+        panic!("expected variable");
+    };
+    assert_eq!(var.as_str().as_ptr(), attrs[0].key.as_str().as_ptr());
+}
@@ -137,6 +137,31 @@ pub trait Decorate {
         self.decorate(decor);
         self
     }
+
+    /// Replaces the object's [`Decor`] with a copy of the decor of `other`.
+    ///
+    /// This is useful for preserving surrounding whitespace and comments when replacing a node
+    /// with a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::expr::Expression;
+    /// use hcl_edit::structure::Body;
+    /// use hcl_edit::Decorate;
+    ///
+    /// let mut body: Body = "count = /* instances */ 1 // keep small\n".parse().unwrap();
+    /// let mut attr = body.get_attribute_mut("count").unwrap();
+    ///
+    /// let mut expr = Expression::from(2);
+    /// expr.copy_decor_from(&attr.value);
+    /// *attr.value_mut() = expr;
+    ///
+    /// assert_eq!(body.to_string(), "count = /* instances */ 2 // keep small\n");
+    /// ```
+    fn copy_decor_from(&mut self, other: &impl Decorate) {
+        *self.decor_mut() = other.decor().clone();
+    }
 }
 
 impl<T> Decorate for Box<T>
@@ -165,7 +190,7 @@ impl<T> Spanned<T> {
         Spanned { value, span: None }
     }
 
-    /// Consumes the `Spanned<T>` and returns the wrapped value.
+    /// Consumes the `Spanned<T>` and returns the wrapped value, discarding its span.
     pub fn into_value(self) -> T {
         self.value
     }
@@ -275,7 +300,19 @@ impl<T> Decorated<T> {
         }
     }
 
-    /// Consumes the `Decorated<T>` and returns the wrapped value.
+    /// Consumes the `Decorated<T>` and returns the wrapped value, discarding its decor and span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::{Decorate, Decorated};
+    ///
+    /// let mut decorated = Decorated::new(String::from("value"));
+    /// decorated.decor_mut().set_prefix("/* comment */ ");
+    ///
+    /// let value: String = decorated.into_value();
+    /// assert_eq!(value, "value");
+    /// ```
     pub fn into_value(self) -> T {
         self.value
     }
@@ -417,7 +454,8 @@ where
 }
 
 impl<T> Formatted<T> {
-    /// Consumes the `Formatted<T>` and returns the wrapped value.
+    /// Consumes the `Formatted<T>` and returns the wrapped value, discarding its raw
+    /// representation, decor and span.
     pub fn into_value(self) -> T {
         self.value
     }
@@ -15,10 +15,15 @@ use std::ops::{self, Range};
 #[derive(Debug, Clone, Eq)]
 pub struct Attribute {
     /// The HCL attribute's key.
+    ///
+    /// Quoted keys of attributes parsed with
+    /// [`ParseOptions::quoted_attr_keys`](crate::parser::ParseOptions::quoted_attr_keys) enabled
+    /// are not necessarily valid identifiers. See [`Attribute::set_quoted_key`].
     pub key: Decorated<Ident>,
     /// The value expression of the HCL attribute.
     pub value: Expression,
 
+    quoted_key: bool,
     decor: Decor,
     span: Option<Range<usize>>,
 }
@@ -29,6 +34,7 @@ impl Attribute {
         Attribute {
             key: key.into(),
             value: value.into(),
+            quoted_key: false,
             decor: Decor::default(),
             span: None,
         }
@@ -50,6 +56,65 @@ impl Attribute {
         self.key.as_str() == key
     }
 
+    /// Configures whether the attribute key should be written as a quoted string.
+    ///
+    /// Quoted attribute keys are not permitted by the HCL specification and are only accepted by
+    /// the parser if enabled via
+    /// [`ParseOptions::quoted_attr_keys`](crate::parser::ParseOptions::quoted_attr_keys), which
+    /// also sets this flag for attributes with quoted keys. This is mainly useful to preserve
+    /// such keys when modifying a document in a lenient HCL dialect.
+    ///
+    /// Whether a key is quoted does not affect attribute equality.
+    ///
+    /// # Errors
+    ///
+    /// Quoted keys which are not valid identifiers can only be written as quoted strings. An
+    /// error is returned when attempting to unquote such a key, and the attribute is left
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl_edit::structure::{Attribute, Body};
+    /// use hcl_edit::Ident;
+    ///
+    /// let mut attr = Attribute::new(Ident::new("foo"), "bar");
+    /// attr.set_quoted_key(true)?;
+    ///
+    /// let body = Body::builder().attribute(attr).build();
+    /// assert_eq!(body.to_string(), "\"foo\" = \"bar\"\n");
+    ///
+    /// let mut attr = Attribute::new(Ident::new_unchecked("aws:region"), "eu-west-1");
+    /// attr.set_quoted_key(true)?;
+    /// assert!(attr.set_quoted_key(false).is_err());
+    /// assert!(attr.quoted_key());
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_quoted_key(&mut self, yes: bool) -> Result<(), hcl_primitives::Error> {
+        if !yes && self.quoted_key {
+            Ident::try_new(self.key.as_str())?;
+        }
+
+        self.quoted_key = yes;
+        Ok(())
+    }
+
+    // Used by the parser for attributes with quoted keys.
+    pub(crate) fn quote_key(&mut self) {
+        self.quoted_key = true;
+    }
+
+    /// Returns `true` if the attribute key is written as a quoted string.
+    ///
+    /// See the documentation of [`Attribute::set_quoted_key`] for more.
+    #[inline]
+    pub fn quoted_key(&self) -> bool {
+        self.quoted_key
+    }
+
     pub(crate) fn despan(&mut self, input: &str) {
         self.decor.despan(input);
         self.key.decor_mut().despan(input);
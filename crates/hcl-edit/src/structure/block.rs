@@ -1,3 +1,4 @@
+use crate::expr::Expression;
 use crate::structure::{Attribute, Body, Structure};
 use crate::{Decor, Decorate, Decorated, Ident};
 use std::ops::{self, Range};
@@ -80,6 +81,26 @@ impl Block {
         self.ident.as_str() == ident
     }
 
+    /// Returns `true` if the block's body contains an attribute with given key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::structure::{Attribute, Block};
+    /// use hcl_edit::Ident;
+    ///
+    /// let block = Block::builder(Ident::new("resource"))
+    ///     .attribute(Attribute::new(Ident::new("count"), 2))
+    ///     .build();
+    ///
+    /// assert!(block.has_attribute("count"));
+    /// assert!(!block.has_attribute("for_each"));
+    /// ```
+    #[inline]
+    pub fn has_attribute(&self, key: &str) -> bool {
+        self.body.has_attribute(key)
+    }
+
     /// Returns `true` if the `Block`'s labels and the provided ones share a common prefix.
     ///
     /// For example, `&["foo"]` will match blocks that fulfil either of these criteria:
@@ -211,6 +232,118 @@ impl Block {
         self.labels.len() == labels.len() && self.has_labels(labels)
     }
 
+    /// Returns `true` if the block body is written on the same line as the block identifier.
+    ///
+    /// This is the case if the body prefers the single-line layout (see
+    /// [`Body::set_prefer_oneline`]) and is either empty or consists of a single attribute.
+    pub fn is_oneline(&self) -> bool {
+        self.body.prefer_oneline() && (self.body.is_empty() || self.body.has_single_attribute())
+    }
+
+    /// Collapses the block body onto a single line, e.g. `block {}` or `block { foo = "bar" }`.
+    ///
+    /// Only bodies which are empty or consist of a single attribute can be written on a single
+    /// line. Whitespace surrounding the attribute and within the body is reset to the default,
+    /// so the result looks as if the block was constructed programmatically.
+    ///
+    /// Returns `false` and leaves the block unchanged if the body cannot be collapsed: if it
+    /// contains more than one structure, a nested block, an attribute whose value is a heredoc,
+    /// or comments which would be lost or would break the single-line layout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl_edit::structure::Body;
+    ///
+    /// let mut body: Body = "a {\n  foo = 1\n}\n\nb {\n  foo = 1\n  bar = 2\n}\n".parse()?;
+    ///
+    /// let collapsed: Vec<bool> = body.blocks_mut().map(|block| block.collapse_body()).collect();
+    /// assert_eq!(collapsed, [true, false]);
+    ///
+    /// assert_eq!(body.to_string(), "a { foo = 1 }\n\nb {\n  foo = 1\n  bar = 2\n}\n");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn collapse_body(&mut self) -> bool {
+        if !is_blank(self.body.decor()) {
+            return false;
+        }
+
+        match self.body.len() {
+            0 => {}
+            1 => match self.body.get(0) {
+                Some(Structure::Attribute(attr)) => {
+                    if matches!(attr.value, Expression::HeredocTemplate(_))
+                        || !is_blank(attr.decor())
+                        || !is_blank(attr.key.decor())
+                        || !is_blank(attr.value.decor())
+                    {
+                        return false;
+                    }
+                }
+                _ => return false,
+            },
+            _ => return false,
+        }
+
+        self.body.decor_mut().clear();
+
+        if let Some(Structure::Attribute(attr)) = self.body.get_mut(0) {
+            attr.decor_mut().clear();
+            attr.key.decor_mut().clear();
+            attr.value.decor_mut().clear();
+        }
+
+        self.body.set_prefer_oneline(true);
+        true
+    }
+
+    /// Expands the block body to the multi-line layout, where the opening and closing braces
+    /// are written on separate lines and each structure is placed on its own line.
+    ///
+    /// This is useful before adding structures to a single-line block. If the block uses the
+    /// single-line layout, the whitespace around the body and its attribute is removed, while
+    /// comments are preserved. The structures of the body are not indented, use the
+    /// [formatter](crate::format) to indent them. Blocks which already use the multi-line
+    /// layout are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl_edit::structure::{Attribute, Body};
+    /// use hcl_edit::Ident;
+    ///
+    /// let mut body: Body = "block { foo = 1 }\n".parse()?;
+    ///
+    /// let block = body.blocks_mut().next().unwrap();
+    /// block.expand_body();
+    /// block.body.push(Attribute::new(Ident::new("bar"), 2));
+    ///
+    /// assert_eq!(body.to_string(), "block {\nfoo = 1\nbar = 2\n}\n");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn expand_body(&mut self) {
+        if !self.is_oneline() {
+            self.body.set_prefer_oneline(false);
+            return;
+        }
+
+        trim_decor(self.body.decor_mut());
+
+        if let Some(Structure::Attribute(attr)) = self.body.get_mut(0) {
+            trim_decor(attr.decor_mut());
+        }
+
+        self.body.set_prefer_oneline(false);
+    }
+
     pub(crate) fn despan(&mut self, input: &str) {
         self.decor.despan(input);
         self.ident.decor_mut().despan(input);
@@ -221,6 +354,27 @@ impl Block {
     }
 }
 
+// Returns `true` if the decor does not contain anything but whitespace.
+fn is_blank(decor: &Decor) -> bool {
+    [decor.prefix(), decor.suffix()]
+        .into_iter()
+        .flatten()
+        .all(|raw| raw.as_str().trim().is_empty())
+}
+
+// Removes leading whitespace from the decor prefix and trailing whitespace from its suffix.
+fn trim_decor(decor: &mut Decor) {
+    if let Some(prefix) = decor.prefix() {
+        let trimmed = prefix.as_str().trim_start().to_owned();
+        decor.set_prefix(trimmed);
+    }
+
+    if let Some(suffix) = decor.suffix() {
+        let trimmed = suffix.as_str().trim_end().to_owned();
+        decor.set_suffix(trimmed);
+    }
+}
+
 impl PartialEq for Block {
     fn eq(&self, other: &Self) -> bool {
         self.ident == other.ident && self.labels == other.labels && self.body == other.body
@@ -244,6 +398,10 @@ impl PartialEq for Block {
 ///   body
 /// }
 /// ```
+///
+/// No other expressions are allowed as block labels. Quoted labels may contain escape sequences,
+/// but template interpolations and directives within them are not evaluated. A block can have
+/// any number of labels, which are followed by the opening brace of the block body.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BlockLabel {
     /// A bare HCL block label.
@@ -1,6 +1,11 @@
 use crate::encode::{EncodeDecorated, EncodeState, NO_DECOR};
+use crate::expr::Expression;
+use crate::format::Formatter;
 use crate::structure::{Attribute, AttributeMut, Block, Structure, StructureMut};
-use crate::{parser, Decor};
+use crate::template::Element;
+use crate::visit::Visit;
+use crate::visit_mut::{visit_expr_mut, VisitMut};
+use crate::{comment, literal, node, parser, Decor, Decorate, NodeRef, StringLiteral};
 use std::fmt;
 use std::ops::Range;
 use std::str::FromStr;
@@ -125,11 +130,47 @@ impl Body {
     }
 
     /// Clears the body, removing all structures.
+    ///
+    /// This has no effect on the allocated capacity, which makes it possible to reuse the `Body`
+    /// when building many bodies in a row. The decor of the body and its layout preferences are
+    /// retained.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::structure::{Attribute, Body};
+    /// use hcl_edit::Ident;
+    ///
+    /// let mut body = Body::with_capacity(2);
+    ///
+    /// for i in 0..3 {
+    ///     body.clear();
+    ///     body.push(Attribute::new(Ident::new("a"), i));
+    ///     body.push(Attribute::new(Ident::new("b"), i * 2));
+    ///
+    ///     assert_eq!(body.len(), 2);
+    ///     assert_eq!(body.to_string(), format!("a = {}\nb = {}\n", i, i * 2));
+    /// }
+    ///
+    /// body.clear();
+    /// assert!(body.is_empty());
+    /// ```
     #[inline]
     pub fn clear(&mut self) {
         self.structures.clear();
     }
 
+    /// Reserves capacity for at least `additional` more structures to be inserted into the
+    /// `Body`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.structures.reserve(additional);
+    }
+
     /// Returns a reference to the structure at the given index, or `None` if the index is out of
     /// bounds.
     #[inline]
@@ -178,6 +219,46 @@ impl Body {
         self.get_blocks(ident).next().is_some()
     }
 
+    /// Returns `true` if the body contains an attribute with given key.
+    ///
+    /// This is an alias of [`Body::has_attribute`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::structure::{Attribute, Body};
+    /// use hcl_edit::Ident;
+    ///
+    /// let body = Body::from_iter([Attribute::new(Ident::new("foo"), "bar")]);
+    ///
+    /// assert!(body.contains_attribute("foo"));
+    /// assert!(!body.contains_attribute("bar"));
+    /// ```
+    #[inline]
+    pub fn contains_attribute(&self, key: &str) -> bool {
+        self.has_attribute(key)
+    }
+
+    /// Returns `true` if the body contains at least one block with given identifier.
+    ///
+    /// This is an alias of [`Body::has_blocks`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl_edit::structure::{Block, Body};
+    /// use hcl_edit::Ident;
+    ///
+    /// let body = Body::from_iter([Block::new(Ident::new("provider"))]);
+    ///
+    /// assert!(body.contains_block("provider"));
+    /// assert!(!body.contains_block("terraform"));
+    /// ```
+    #[inline]
+    pub fn contains_block(&self, ident: &str) -> bool {
+        self.has_blocks(ident)
+    }
+
     /// Returns a reference to the `Attribute` with given key if it exists, otherwise `None`.
     ///
     /// # Example
@@ -380,6 +461,165 @@ impl Body {
         }
     }
 
+    /// Inserts a block directly before the structure at position `index`, synthesizing decor so
+    /// that the result is well-formatted.
+    ///
+    /// The decor is synthesized as follows:
+    ///
+    /// - The new block is indented like the structure at `index`. Structures nested within the
+    ///   new block are indented by two additional spaces per nesting level and their existing
+    ///   decor is normalized as described in the [`format`](crate::format) module.
+    /// - The new block is separated from the preceding structure, if any, by a single blank line.
+    /// - A blank line is added between the new block and the structure at `index` unless that
+    ///   structure is already preceded by one. Comments preceding the structure at `index` stay
+    ///   attached to it.
+    ///
+    /// The position of a specific block can be obtained via [`Iterator::position`], e.g.
+    /// `body.iter().position(|s| s.as_block().is_some_and(|b| b.has_ident("resource")))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl_edit::structure::{Attribute, Block, Body};
+    /// use hcl_edit::Ident;
+    ///
+    /// let input = r#"
+    /// // The bucket.
+    /// resource "aws_s3_bucket" "bucket" {}
+    /// "#;
+    ///
+    /// let mut body: Body = input.parse()?;
+    ///
+    /// let block = Block::builder(Ident::new("variable"))
+    ///     .label("name")
+    ///     .attribute(Attribute::new(Ident::new("type"), "string"))
+    ///     .build();
+    ///
+    /// body.insert_block_before(0, block);
+    ///
+    /// let expected = r#"variable "name" {
+    ///   type = "string"
+    /// }
+    ///
+    /// // The bucket.
+    /// resource "aws_s3_bucket" "bucket" {}
+    /// "#;
+    ///
+    /// assert_eq!(body.to_string(), expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn insert_block_before(&mut self, index: usize, block: impl Into<Block>) {
+        assert!(
+            index < self.len(),
+            "insertion index (is {index}) should be < len (is {})",
+            self.len()
+        );
+
+        self.insert_block_formatted(index, index, block.into());
+    }
+
+    /// Inserts a block directly after the structure at position `index`, synthesizing decor so
+    /// that the result is well-formatted.
+    ///
+    /// The new block is indented like the structure at `index` and separated from its
+    /// neighbours by single blank lines. See [`Body::insert_block_before`] for a detailed
+    /// description of the synthesized decor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl_edit::structure::{Attribute, Block, Body};
+    /// use hcl_edit::Ident;
+    ///
+    /// let input = r#"
+    /// resource "aws_s3_bucket" "bucket" {
+    ///   bucket = "my-bucket"
+    /// }
+    /// resource "aws_db_instance" "db" {}
+    /// "#;
+    ///
+    /// let mut body: Body = input.parse()?;
+    ///
+    /// let index = body
+    ///     .iter()
+    ///     .position(|structure| {
+    ///         structure
+    ///             .as_block()
+    ///             .is_some_and(|block| block.has_labels(&["aws_s3_bucket"]))
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let block = Block::builder(Ident::new("resource"))
+    ///     .labels(["aws_s3_bucket_acl", "acl"])
+    ///     .attribute(Attribute::new(Ident::new("acl"), "private"))
+    ///     .build();
+    ///
+    /// body.insert_block_after(index, block);
+    ///
+    /// let expected = r#"
+    /// resource "aws_s3_bucket" "bucket" {
+    ///   bucket = "my-bucket"
+    /// }
+    ///
+    /// resource "aws_s3_bucket_acl" "acl" {
+    ///   acl = "private"
+    /// }
+    ///
+    /// resource "aws_db_instance" "db" {}
+    /// "#;
+    ///
+    /// assert_eq!(body.to_string(), expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn insert_block_after(&mut self, index: usize, block: impl Into<Block>) {
+        assert!(
+            index < self.len(),
+            "insertion index (is {index}) should be < len (is {})",
+            self.len()
+        );
+
+        self.insert_block_formatted(index + 1, index, block.into());
+    }
+
+    fn insert_block_formatted(&mut self, index: usize, anchor: usize, mut block: Block) {
+        let anchor_prefix = self.structures[anchor].decor().prefix();
+        let line = anchor_prefix.map_or("", |prefix| prefix.rsplit('\n').next().unwrap_or(""));
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        Formatter::default().format_block(&mut block, indent);
+
+        let prefix = if index > 0 {
+            format!("\n{indent}")
+        } else {
+            indent.to_owned()
+        };
+
+        block.decor_mut().set_prefix(prefix);
+        self.structures.insert(index, Structure::Block(block));
+
+        if let Some(next) = self.structures.get_mut(index + 1) {
+            let decor = next.decor_mut();
+            let prefix = decor.prefix().map_or("", |prefix| prefix);
+
+            if !prefix.trim_start_matches([' ', '\t']).starts_with('\n') {
+                let prefix = format!("\n{prefix}");
+                decor.set_prefix(prefix);
+            }
+        }
+    }
+
     /// Appends a structure to the back of the body.
     ///
     /// If it is attempted to append an `Attribute` which already exists in the body, it is ignored
@@ -620,6 +860,194 @@ impl Body {
         )
     }
 
+    /// Rewrites all quoted string templates in the body which consist of a single interpolation
+    /// into the bare interpolated expression.
+    ///
+    /// This normalizes configuration written for older Terraform versions, which required every
+    /// expression to be wrapped in an interpolation, similar to `terraform 0.12upgrade`. Nested
+    /// expressions are unwrapped as well. Templates containing anything else besides a single
+    /// interpolation, as well as heredoc templates, are left as-is. Comments and whitespace
+    /// surrounding the unwrapped expressions are preserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl_edit::structure::Body;
+    ///
+    /// let input = r#"
+    /// count = "${length(var.x)}" # pure interpolation
+    /// name  = "${var.prefix}-${var.name}"
+    /// "#;
+    ///
+    /// let mut body: Body = input.parse()?;
+    /// body.unwrap_interpolations();
+    ///
+    /// let expected = r#"
+    /// count = length(var.x) # pure interpolation
+    /// name  = "${var.prefix}-${var.name}"
+    /// "#;
+    ///
+    /// assert_eq!(body.to_string(), expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn unwrap_interpolations(&mut self) {
+        InterpolationUnwrapper.visit_body_mut(self);
+    }
+
+    /// Removes all comments from the body, including comments within nested blocks and
+    /// expressions.
+    ///
+    /// In contrast to clearing the [`Decor`] of the body's nodes, the surrounding whitespace is
+    /// preserved: lines which only contained comments are removed, while blank lines and
+    /// indentation are kept, so that the layout of the document stays intact.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl_edit::structure::Body;
+    ///
+    /// let input = r#"
+    /// // The bucket.
+    /// resource "aws_s3_bucket" "b" {
+    ///   bucket = "my-bucket" # Must be unique.
+    ///
+    ///   acl = /* canned */ "private"
+    /// }
+    /// "#;
+    ///
+    /// let mut body: Body = input.parse()?;
+    /// body.strip_comments();
+    ///
+    /// let expected = r#"
+    /// resource "aws_s3_bucket" "b" {
+    ///   bucket = "my-bucket"
+    ///
+    ///   acl = "private"
+    /// }
+    /// "#;
+    ///
+    /// assert_eq!(body.to_string(), expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn strip_comments(&mut self) {
+        comment::strip_comments(self);
+    }
+
+    /// Traverses the body with the given visitor.
+    ///
+    /// This is a shorthand for calling [`Visit::visit_body`] on the visitor. See the
+    /// [`visit`](crate::visit) module for details, and [`expr_visitor`](crate::visit::expr_visitor)
+    /// for a closure-based visitor.
+    pub fn visit<V>(&self, visitor: &mut V)
+    where
+        V: Visit + ?Sized,
+    {
+        visitor.visit_body(self);
+    }
+
+    /// Traverses the body with the given mutable visitor.
+    ///
+    /// This is a shorthand for calling [`VisitMut::visit_body_mut`] on the visitor. See the
+    /// [`visit_mut`](crate::visit_mut) module for details, and
+    /// [`expr_visitor_mut`](crate::visit_mut::expr_visitor_mut) for a closure-based visitor.
+    pub fn visit_mut<V>(&mut self, visitor: &mut V)
+    where
+        V: VisitMut + ?Sized,
+    {
+        visitor.visit_body_mut(self);
+    }
+
+    /// Returns the innermost syntax tree node which covers the given zero-based byte offset.
+    ///
+    /// This relies on span information and thus only works for bodies emitted by the parser,
+    /// where `offset` refers to a position in the original input. Returns `None` if the offset
+    /// is not covered by any attribute or block, e.g. because it points to whitespace or a
+    /// comment between two structures.
+    ///
+    /// Identifiers of variables are reported as [`NodeRef::Expression`]. Template literals and
+    /// operators are not represented as nodes, so for offsets pointing to these the enclosing
+    /// expression is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl_edit::expr::Expression;
+    /// use hcl_edit::structure::Body;
+    /// use hcl_edit::NodeRef;
+    ///
+    /// let input = "resource \"aws_s3_bucket\" \"b\" {\n  bucket = var.name\n}\n";
+    /// let body: Body = input.parse()?;
+    ///
+    /// let offset = input.find("name").unwrap();
+    ///
+    /// match body.node_at(offset) {
+    ///     Some(NodeRef::Ident(ident)) => assert_eq!(ident.as_str(), "name"),
+    ///     other => panic!("unexpected node: {other:?}"),
+    /// }
+    ///
+    /// let offset = input.find("var").unwrap();
+    ///
+    /// match body.node_at(offset) {
+    ///     Some(NodeRef::Expression(Expression::Variable(var))) => assert_eq!(var.as_str(), "var"),
+    ///     other => panic!("unexpected node: {other:?}"),
+    /// }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn node_at(&self, offset: usize) -> Option<NodeRef<'_>> {
+        node::node_at(self, offset)
+    }
+
+    /// Collects all string literals of the body in document order, together with their path and
+    /// span.
+    ///
+    /// This includes quoted strings as well as the literal parts of string and heredoc
+    /// templates, wherever they appear within attribute values. Only literal text is returned:
+    /// template interpolations and directives are not evaluated, so computed strings are never
+    /// part of the result. Block labels and object keys are not reported as literals, they are
+    /// part of the path instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl_edit::structure::Body;
+    ///
+    /// let input = r#"
+    /// service "api" {
+    ///   greeting = "Hello, ${name}!"
+    ///   tags     = { env = "prod" }
+    /// }
+    /// "#;
+    ///
+    /// let body: Body = input.parse()?;
+    /// let literals = body.string_literals();
+    ///
+    /// let values: Vec<_> = literals.iter().map(|lit| lit.value.as_str()).collect();
+    /// assert_eq!(values, ["Hello, ", "!", "prod"]);
+    ///
+    /// assert_eq!(literals[2].path, ["service", "api", "tags", "env"]);
+    /// assert_eq!(&input[literals[2].span.clone().unwrap()], "\"prod\"");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn string_literals(&self) -> Vec<StringLiteral> {
+        literal::string_literals(self)
+    }
+
     /// Configures whether the body should be displayed on a single line.
     ///
     /// This is only a hint which will be applied if the `Body` is part of a `Block` (that is: not
@@ -686,6 +1114,24 @@ impl Body {
     }
 }
 
+struct InterpolationUnwrapper;
+
+impl VisitMut for InterpolationUnwrapper {
+    fn visit_expr_mut(&mut self, expr: &mut Expression) {
+        if let Expression::StringTemplate(template) = expr {
+            if let Some(Element::Interpolation(interpolation)) = template.as_single_element_mut() {
+                let mut unwrapped = std::mem::replace(&mut interpolation.expr, Expression::null());
+
+                // Apply the existing decor to the unwrapped expression.
+                std::mem::swap(expr.decor_mut(), unwrapped.decor_mut());
+                *expr = unwrapped;
+            }
+        }
+
+        visit_expr_mut(self, expr);
+    }
+}
+
 impl PartialEq for Body {
     fn eq(&self, other: &Self) -> bool {
         self.structures == other.structures
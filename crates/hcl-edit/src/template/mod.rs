@@ -5,7 +5,7 @@ mod tests;
 
 use crate::encode::{Encode, EncodeState};
 use crate::expr::Expression;
-use crate::util::{dedent_by, min_leading_whitespace};
+use crate::util::{common_prefix, dedent_by, min_leading_whitespace};
 use crate::{parser, Decor, Decorate, Decorated, Ident, RawString, Spanned};
 use std::fmt;
 use std::ops::{Deref, DerefMut, Range};
@@ -163,7 +163,7 @@ pub struct HeredocTemplate {
     /// The raw template contained in the heredoc.
     pub template: Template,
 
-    indent: Option<usize>,
+    indent: Option<String>,
     trailing: RawString,
     decor: Decor,
     span: Option<Range<usize>>,
@@ -183,13 +183,29 @@ impl HeredocTemplate {
     }
 
     /// Return the heredoc's indent, if there is any.
+    ///
+    /// The indent is the number of leading whitespace characters that are stripped off of each
+    /// line. Every whitespace character counts as one, i.e. a tab is equivalent to a single
+    /// space.
     pub fn indent(&self) -> Option<usize> {
-        self.indent
+        self.indent.as_ref().map(|indent| indent.chars().count())
+    }
+
+    /// Return the whitespace that is used to indent the heredoc's lines when it is encoded, if
+    /// there is any.
+    pub fn indent_str(&self) -> Option<&str> {
+        self.indent.as_deref()
     }
 
-    /// Set the heredoc's indent.
+    /// Set the heredoc's indent to the given number of spaces.
     pub fn set_indent(&mut self, indent: usize) {
-        self.indent = Some(indent);
+        self.indent = Some(" ".repeat(indent));
+    }
+
+    /// Set the whitespace that is used to indent the heredoc's lines, e.g. to indent using tabs
+    /// instead of spaces.
+    pub fn set_indent_str(&mut self, indent: impl Into<String>) {
+        self.indent = Some(indent.into());
     }
 
     /// Return a reference to the raw trailing decor before the heredoc's closing delimiter.
@@ -204,8 +220,17 @@ impl HeredocTemplate {
 
     /// Dedent the heredoc template.
     ///
-    /// This will set the heredoc's indent to the number of leading
-    /// spaces that were stripped off of template string literals, if any.
+    /// This strips the minimum number of leading whitespace characters of all non-empty lines
+    /// off of the template's string literals and sets the heredoc's indent accordingly.
+    ///
+    /// Following the HCL specification, every whitespace character counts as one column: tabs
+    /// are not expanded, so a line indented by a single tab and a line indented by a single
+    /// space have the same indent. For mixed indentation, the same number of characters is
+    /// stripped from every line, regardless of whether these are tabs or spaces.
+    ///
+    /// If the stripped whitespace is identical on all lines, e.g. because the heredoc is
+    /// consistently indented using tabs, it is used to re-indent the heredoc when encoding it.
+    /// Otherwise the heredoc is re-indented using spaces.
     pub fn dedent(&mut self) {
         let stripped_indent = self.template.dedent();
         self.indent = stripped_indent;
@@ -399,9 +424,12 @@ impl Template {
         }
     }
 
-    /// Dedents string literals in the template, returning the maximum indent that was stripped,
-    /// if any.
-    pub(crate) fn dedent(&mut self) -> Option<usize> {
+    /// Dedents string literals in the template, returning the indentation that was stripped, if
+    /// any.
+    ///
+    /// The returned indentation is the whitespace that was stripped off of each line if it was
+    /// identical on all lines, otherwise it consists of spaces.
+    pub(crate) fn dedent(&mut self) -> Option<String> {
         let mut indent: Option<usize> = None;
         let mut skip_first_line = false;
 
@@ -409,6 +437,10 @@ impl Template {
             if let Element::Literal(literal) = element {
                 if let Some(leading_ws) = min_leading_whitespace(literal, skip_first_line) {
                     indent = Some(indent.map_or(leading_ws, |indent| indent.min(leading_ws)));
+                } else if has_content_lines(literal, skip_first_line) {
+                    // A non-empty line without leading whitespace means that no indent can be
+                    // stripped.
+                    return None;
                 }
                 skip_first_line = !literal.ends_with('\n');
             } else if !skip_first_line {
@@ -418,24 +450,43 @@ impl Template {
             }
         }
 
-        if let Some(indent) = indent {
-            skip_first_line = false;
+        let n = indent?;
+        let mut prefix: Option<Option<String>> = None;
+        skip_first_line = false;
 
-            for element in &mut self.elements {
-                if let Element::Literal(literal) = element {
-                    let dedented = dedent_by(literal, indent, skip_first_line);
-                    *literal.as_mut() = dedented.into();
-                    skip_first_line = !literal.ends_with('\n');
-                } else if !skip_first_line {
-                    skip_first_line = true;
+        for element in &mut self.elements {
+            if let Element::Literal(literal) = element {
+                if let Some(literal_prefix) = common_prefix(literal, n, skip_first_line) {
+                    match &prefix {
+                        Some(Some(prefix)) if prefix == literal_prefix => {}
+                        Some(_) => prefix = Some(None),
+                        None => prefix = Some(Some(literal_prefix.to_owned())),
+                    }
+                } else if has_content_lines(literal, skip_first_line) {
+                    prefix = Some(None);
                 }
+
+                let dedented = dedent_by(literal, n, skip_first_line);
+                *literal.as_mut() = dedented.into();
+                skip_first_line = !literal.ends_with('\n');
+            } else if !skip_first_line {
+                skip_first_line = true;
             }
         }
 
-        indent
+        Some(prefix.flatten().unwrap_or_else(|| " ".repeat(n)))
     }
 }
 
+// Returns `true` if `literal` contains non-empty lines, ignoring the first line if `skip_first` is
+// `true`.
+fn has_content_lines(literal: &str, skip_first: bool) -> bool {
+    literal
+        .lines()
+        .skip(usize::from(skip_first))
+        .any(|line| !line.is_empty())
+}
+
 impl PartialEq for Template {
     fn eq(&self, other: &Self) -> bool {
         self.elements == other.elements
@@ -10,6 +10,8 @@ fn dedent_template() {
         ("  foo\n${bar}\n    baz\n", "  foo\n${bar}\n    baz\n"),
         ("  foo${bar}\n    baz", "foo${bar}\n  baz"),
         ("  foo\n    bar\n      baz", "foo\n  bar\n    baz"),
+        // A line without indent following an interpolation prevents dedenting.
+        ("  foo ${bar}\nbaz", "  foo ${bar}\nbaz"),
     ];
 
     for (input, expected) in tests {
@@ -23,3 +25,51 @@ fn dedent_template() {
         );
     }
 }
+
+#[test]
+fn dedent_heredoc_whitespace() {
+    let tests = [
+        // Space-only indentation.
+        ("    foo\n      bar\n", "foo\n  bar\n", Some("    ")),
+        // Tab-only indentation. Every tab counts as a single character.
+        ("\t\tfoo\n\t\t\tbar\n", "foo\n\tbar\n", Some("\t\t")),
+        // Mixed indentation strips the same number of characters from every line and is
+        // re-indented using spaces.
+        ("\tfoo\n  bar\n", "foo\n bar\n", Some(" ")),
+        ("\t  foo\n \tbar\n", " foo\nbar\n", Some("  ")),
+        ("  foo\n\t${bar}\n", " foo\n${bar}\n", Some(" ")),
+        // Identical leading whitespace across literals is preserved.
+        ("\t foo ${bar}\n\t baz\n", "foo ${bar}\nbaz\n", Some("\t ")),
+        // Empty lines do not affect the indent.
+        ("\tfoo\n\n\tbar\n", "foo\n\nbar\n", Some("\t")),
+    ];
+
+    for (input, expected, expected_indent) in tests {
+        let mut heredoc = HeredocTemplate::new(Ident::new("EOT"), input.parse().unwrap());
+        heredoc.dedent();
+
+        assert_eq!(
+            heredoc.template.to_string(),
+            expected,
+            "unexpected dedent result for input `{input:?}`",
+        );
+        assert_eq!(heredoc.indent_str(), expected_indent);
+        assert_eq!(
+            heredoc.indent(),
+            expected_indent.map(|indent| indent.chars().count())
+        );
+    }
+}
+
+#[test]
+fn heredoc_tab_indent_roundtrip() {
+    let input = "<<-EOT\n\t\tfoo\n\t\t\tbar\n\tEOT";
+    let expr: crate::expr::Expression = input.parse().unwrap();
+    let heredoc = expr.as_heredoc_template().unwrap();
+
+    assert_eq!(heredoc.template.to_string(), "foo\n\tbar\n");
+    assert_eq!(heredoc.indent(), Some(2));
+
+    // The original indentation is preserved when encoding.
+    assert_eq!(expr.to_string(), "<<-EOT\n\t\tfoo\n\t\t\tbar\n\tEOT");
+}
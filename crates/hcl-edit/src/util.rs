@@ -23,11 +23,12 @@ pub(crate) fn dedent_by(s: &str, n: usize, skip_first: bool) -> Cow<str> {
     Cow::Owned(dedented)
 }
 
+// Returns the minimum number of leading whitespace characters of all non-empty lines in `s`, or
+// `None` if there is a non-empty line without leading whitespace.
+//
+// Every whitespace character counts as one, i.e. tabs are not expanded and are equivalent to a
+// single space.
 pub(crate) fn min_leading_whitespace(s: &str, skip_first: bool) -> Option<usize> {
-    if s.is_empty() {
-        return None;
-    }
-
     let mut leading_ws: Option<usize> = None;
 
     // Find the minimum number of possible leading units of whitespace that can be be stripped off
@@ -40,7 +41,7 @@ pub(crate) fn min_leading_whitespace(s: &str, skip_first: bool) -> Option<usize>
         let line_leading_ws = line.chars().take_while(|ch| ch.is_whitespace()).count();
 
         if line_leading_ws == 0 {
-            // Fast path: no dedent needed if we encounter a non-empty line which starts with a
+            // Fast path: no dedent possible if we encounter a non-empty line which starts with a
             // non-whitespace character.
             return None;
         }
@@ -53,8 +54,29 @@ pub(crate) fn min_leading_whitespace(s: &str, skip_first: bool) -> Option<usize>
     leading_ws
 }
 
-pub(crate) fn indent_by(s: &str, n: usize, skip_first: bool) -> String {
-    let prefix = " ".repeat(n);
+// Returns the first `n` characters of `s` if they are identical on all non-empty lines, or `None`
+// if the lines' leading `n` characters differ.
+pub(crate) fn common_prefix(s: &str, n: usize, skip_first: bool) -> Option<&str> {
+    let mut common: Option<&str> = None;
+
+    for (i, line) in s.lines().enumerate() {
+        if (i == 0 && skip_first) || line.is_empty() {
+            continue;
+        }
+
+        let len = line.char_indices().nth(n).map_or(line.len(), |(i, _)| i);
+        let prefix = &line[..len];
+
+        match common {
+            Some(common) if common != prefix => return None,
+            _ => common = Some(prefix),
+        }
+    }
+
+    common
+}
+
+pub(crate) fn indent_with(s: &str, prefix: &str, skip_first: bool) -> String {
     let length = s.len();
     let mut output = String::with_capacity(length + length / 2);
 
@@ -63,10 +85,10 @@ pub(crate) fn indent_by(s: &str, n: usize, skip_first: bool) -> String {
             output.push('\n');
 
             if !line.is_empty() {
-                output.push_str(&prefix);
+                output.push_str(prefix);
             }
         } else if !skip_first && !line.is_empty() {
-            output.push_str(&prefix);
+            output.push_str(prefix);
         }
 
         output.push_str(line);
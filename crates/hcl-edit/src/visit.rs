@@ -153,6 +153,65 @@ pub trait Visit {
     }
 }
 
+/// A [`Visit`] implementation which calls a closure for every expression.
+///
+/// This is useful for one-off visits which do not warrant a dedicated visitor type. Values of
+/// this type are created by [`expr_visitor`], see its documentation for more.
+pub struct ExprVisitor<F> {
+    f: F,
+}
+
+/// Creates a [`Visit`] implementation which calls `f` for every expression.
+///
+/// Expressions are visited in pre-order: `f` is called for an expression before it is called
+/// for any of its sub-expressions.
+///
+/// # Example
+///
+/// Count the function calls within a body:
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use hcl_edit::expr::Expression;
+/// use hcl_edit::structure::Body;
+/// use hcl_edit::visit::expr_visitor;
+///
+/// let body: Body = r#"
+///     name = upper(var.name)
+///     tags = merge(local.tags, { Name = "${lower(var.name)}" })
+/// "#.parse()?;
+///
+/// let mut func_calls = 0;
+///
+/// body.visit(&mut expr_visitor(|expr| {
+///     if let Expression::FuncCall(_) = expr {
+///         func_calls += 1;
+///     }
+/// }));
+///
+/// assert_eq!(func_calls, 3);
+/// #   Ok(())
+/// # }
+/// ```
+pub fn expr_visitor<F>(f: F) -> ExprVisitor<F>
+where
+    F: FnMut(&Expression),
+{
+    ExprVisitor { f }
+}
+
+impl<F> Visit for ExprVisitor<F>
+where
+    F: FnMut(&Expression),
+{
+    fn visit_expr(&mut self, node: &Expression) {
+        (self.f)(node);
+        visit_expr(self, node);
+    }
+}
+
 pub fn visit_body<V>(v: &mut V, node: &Body)
 where
     V: Visit + ?Sized,
@@ -479,5 +538,6 @@ where
         v.visit_ident(key_var);
     }
     v.visit_ident(&node.value_var);
+    v.visit_expr(&node.collection_expr);
     v.visit_template(&node.template);
 }
@@ -179,6 +179,64 @@ pub trait VisitMut {
     }
 }
 
+/// A [`VisitMut`] implementation which calls a closure for every expression.
+///
+/// This is useful for one-off visits which do not warrant a dedicated visitor type. Values of
+/// this type are created by [`expr_visitor_mut`], see its documentation for more.
+pub struct ExprVisitorMut<F> {
+    f: F,
+}
+
+/// Creates a [`VisitMut`] implementation which calls `f` for every expression.
+///
+/// Expressions are visited in pre-order: `f` is called for an expression before it is called
+/// for any of its sub-expressions. If `f` replaces an expression, the sub-expressions of the
+/// replacement are visited.
+///
+/// # Example
+///
+/// Replace all `null` values with empty strings:
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use hcl_edit::expr::Expression;
+/// use hcl_edit::structure::Body;
+/// use hcl_edit::visit_mut::expr_visitor_mut;
+/// use hcl_edit::Decorate;
+///
+/// let mut body: Body = "a = null\nb = [1, null]\n".parse()?;
+///
+/// body.visit_mut(&mut expr_visitor_mut(|expr| {
+///     if let Expression::Null(null) = expr {
+///         let decor = null.decor().clone();
+///         *expr = Expression::from("");
+///         *expr.decor_mut() = decor;
+///     }
+/// }));
+///
+/// assert_eq!(body.to_string(), "a = \"\"\nb = [1, \"\"]\n");
+/// #   Ok(())
+/// # }
+/// ```
+pub fn expr_visitor_mut<F>(f: F) -> ExprVisitorMut<F>
+where
+    F: FnMut(&mut Expression),
+{
+    ExprVisitorMut { f }
+}
+
+impl<F> VisitMut for ExprVisitorMut<F>
+where
+    F: FnMut(&mut Expression),
+{
+    fn visit_expr_mut(&mut self, node: &mut Expression) {
+        (self.f)(node);
+        visit_expr_mut(self, node);
+    }
+}
+
 pub fn visit_body_mut<V>(v: &mut V, node: &mut Body)
 where
     V: VisitMut + ?Sized,
@@ -495,5 +553,6 @@ where
         v.visit_ident_mut(key_var);
     }
     v.visit_ident_mut(&mut node.value_var);
+    v.visit_expr_mut(&mut node.collection_expr);
     v.visit_template_mut(&mut node.template);
 }
@@ -0,0 +1,186 @@
+use hcl_edit::format::{self, CommentStyle, Formatter};
+use hcl_edit::structure::Body;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+#[track_caller]
+fn assert_format(input: &str, expected: &str) {
+    let mut body: Body = input.parse().unwrap();
+    format::format_body(&mut body);
+    assert_eq!(body.to_string(), expected);
+}
+
+#[test]
+fn format_body() {
+    let input = indoc! {r#"
+        # Leading comment.
+
+
+          foo   =   1    # Trailing comment.
+        block   "label"    { # After brace.
+              # Inner comment.
+                bar    = 2
+
+
+          nested     {
+                baz = 3
+        /* multi
+            line */
+         }
+             }
+        empty   {   }
+        oneline {   qux   =   4   }
+    "#};
+
+    let expected = indoc! {r#"
+        # Leading comment.
+
+        foo = 1 # Trailing comment.
+        block "label" { # After brace.
+          # Inner comment.
+          bar = 2
+
+          nested {
+            baz = 3
+            /* multi
+            line */
+          }
+        }
+        empty {}
+        oneline { qux = 4 }
+    "#};
+
+    assert_format(input, expected);
+}
+
+#[test]
+fn format_body_custom_indent() {
+    let mut body: Body = "block {\n foo = 1\n nested {\n bar = 2\n }\n}\n"
+        .parse()
+        .unwrap();
+
+    Formatter::builder()
+        .indent("    ")
+        .build()
+        .format_body(&mut body);
+
+    let expected = indoc! {r#"
+        block {
+            foo = 1
+            nested {
+                bar = 2
+            }
+        }
+    "#};
+
+    assert_eq!(body.to_string(), expected);
+}
+
+#[test]
+fn format_body_disabled_region() {
+    let input = indoc! {r#"
+        a    = 1
+        # hcl-fmt: off
+        matrix = [
+          1, 0,
+              0, 1,
+        ]
+           odd    "indent" {
+                   keep   =   "me"
+        }
+        // hcl-fmt: on
+              b    = 2
+        block {
+            c   = 3
+              # hcl-fmt: off
+                  d   =   4
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        a = 1
+        # hcl-fmt: off
+        matrix = [
+          1, 0,
+              0, 1,
+        ]
+           odd    "indent" {
+                   keep   =   "me"
+        }
+        // hcl-fmt: on
+        b = 2
+        block {
+          c = 3
+          # hcl-fmt: off
+                  d   =   4
+        }
+    "#};
+
+    assert_format(input, expected);
+}
+
+#[test]
+fn format_body_comment_style() {
+    let input = indoc! {r#"
+        // Leading comment.
+        //Without space.
+        foo = 1 // Trailing comment.
+        # Already a hash comment.
+        /* Inline. */ // After inline comment.
+        baz = 2 /* Inline. */ // Trailing after inline comment.
+        block "label" { // After brace.
+          // Inner comment.
+          /* multi
+          // line */
+          bar = [
+            // Inside an expression.
+            2,
+          ]
+          oneline { baz = 3 } // After oneline block.
+          // hcl-fmt: off
+          // Disabled region.
+          qux   =   4 // Verbatim.
+        }
+        // Body suffix.
+    "#};
+
+    let expected = indoc! {r#"
+        # Leading comment.
+        #Without space.
+        foo = 1 # Trailing comment.
+        # Already a hash comment.
+        /* Inline. */ # After inline comment.
+        baz = 2 /* Inline. */ # Trailing after inline comment.
+        block "label" { # After brace.
+          # Inner comment.
+          /* multi
+          // line */
+          bar = [
+            // Inside an expression.
+            2,
+          ]
+          oneline { baz = 3 } # After oneline block.
+          # hcl-fmt: off
+          // Disabled region.
+          qux   =   4 // Verbatim.
+        }
+        # Body suffix.
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+
+    Formatter::builder()
+        .comment_style(CommentStyle::Hash)
+        .build()
+        .format_body(&mut body);
+
+    assert_eq!(body.to_string(), expected);
+
+    // The conversion works in both directions.
+    Formatter::builder()
+        .comment_style(CommentStyle::DoubleSlash)
+        .build()
+        .format_body(&mut body);
+
+    assert_eq!(body.to_string(), expected.replace('#', "//"));
+}
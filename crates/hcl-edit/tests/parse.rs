@@ -1,4 +1,7 @@
-use hcl_edit::parser::parse_body;
+use hcl_edit::expr::Expression;
+use hcl_edit::parser::{parse_attribute, parse_block, parse_body, ParseOptions};
+use hcl_edit::structure::Body;
+use hcl_edit::Decorate;
 use indoc::indoc;
 use pretty_assertions::assert_eq;
 
@@ -81,6 +84,39 @@ fn invalid_blocks() {
               |
               = invalid block body; expected `}`, newline or identifier"#}
     );
+
+    assert_error!(
+        "resource aws_instance 1 {}",
+        indoc! {r#"
+             --> HCL parse error in line 1, column 23
+              |
+            1 | resource aws_instance 1 {}
+              |                       ^---
+              |
+              = invalid block; expected block label (string or identifier) or `{`"#}
+    );
+
+    assert_error!(
+        "resource \"aws_instance\" var.name {}",
+        indoc! {r#"
+             --> HCL parse error in line 1, column 28
+              |
+            1 | resource "aws_instance" var.name {}
+              |                            ^---
+              |
+              = invalid block; expected block label (string or identifier) or `{`"#}
+    );
+
+    assert_error!(
+        "resource \"aws_instance\" \"web {}",
+        indoc! {r#"
+             --> HCL parse error in line 1, column 32
+              |
+            1 | resource "aws_instance" "web {}
+              |                                ^---
+              |
+              = invalid block label; expected `"`"#}
+    );
 }
 
 #[test]
@@ -162,3 +198,277 @@ fn invalid_exprs() {
               = invalid expression; expected `"`, `[`, `{`, `-`, `!`, `(`, `_`, `<`, letter or digit"#}
     );
 }
+
+#[test]
+fn error_details() {
+    let err = parse_body("foo = 1\nbar [").unwrap_err();
+    assert_eq!(err.location().line(), 2);
+    assert_eq!(err.location().column(), 5);
+    assert_eq!(err.line(), "bar [");
+    assert_eq!(err.expected(), ["{", "=", "\"", "identifier"]);
+    assert_eq!(
+        err.message(),
+        "invalid structure; expected `{`, `=`, `\"` or identifier"
+    );
+
+    // Errors from the template and expression parsers have the same shape.
+    let err = parse_body("foo = \"${bar\"").unwrap_err();
+    assert_eq!(err.location().line(), 1);
+    assert_eq!(err.location().column(), 13);
+    assert_eq!(err.expected(), ["}"]);
+
+    let err = parse_body("foo = <<EOT\nbar\n").unwrap_err();
+    assert_eq!(err.location().column(), 7);
+    assert!(err.expected().is_empty());
+}
+
+#[test]
+fn unterminated_heredocs() {
+    assert_error!(
+        "foo = 1\nbar = <<EOT\nbaz\n",
+        indoc! {r#"
+             --> HCL parse error in line 2, column 7
+              |
+            2 | bar = <<EOT
+              |       ^---
+              |
+              = unterminated heredoc started at line 2 with delimiter `EOT` (the closing `EOT` must be on a line of its own)"#}
+    );
+
+    assert_error!(
+        "block {\n  bar = <<-EOT\n    baz\n    EO\n}\n",
+        indoc! {r#"
+             --> HCL parse error in line 2, column 9
+              |
+            2 |   bar = <<-EOT
+              |         ^---
+              |
+              = unterminated heredoc started at line 2 with delimiter `EOT` (the closing `EOT` may be indented, but must be on a line of its own)"#}
+    );
+
+    // Heredocs without delimiter are not reported as unterminated.
+    assert_error!(
+        "bar = <<\nbaz\n",
+        indoc! {r#"
+             --> HCL parse error in line 1, column 9
+              |
+            1 | bar = <<
+              |         ^---
+              |
+              = missing heredoc delimiter (`<<` must be directly followed by an identifier, e.g. `<<EOT`)"#}
+    );
+
+    assert_error!(
+        "bar = <<- EOT\nbaz\nEOT\n",
+        indoc! {r#"
+             --> HCL parse error in line 1, column 10
+              |
+            1 | bar = <<- EOT
+              |          ^---
+              |
+              = missing heredoc delimiter (`<<-` must be directly followed by an identifier, e.g. `<<-EOT`)"#}
+    );
+
+    // Errors within the heredoc template are reported where they occur.
+    assert_error!(
+        "bar = <<EOT\n${baz\nEOT\n",
+        indoc! {r#"
+             --> HCL parse error in line 3, column 1
+              |
+            3 | EOT
+              | ^---
+              |
+              = expected `}`"#}
+    );
+}
+
+#[test]
+fn extra_ident_chars() {
+    let input = indoc! {r#"
+        aws:region = "eu-west-1"
+
+        k8s:namespace "default" {
+          labels = { app:name = "web" }
+          port   = var:port
+        }
+    "#};
+
+    // Strict HCL does not allow `:` within identifiers.
+    assert!(parse_body(input).is_err());
+
+    let options = ParseOptions::new().extra_ident_chars([':']);
+    let body = options.parse_body(input).unwrap();
+
+    assert!(body.has_attribute("aws:region"));
+
+    let block = body.get_blocks("k8s:namespace").next().unwrap();
+    let port = block.body.get_attribute("port").unwrap();
+    assert_eq!(
+        port.value.as_variable().map(|var| var.as_str()),
+        Some("var:port")
+    );
+
+    // The encoded body is unchanged.
+    assert_eq!(body.to_string(), input);
+
+    // Extra characters are not allowed at the start of an identifier.
+    assert!(options.parse_body(":region = 1").is_err());
+
+    // The default options behave exactly like the free parser functions.
+    assert!(ParseOptions::default().parse_body(input).is_err());
+
+    // `.` within identifiers shadows the attribute access operator.
+    let body = parse_body("attr = var.name").unwrap();
+    assert!(matches!(
+        body.get_attribute("attr").unwrap().value,
+        Expression::Traversal(_)
+    ));
+
+    let options = ParseOptions::new().extra_ident_chars(['.']);
+    let expr = options.parse_expr("var.name").unwrap();
+    assert_eq!(expr.as_variable().map(|var| var.as_str()), Some("var.name"));
+}
+
+#[test]
+fn quoted_attr_keys() {
+    let input = indoc! {r#"
+        "region" = "eu-west-1" # quoted
+        "aws:profile"  = "default"
+
+        provider "aws" {
+          "max_retries" = 3
+        }
+
+        block { "one" = "line" }
+        plain = true
+    "#};
+
+    // Strict HCL does not allow quoted attribute keys.
+    assert!(parse_body(input).is_err());
+
+    let options = ParseOptions::new().quoted_attr_keys(true);
+    let body = options.parse_body(input).unwrap();
+
+    let region = body.get_attribute("region").unwrap();
+    assert!(region.quoted_key());
+    assert_eq!(region.value.as_str(), Some("eu-west-1"));
+
+    assert!(body.get_attribute("aws:profile").unwrap().quoted_key());
+    assert!(!body.get_attribute("plain").unwrap().quoted_key());
+
+    let provider = body.get_blocks("provider").next().unwrap();
+    assert!(provider
+        .body
+        .get_attribute("max_retries")
+        .unwrap()
+        .quoted_key());
+
+    let block = body.get_blocks("block").next().unwrap();
+    assert!(block.body.get_attribute("one").unwrap().quoted_key());
+
+    // Quoted keys are written quoted again.
+    assert_eq!(body.to_string(), input);
+
+    // Quoted and unquoted keys are considered equal.
+    assert_eq!(
+        options
+            .parse_body("foo = 1\n\"foo\" = 2")
+            .unwrap_err()
+            .to_string(),
+        indoc! {r#"
+             --> HCL parse error in line 2, column 1
+              |
+            2 | "foo" = 2
+              | ^---
+              |
+              = invalid attribute; expected unique attribute key; found redefined attribute"#}
+    );
+
+    // Quoted strings are still not allowed as block identifiers.
+    assert!(options.parse_body("\"block\" {}").is_err());
+
+    // Quoted keys must be non-empty literal strings.
+    for input in ["\"\" = 1", "\"a${b}\" = 1"] {
+        assert_eq!(
+            options.parse_body(input).unwrap_err().message(),
+            "quoted attribute key must be a non-empty literal string"
+        );
+    }
+
+    // Keys which are not valid identifiers cannot be unquoted.
+    let mut attr = body.get_attribute("aws:profile").unwrap().clone();
+    assert!(attr.set_quoted_key(false).is_err());
+    assert!(attr.quoted_key());
+
+    let mut attr = region.clone();
+    attr.set_quoted_key(false).unwrap();
+    assert!(!attr.quoted_key());
+    assert_eq!(
+        Body::builder().attribute(attr).build().to_string(),
+        "region = \"eu-west-1\" # quoted\n"
+    );
+}
+
+#[test]
+fn single_structures() {
+    let attr = parse_attribute("// comment\nfoo = [1, 2] # trailing\n").unwrap();
+    assert_eq!(attr.key.as_str(), "foo");
+    assert_eq!(
+        attr.decor().prefix().map(|prefix| prefix.as_ref()),
+        Some("// comment\n")
+    );
+    assert_eq!(
+        attr.decor().suffix().map(|suffix| suffix.as_ref()),
+        Some(" # trailing")
+    );
+
+    let block = parse_block(indoc! {r#"
+        resource "aws_s3_bucket" "bucket" {
+          bucket = "my-bucket"
+        }
+    "#})
+    .unwrap();
+    assert_eq!(block.ident.as_str(), "resource");
+    assert_eq!(block.labels.len(), 2);
+    assert!(block.body.has_attribute("bucket"));
+
+    let err = parse_attribute("foo = 1\nbar = 2\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        indoc! {r#"
+             --> HCL parse error in line 2, column 1
+              |
+            2 | bar = 2
+              | ^---
+              |
+              = expected end of input"#}
+    );
+
+    let err = parse_block("foo {}\n\nbar {}").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        indoc! {r#"
+             --> HCL parse error in line 3, column 1
+              |
+            3 | bar {}
+              | ^---
+              |
+              = expected end of input"#}
+    );
+
+    let err = parse_attribute("foo {}").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        indoc! {r#"
+             --> HCL parse error in line 1, column 1
+              |
+            1 | foo {}
+              | ^---
+              |
+              = expected attribute"#}
+    );
+
+    assert!(parse_block("foo = 1").is_err());
+    assert!(parse_block("").is_err());
+    assert!(parse_attribute("").is_err());
+}
@@ -0,0 +1,450 @@
+use hcl_edit::expr::Expression;
+use hcl_edit::structure::{Attribute, Block, Body};
+use hcl_edit::{Decorate, Ident, NodeRef, Span};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn unwrap_interpolations() {
+    let input = indoc! {r#"
+        resource "aws_instance" "web" {
+          count    = "${length(var.x)}"
+          ami      = "${ lookup(var.amis, "${var.region}") }" // nested
+          tags     = { Name = "${var.name}" }
+          name     = "web-${count.index}"
+          template = <<EOT
+        ${var.template}
+        EOT
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        resource "aws_instance" "web" {
+          count    = length(var.x)
+          ami      = lookup(var.amis, var.region) // nested
+          tags     = { Name = var.name }
+          name     = "web-${count.index}"
+          template = <<EOT
+        ${var.template}
+        EOT
+        }
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+    body.unwrap_interpolations();
+    assert_eq!(body.to_string(), expected);
+}
+
+#[test]
+fn insert_block_nested() {
+    let input = indoc! {r#"
+        module "vpc" {
+          source = "./vpc"
+          # The subnets.
+          subnet "a" {
+            cidr = "10.0.0.0/24"
+          }
+        }
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+
+    let new_block = || {
+        Block::builder(Ident::new("subnet"))
+            .label("b")
+            .attribute(Attribute::new(Ident::new("cidr"), "10.0.1.0/24"))
+            .block(
+                Block::builder(Ident::new("tags"))
+                    .attribute(Attribute::new(Ident::new("name"), "b"))
+                    .build(),
+            )
+            .build()
+    };
+
+    let module = body.get_blocks_mut("module").next().unwrap();
+    module.body.insert_block_after(1, new_block());
+    module.body.insert_block_before(1, new_block());
+
+    let expected = indoc! {r#"
+        module "vpc" {
+          source = "./vpc"
+
+          subnet "b" {
+            cidr = "10.0.1.0/24"
+            tags {
+              name = "b"
+            }
+          }
+
+          # The subnets.
+          subnet "a" {
+            cidr = "10.0.0.0/24"
+          }
+
+          subnet "b" {
+            cidr = "10.0.1.0/24"
+            tags {
+              name = "b"
+            }
+          }
+        }
+    "#};
+
+    assert_eq!(body.to_string(), expected);
+}
+
+#[test]
+#[should_panic = "insertion index (is 1) should be < len (is 1)"]
+fn insert_block_out_of_bounds() {
+    let mut body: Body = "a = 1\n".parse().unwrap();
+    body.insert_block_after(1, Block::new(Ident::new("b")));
+}
+
+#[test]
+fn replace_expression_copying_decor() {
+    let input = indoc! {r#"
+        resource "aws_instance" "web" {
+          ami           = "ami-123" # The AMI.
+          instance_type = /* size */ "t2.micro"
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        resource "aws_instance" "web" {
+          ami           = var.ami # The AMI.
+          instance_type = /* size */ "t3.micro"
+        }
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+    let block = body.get_blocks_mut("resource").next().unwrap();
+
+    for (key, value) in [("ami", "var.ami"), ("instance_type", "\"t3.micro\"")] {
+        let mut attr = block.body.get_attribute_mut(key).unwrap();
+        let mut expr: Expression = value.parse().unwrap();
+        expr.copy_decor_from(&attr.value);
+        *attr.value_mut() = expr;
+    }
+
+    assert_eq!(body.to_string(), expected);
+}
+
+#[test]
+fn edit_conditional_preserving_decor() {
+    let input = indoc! {r#"
+        instance_type = var.prod ? /* large */ "m5.large" : "t3.micro" # default
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+    let mut attr = body.get_attribute_mut("instance_type").unwrap();
+    let cond = attr.value_mut().as_conditional_mut().unwrap();
+
+    cond.swap_branches();
+
+    assert_eq!(
+        body.to_string(),
+        indoc! {r#"
+            instance_type = var.prod ? /* large */ "t3.micro" : "m5.large" # default
+        "#}
+    );
+
+    let mut attr = body.get_attribute_mut("instance_type").unwrap();
+    let cond = attr.value_mut().as_conditional_mut().unwrap();
+
+    let old = cond.set_cond_expr("var.large".parse::<Expression>().unwrap());
+    assert_eq!(old.to_string(), "var.prod");
+    cond.set_false_expr("m5.xlarge");
+
+    assert_eq!(
+        body.to_string(),
+        indoc! {r#"
+            instance_type = var.large ? /* large */ "t3.micro" : "m5.xlarge" # default
+        "#}
+    );
+}
+
+#[test]
+fn node_at() {
+    let input = indoc! {r#"
+        # Comment.
+        resource "aws_instance" "web" {
+          ami  = lookup(var.amis, "${var.region}-${index}")
+          tags = { Name = [for n in local.names : upper(n)] }
+        }
+    "#};
+
+    let body: Body = input.parse().unwrap();
+    let block = input.trim_start_matches("# Comment.\n").trim_end();
+
+    let node_at = |needle: &str, skip: usize| {
+        let offset = input.find(needle).unwrap() + skip;
+        body.node_at(offset).map(|node| {
+            let kind = match node {
+                NodeRef::Attribute(_) => "attribute",
+                NodeRef::Block(_) => "block",
+                NodeRef::BlockLabel(_) => "block label",
+                NodeRef::Expression(_) => "expression",
+                NodeRef::Ident(_) => "ident",
+            };
+
+            (kind, &input[node.span().unwrap()])
+        })
+    };
+
+    assert_eq!(node_at("# Comment", 0), None);
+    assert_eq!(node_at("resource", 2), Some(("ident", "resource")));
+    assert_eq!(node_at("\"web\"", 1), Some(("block label", "\"web\"")));
+    assert_eq!(node_at(" {\n", 0), Some(("block", block)));
+    assert_eq!(node_at("ami", 0), Some(("ident", "ami")));
+    assert_eq!(
+        node_at("= lookup", 0),
+        Some((
+            "attribute",
+            "ami  = lookup(var.amis, \"${var.region}-${index}\")"
+        ))
+    );
+    assert_eq!(node_at("lookup", 1), Some(("ident", "lookup")));
+    assert_eq!(node_at("var.amis", 0), Some(("expression", "var")));
+    assert_eq!(node_at("amis", 0), Some(("ident", "amis")));
+    assert_eq!(node_at("region", 0), Some(("ident", "region")));
+    assert_eq!(node_at("index", 0), Some(("expression", "index")));
+    assert_eq!(
+        node_at("}-$", 1),
+        Some(("expression", "\"${var.region}-${index}\""))
+    );
+    assert_eq!(node_at("Name", 0), Some(("ident", "Name")));
+    assert_eq!(node_at(" n in", 1), Some(("ident", "n")));
+    assert_eq!(node_at("names", 0), Some(("ident", "names")));
+    assert_eq!(node_at("upper", 0), Some(("ident", "upper")));
+    assert_eq!(node_at("(n)", 1), Some(("expression", "n")));
+    assert_eq!(
+        node_at(" : upper", 0),
+        Some(("expression", "[for n in local.names : upper(n)]"))
+    );
+    assert_eq!(node_at("\n}", 1), Some(("block", block)));
+    assert_eq!(body.node_at(input.len()), None);
+}
+
+#[test]
+fn collapse_block_body() {
+    let input = indoc! {r#"
+        empty {
+        }
+
+        single {
+          foo = [1, 2]
+        }
+
+        comment {
+          foo = 1 # comment
+        }
+
+        heredoc {
+          foo = <<EOT
+        bar
+        EOT
+        }
+
+        nested {
+          inner {}
+        }
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+
+    let collapsed: Vec<bool> = body
+        .blocks_mut()
+        .map(|block| block.collapse_body())
+        .collect();
+
+    assert_eq!(collapsed, [true, true, false, false, false]);
+
+    let expected = indoc! {r#"
+        empty {}
+
+        single { foo = [1, 2] }
+
+        comment {
+          foo = 1 # comment
+        }
+
+        heredoc {
+          foo = <<EOT
+        bar
+        EOT
+        }
+
+        nested {
+          inner {}
+        }
+    "#};
+
+    assert_eq!(body.to_string(), expected);
+
+    // The collapsed body can be parsed again.
+    assert_eq!(expected.parse::<Body>().unwrap(), body);
+}
+
+#[test]
+fn expand_block_body() {
+    let input = indoc! {r#"
+        empty { }
+        single { foo = 1 /* comment */ }
+        multi {
+          foo = 1
+        }
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+
+    for block in body.blocks_mut() {
+        assert_eq!(block.is_oneline(), !block.has_ident("multi"));
+        block.expand_body();
+        assert!(!block.is_oneline());
+        block.body.push(Attribute::new(Ident::new("bar"), 2));
+    }
+
+    let expected = indoc! {r#"
+        empty {
+        bar = 2
+        }
+        single {
+        foo = 1 /* comment */
+        bar = 2
+        }
+        multi {
+          foo = 1
+        bar = 2
+        }
+    "#};
+
+    assert_eq!(body.to_string(), expected);
+}
+
+#[test]
+fn string_literals() {
+    let input = indoc! {r#"
+        name = "app"
+
+        service "api" {
+          ports  = [80, "443"]
+          labels = { "app.kubernetes.io/name" = "api", (var.key) = "dynamic" }
+          script = <<-EOT
+            echo ${var.token}
+            %{ if debug }set -x%{ endif }
+          EOT
+
+          nested {
+            secret = lookup(var.secrets, "db", cond ? "yes" : upper("no"))
+          }
+        }
+    "#};
+
+    let body: Body = input.parse().unwrap();
+
+    let literals: Vec<_> = body
+        .string_literals()
+        .into_iter()
+        .map(|lit| {
+            let source = &input[lit.span.unwrap()];
+            (lit.path.join("."), lit.value, source)
+        })
+        .collect();
+
+    let literal =
+        |path: &str, value: &str, source: &'static str| (path.to_owned(), value.to_owned(), source);
+
+    assert_eq!(
+        literals,
+        [
+            literal("name", "app", "\"app\""),
+            literal("service.api.ports.1", "443", "\"443\""),
+            literal(
+                "service.api.labels.app.kubernetes.io/name",
+                "api",
+                "\"api\""
+            ),
+            literal("service.api.labels.(var.key)", "dynamic", "\"dynamic\""),
+            // Heredoc literals are dedented, their spans still cover the indentation.
+            literal("service.api.script", "echo ", "    echo "),
+            literal("service.api.script", "\n", "\n    "),
+            literal("service.api.script", "set -x", "set -x"),
+            literal("service.api.script", "\n", "\n"),
+            literal("service.api.nested.secret", "db", "\"db\""),
+            literal("service.api.nested.secret", "yes", "\"yes\""),
+            literal("service.api.nested.secret", "no", "\"no\""),
+        ]
+    );
+}
+
+#[test]
+fn strip_comments() {
+    let input = indoc! {r#"
+        # Header comment.
+        // Another one.
+
+        locals {
+          // Indented comment.
+          list = [
+            1, # one
+            /* two */ 2,
+            # three
+            3,
+          ]
+
+          obj = {
+            foo = "bar" // trailing
+            /* key */ baz = qux(a, /* arg */ b)
+          }
+
+          cond = var.enabled ? /* yes */ 1 : 0 # trailing
+        }
+
+        /*
+         * Multi-line comment.
+         */
+        resource "aws_s3_bucket" /* label */ "bucket" {
+          bucket = "my-bucket" /* inline */
+
+          acl = "private"
+        }
+
+        # Footer comment.
+    "#};
+
+    let expected = indoc! {r#"
+
+        locals {
+          list = [
+            1,
+            2,
+            3,
+          ]
+
+          obj = {
+            foo = "bar"
+            baz = qux(a, b)
+          }
+
+          cond = var.enabled ? 1 : 0
+        }
+
+        resource "aws_s3_bucket" "bucket" {
+          bucket = "my-bucket"
+
+          acl = "private"
+        }
+
+    "#};
+
+    let mut body: Body = input.parse().unwrap();
+    body.strip_comments();
+
+    let stripped = body.to_string();
+    assert_eq!(stripped, expected);
+
+    // The result is still valid HCL which does not contain any comments.
+    let mut reparsed: Body = stripped.parse().unwrap();
+    reparsed.strip_comments();
+    assert_eq!(reparsed.to_string(), expected);
+}
@@ -0,0 +1,114 @@
+use hcl_edit::expr::Expression;
+use hcl_edit::structure::Body;
+use hcl_edit::visit::{expr_visitor, visit_expr, Visit};
+use hcl_edit::visit_mut::{expr_visitor_mut, visit_expr_mut, VisitMut};
+use hcl_edit::Ident;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+#[derive(Default)]
+struct VariableCollector {
+    variables: Vec<String>,
+}
+
+impl Visit for VariableCollector {
+    fn visit_expr(&mut self, expr: &Expression) {
+        if let Expression::Variable(var) = expr {
+            self.variables.push(var.to_string());
+        } else {
+            visit_expr(self, expr);
+        }
+    }
+}
+
+struct VariableRenamer<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl VisitMut for VariableRenamer<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expression) {
+        match expr {
+            Expression::Variable(var) if var.as_str() == self.from => {
+                *var.value_mut() = Ident::new(self.to);
+            }
+            _ => visit_expr_mut(self, expr),
+        }
+    }
+}
+
+const TEMPLATES: &str = indoc! {r#"
+    string  = "hello ${name}!"
+    heredoc = <<EOT
+    ${name}: %{ if enabled }${name}%{ else }none%{ endif }
+    %{ for item in name }${item}%{ endfor }
+    EOT
+"#};
+
+#[test]
+fn visit_template_elements() {
+    let body: Body = TEMPLATES.parse().unwrap();
+
+    let mut visitor = VariableCollector::default();
+    visitor.visit_body(&body);
+
+    assert_eq!(
+        visitor.variables,
+        ["name", "name", "enabled", "name", "name", "item"]
+    );
+}
+
+#[test]
+fn visit_mut_template_elements() {
+    let mut body: Body = TEMPLATES.parse().unwrap();
+
+    let mut visitor = VariableRenamer {
+        from: "name",
+        to: "username",
+    };
+    visitor.visit_body_mut(&mut body);
+
+    let expected = indoc! {r#"
+        string  = "hello ${username}!"
+        heredoc = <<EOT
+        ${username}: %{ if enabled }${username}%{ else }none%{ endif }
+        %{ for item in username }${item}%{ endfor }
+        EOT
+    "#};
+
+    assert_eq!(body.to_string(), expected);
+}
+
+#[test]
+fn closure_visitors() {
+    let mut body: Body = TEMPLATES.parse().unwrap();
+
+    let mut variables = Vec::new();
+    body.visit(&mut expr_visitor(|expr| {
+        if let Expression::Variable(var) = expr {
+            variables.push(var.to_string());
+        }
+    }));
+
+    assert_eq!(
+        variables,
+        ["name", "name", "enabled", "name", "name", "item"]
+    );
+
+    body.visit_mut(&mut expr_visitor_mut(|expr| {
+        if let Expression::Variable(var) = expr {
+            if var.as_str() == "name" {
+                *var.value_mut() = Ident::new("username");
+            }
+        }
+    }));
+
+    let mut visitor = VariableRenamer {
+        from: "name",
+        to: "username",
+    };
+    let mut expected: Body = TEMPLATES.parse().unwrap();
+    expected.visit_mut(&mut visitor);
+
+    assert_eq!(body.to_string(), expected.to_string());
+}
@@ -15,6 +15,11 @@ type Inner = String;
 
 /// An opaque string storage which inlines small strings on the stack if the `perf` feature is
 /// enabled.
+///
+/// With the `perf` feature, strings which are too large to be inlined are reference counted, so
+/// that cloning an `InternalString` never allocates. The `hcl-edit` parser makes use of this to
+/// let repeated identifiers within a document, like attribute keys and block identifiers, share
+/// the same storage. Without the `perf` feature, every `InternalString` owns its data.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct InternalString(Inner);
 
@@ -32,4 +32,4 @@ pub use self::ident::Ident;
 pub use self::internal_string::InternalString;
 
 #[doc(inline)]
-pub use self::number::Number;
+pub use self::number::{DisplayGo, Number};
@@ -1,9 +1,12 @@
 //! HCL number representation.
 
+use crate::Error;
+use alloc::format;
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use core::str::FromStr;
 #[cfg(feature = "serde")]
 use serde::de::Unexpected;
 
@@ -50,9 +53,18 @@ impl N {
         #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
         let no_fraction = value - (value as i64 as f64) == 0.0;
 
-        if no_fraction {
-            #[allow(clippy::cast_possible_truncation)]
-            N::from(value as i64)
+        // Integral floats outside of the `i64`/`u64` range stay floats instead of being saturated.
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        if no_fraction && value >= i64::MIN as f64 && value < u64::MAX as f64 {
+            if value < 0.0 {
+                N::from(value as i64)
+            } else {
+                N::PosInt(value as u64)
+            }
         } else {
             N::Float(value)
         }
@@ -166,10 +178,17 @@ impl Neg for N {
 
     fn neg(self) -> Self::Output {
         match self {
-            #[allow(clippy::cast_possible_wrap)]
-            N::PosInt(value) => N::NegInt(-(value as i64)),
-            N::NegInt(value) => N::from(-value),
-            N::Float(value) => N::Float(-value),
+            // Negating `0` must not produce a `NegInt`, and values above `i64::MAX` need special
+            // care: `i64::MAX + 1` still fits into an `i64` once negated, everything above does
+            // not.
+            #[allow(clippy::cast_precision_loss)]
+            N::PosInt(value) => match 0i64.checked_sub_unsigned(value) {
+                Some(0) => N::PosInt(0),
+                Some(value) => N::NegInt(value),
+                None => N::Float(-(value as f64)),
+            },
+            N::NegInt(value) => N::PosInt(value.unsigned_abs()),
+            N::Float(value) => N::from_finite_f64(-value),
         }
     }
 }
@@ -308,6 +327,40 @@ impl Number {
         self.n.is_u64()
     }
 
+    /// Returns an object that implements [`Display`][fmt::Display] for formatting the `Number`
+    /// like Go does.
+    ///
+    /// Floats are formatted exactly like Go's `strconv.FormatFloat(f, 'g', -1, 64)` which is
+    /// useful to produce output that is byte-identical to Terraform and other tools written in
+    /// Go. Integers are formatted like the [`Display`][fmt::Display] implementation of `Number`
+    /// does.
+    ///
+    /// The algorithm works as follows: first, the shortest sequence of decimal digits which
+    /// round-trips to the same float is computed. Let `exp` be the decimal exponent of the
+    /// first digit. If `exp < -4 || exp >= 6`, the number is written in scientific notation
+    /// with all digits in the mantissa and an exponent which has an explicit sign and at least
+    /// two digits, e.g. `1.2345675e+06` or `1e-05`. Otherwise it is written in decimal
+    /// notation without an exponent and without trailing zeros, e.g. `123456.789` or `0.0001`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hcl_primitives::Number;
+    /// let float = Number::from_f64(1234567.5).unwrap();
+    /// assert_eq!(float.to_string(), "1234567.5");
+    /// assert_eq!(float.display_go().to_string(), "1.2345675e+06");
+    ///
+    /// let float = Number::from_f64(0.00001).unwrap();
+    /// assert_eq!(float.to_string(), "0.00001");
+    /// assert_eq!(float.display_go().to_string(), "1e-05");
+    ///
+    /// let int = Number::from(1234567);
+    /// assert_eq!(int.display_go().to_string(), "1234567");
+    /// ```
+    pub fn display_go(&self) -> DisplayGo {
+        DisplayGo(*self)
+    }
+
     // Not public API. Used to generate better deserialization errors in `hcl-rs`.
     #[cfg(feature = "serde")]
     #[doc(hidden)]
@@ -391,12 +444,314 @@ impl fmt::Display for Number {
     }
 }
 
+/// Helper struct for formatting a [`Number`] like Go does.
+///
+/// This struct is created by the [`display_go`][Number::display_go] method on [`Number`]. See
+/// its documentation for more details.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayGo(Number);
+
+impl fmt::Display for DisplayGo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.n {
+            N::Float(v) => fmt_go_float(f, v),
+            _ => fmt::Display::fmt(&self.0, f),
+        }
+    }
+}
+
+// Formats a finite float like Go's `strconv.FormatFloat(v, 'g', -1, 64)`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn fmt_go_float(f: &mut fmt::Formatter, v: f64) -> fmt::Result {
+    use core::fmt::Write;
+
+    // `ryu` produces the shortest digit sequence which round-trips, either in decimal or in
+    // scientific notation. Normalize it into the significant digits and the position of the
+    // decimal point relative to the first digit.
+    let mut buf = ryu::Buffer::new();
+    let formatted = buf.format_finite(v);
+    let (neg, formatted) = match formatted.strip_prefix('-') {
+        Some(formatted) => (true, formatted),
+        None => (false, formatted),
+    };
+    let (mantissa, exp) = match formatted.split_once('e') {
+        Some((mantissa, exp)) => (mantissa, exp.parse::<i32>().unwrap()),
+        None => (formatted, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    let mut digits = [0u8; 40];
+    let mut nd = 0;
+    let mut dp = int.len() as i32 + exp;
+
+    for b in int.bytes().chain(frac.bytes()) {
+        if nd == 0 && b == b'0' {
+            dp -= 1;
+        } else {
+            digits[nd] = b;
+            nd += 1;
+        }
+    }
+
+    while nd > 0 && digits[nd - 1] == b'0' {
+        nd -= 1;
+    }
+
+    let digits = &digits[..nd];
+    let digit = |i: i32| usize::try_from(i).ok().and_then(|i| digits.get(i)).copied();
+
+    if neg {
+        f.write_char('-')?;
+    }
+
+    if digits.is_empty() {
+        return f.write_char('0');
+    }
+
+    let exp = dp - 1;
+
+    if (-4..6).contains(&exp) {
+        if dp > 0 {
+            for i in 0..dp {
+                f.write_char(char::from(digit(i).unwrap_or(b'0')))?;
+            }
+        } else {
+            f.write_char('0')?;
+        }
+
+        if nd as i32 > dp {
+            f.write_char('.')?;
+
+            // Digits before the first significant digit are zeros.
+            for i in dp..nd as i32 {
+                f.write_char(char::from(digit(i).unwrap_or(b'0')))?;
+            }
+        }
+
+        Ok(())
+    } else {
+        f.write_char(char::from(digits[0]))?;
+
+        if nd > 1 {
+            f.write_char('.')?;
+
+            for &b in &digits[1..] {
+                f.write_char(char::from(b))?;
+            }
+        }
+
+        let sign = if exp < 0 { '-' } else { '+' };
+        write!(f, "e{sign}{:02}", exp.unsigned_abs())
+    }
+}
+
 impl fmt::Debug for Number {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Number({self})")
     }
 }
 
+/// Parses a `Number` from a decimal string without taking a detour through `f64` whenever the
+/// value can be represented exactly.
+///
+/// The input must follow the HCL number syntax (digits with optional fraction and exponent),
+/// optionally preceded by a `-` sign. The internal representation is chosen as follows:
+///
+/// - Integral values that fit into `u64` (or `i64` if negative) are stored as integers. This
+///   includes inputs with a fraction or exponent like `1.0`, `1e2` or `1.5e3`, as well as large
+///   integers like `18446744073709551615` which are not representable exactly as `f64`.
+/// - All other values are stored as the `f64` closest to the input, e.g. `1.5`, `1e-2` or
+///   integers beyond the `u64` range.
+///
+/// A `Number` does not retain its original textual form: its [`Display`][fmt::Display]
+/// implementation always writes integers in decimal notation and floats using their shortest
+/// representation that parses back into the same value. To re-emit a number exactly as written,
+/// keep the input string alongside it. The `hcl-edit` crate does this for parsed number literals
+/// and exposes the original text via the number's `repr`.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid number or if its value exceeds the range of a
+/// finite `f64`.
+///
+/// # Example
+///
+/// ```
+/// # use hcl_primitives::Number;
+/// let number: Number = "1e2".parse().unwrap();
+/// assert!(number.is_u64());
+/// assert_eq!(number.to_string(), "100");
+///
+/// let number: Number = "1.5".parse().unwrap();
+/// assert!(number.is_f64());
+///
+/// assert!("1e400".parse::<Number>().is_err());
+/// ```
+impl FromStr for Number {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, s),
+        };
+
+        let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+            None => (unsigned, None),
+        };
+
+        let (int, fraction) = match mantissa.split_once('.') {
+            Some((int, fraction)) => (int, Some(fraction)),
+            None => (mantissa, None),
+        };
+
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+        let valid = is_digits(int)
+            && fraction.is_none_or(is_digits)
+            && exponent.is_none_or(|exp| is_digits(exp.strip_prefix(['+', '-']).unwrap_or(exp)));
+
+        if !valid {
+            return Err(Error::new(format!("invalid number: `{s}`")));
+        }
+
+        let exponent = exponent.map_or(Some(0), |exp| exp.parse::<i64>().ok());
+
+        let integer = exponent
+            .and_then(|exp| parse_integral(int, fraction.unwrap_or_default(), exp))
+            .and_then(|value| {
+                if !negative {
+                    Some(Number::from(value))
+                } else if value == 1 << 63 {
+                    Some(Number::from(i64::MIN))
+                } else {
+                    i64::try_from(value).ok().map(|value| Number::from(-value))
+                }
+            });
+
+        match integer {
+            Some(number) => Ok(number),
+            None => s
+                .parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .ok_or_else(|| Error::new(format!("number out of range: `{s}`"))),
+        }
+    }
+}
+
+// Parses the integral value of `int.fraction * 10^exponent` exactly. Returns `None` if the value
+// has a fractional part or does not fit into a `u64`.
+fn parse_integral(int: &str, fraction: &str, exponent: i64) -> Option<u64> {
+    let fraction = fraction.trim_end_matches('0');
+    let mut scale = exponent.checked_sub(i64::try_from(fraction.len()).ok()?)?;
+    let mut int = int;
+
+    if scale < 0 && fraction.is_empty() {
+        // Trailing zeros of the integer part can absorb a negative scale.
+        let trimmed = int.trim_end_matches('0');
+        let zeros = i64::try_from(int.len() - trimmed.len()).ok()?;
+        let absorbed = zeros.min(-scale);
+        int = &int[..int.len() - usize::try_from(absorbed).ok()?];
+        scale += absorbed;
+    }
+
+    if scale < 0 {
+        return None;
+    }
+
+    let value = int
+        .bytes()
+        .chain(fraction.bytes())
+        .try_fold(0u64, |acc, b| {
+            acc.checked_mul(10)?.checked_add(u64::from(b - b'0'))
+        })?;
+
+    if value == 0 {
+        return Some(0);
+    }
+
+    value.checked_mul(10u64.checked_pow(u32::try_from(scale).ok()?)?)
+}
+
+/// Converts a [`Decimal`][rust_decimal::Decimal] into a `Number`.
+///
+/// Integral decimals within the `u64` or `i64` range are converted exactly. All other values are
+/// converted to the closest `f64`, which may lose precision: a `Decimal` holds up to 28
+/// significant digits whereas a `f64` only holds about 15 to 17.
+///
+/// # Example
+///
+/// ```
+/// # use hcl_primitives::Number;
+/// use rust_decimal::Decimal;
+///
+/// assert_eq!(Number::from(Decimal::new(4200, 2)), Number::from(42));
+/// assert_eq!(Number::from(Decimal::new(1999, 2)), Number::from_f64(19.99).unwrap());
+/// ```
+#[cfg(feature = "decimal")]
+impl From<rust_decimal::Decimal> for Number {
+    fn from(decimal: rust_decimal::Decimal) -> Self {
+        // The string representation of a `Decimal` is always a valid number within the range of
+        // a finite `f64`, so parsing cannot fail.
+        format!("{decimal}")
+            .parse()
+            .expect("a Decimal failed to convert unexpectedly")
+    }
+}
+
+/// Converts a `Number` into a [`Decimal`][rust_decimal::Decimal].
+///
+/// Integers are converted exactly. Floats are converted from their shortest decimal
+/// representation, so that `0.1` becomes exactly `0.1` instead of the binary approximation
+/// stored in the `f64`.
+///
+/// # Errors
+///
+/// Returns an error if the number is outside of the range of a `Decimal` (roughly `±7.9e28`) or
+/// if it requires more than 28 digits after the decimal point, e.g. `1e-30`.
+///
+/// # Example
+///
+/// ```
+/// # use hcl_primitives::Number;
+/// use rust_decimal::Decimal;
+///
+/// let number: Number = "19.99".parse().unwrap();
+/// assert_eq!(Decimal::try_from(number).unwrap(), Decimal::new(1999, 2));
+///
+/// let number: Number = "1e30".parse().unwrap();
+/// assert!(Decimal::try_from(number).is_err());
+/// ```
+#[cfg(feature = "decimal")]
+impl TryFrom<Number> for rust_decimal::Decimal {
+    type Error = Error;
+
+    fn try_from(number: Number) -> Result<Self, Self::Error> {
+        use rust_decimal::Decimal;
+
+        match number.n {
+            N::PosInt(v) => Ok(Decimal::from(v)),
+            N::NegInt(v) => Ok(Decimal::from(v)),
+            N::Float(v) => {
+                let mut buf = ryu::Buffer::new();
+                let s = buf.format_finite(v);
+
+                let result = if s.contains('e') {
+                    Decimal::from_scientific(s)
+                } else {
+                    Decimal::from_str_exact(s)
+                };
+
+                result.map_err(|err| {
+                    Error::new(format!("cannot convert number `{s}` to decimal: {err}"))
+                })
+            }
+        }
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for Number {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -492,11 +847,54 @@ mod tests {
         };
     }
 
+    #[test]
+    fn display_go() {
+        // Expected values were obtained via Go's `strconv.FormatFloat(f, 'g', -1, 64)`.
+        let cases = [
+            (1.5, "1.5"),
+            (-1.5, "-1.5"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (0.123_456_789_012_345_68, "0.12345678901234568"),
+            (0.0001, "0.0001"),
+            (0.000_012_5, "1.25e-05"),
+            (-2.5e-7, "-2.5e-07"),
+            (123_456.789, "123456.789"),
+            (999_999.5, "999999.5"),
+            (1_234_567.5, "1.2345675e+06"),
+            (1e20, "1e+20"),
+            (-1.5e21, "-1.5e+21"),
+            (1e100, "1e+100"),
+            (5e-324, "5e-324"),
+            (f64::MAX, "1.7976931348623157e+308"),
+        ];
+
+        for (float, expected) in cases {
+            assert_eq!(float!(float).display_go().to_string(), expected);
+        }
+
+        // Integers are not affected.
+        assert_eq!(int!(1_234_567).display_go().to_string(), "1234567");
+        assert_eq!(int!(-12i64).display_go().to_string(), "-12");
+        assert_eq!(
+            int!(u64::MAX).display_go().to_string(),
+            "18446744073709551615"
+        );
+    }
+
     #[test]
     fn neg() {
         assert_op!(-int!(1u64), int!(-1i64), is_i64);
         assert_op!(-float!(1.5), float!(-1.5), is_f64);
         assert_op!(-float!(1.0), int!(-1i64), is_i64);
+        assert_op!(-int!(0u64), int!(0u64), is_u64);
+        assert_op!(-int!(-5i64), int!(5u64), is_u64);
+        assert_op!(-int!(i64::MIN), int!(i64::MIN.unsigned_abs()), is_u64);
+        assert_op!(-int!(i64::MIN.unsigned_abs()), int!(i64::MIN), is_i64);
+        assert_op!(
+            -int!(u64::MAX),
+            float!(-18_446_744_073_709_551_615.0),
+            is_f64
+        );
     }
 
     #[test]
@@ -546,4 +944,91 @@ mod tests {
         assert_op!(float!(4.0) % float!(2.0), int!(0), is_u64);
         assert_op!(float!(-4.0) % float!(3.0), int!(-1), is_i64);
     }
+
+    #[test]
+    fn from_str() {
+        macro_rules! assert_parse {
+            ($s:expr, $expected:expr, $check:ident) => {
+                let number: Number = $s.parse().unwrap();
+                assert_eq!(number, $expected, "incorrect number parsed from `{}`", $s);
+                assert!(number.$check(), "unexpected representation for `{}`", $s);
+            };
+        }
+
+        assert_parse!("100", int!(100u64), is_u64);
+        assert_parse!("-100", int!(-100i64), is_i64);
+        assert_parse!("1.5", float!(1.5), is_f64);
+        assert_parse!("-1.5", float!(-1.5), is_f64);
+        assert_parse!("1e2", int!(100u64), is_u64);
+        assert_parse!("1.50E+3", int!(1500u64), is_u64);
+        assert_parse!("100e-2", int!(1u64), is_u64);
+        assert_parse!("1e-2", float!(0.01), is_f64);
+        assert_parse!("0.0", int!(0u64), is_u64);
+        assert_parse!("-9223372036854775808", int!(i64::MIN), is_i64);
+        assert_parse!("18446744073709551615", int!(u64::MAX), is_u64);
+        assert_parse!("18446744073709551615e0", int!(u64::MAX), is_u64);
+        assert_parse!(
+            "18446744073709551616",
+            float!(18_446_744_073_709_551_616.0),
+            is_f64
+        );
+        assert_parse!("1e20", float!(1e20), is_f64);
+
+        for invalid in [
+            "", "-", "+1", "1.", ".5", "1e", "1e+", "0x10", "NaN", "inf", "1e400",
+        ] {
+            assert!(
+                invalid.parse::<Number>().is_err(),
+                "expected `{invalid}` to fail"
+            );
+        }
+    }
+
+    #[test]
+    fn from_f64_out_of_integer_range() {
+        assert!(Number::from_f64(1e20).unwrap().is_f64());
+        assert!(Number::from_f64(-1e19).unwrap().is_f64());
+        assert!(Number::from_f64(1e19).unwrap().is_u64());
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn decimal_conversions() {
+        use rust_decimal::Decimal;
+
+        macro_rules! assert_roundtrip {
+            ($input:expr, $decimal:expr, $check:ident) => {
+                let number: Number = $input.parse().unwrap();
+                assert!(number.$check());
+                assert_eq!(Decimal::try_from(number).unwrap(), $decimal);
+                assert_eq!(Number::from($decimal), number);
+            };
+        }
+
+        assert_roundtrip!("0", Decimal::ZERO, is_u64);
+        assert_roundtrip!("42", Decimal::new(42, 0), is_u64);
+        assert_roundtrip!("-42", Decimal::new(-42, 0), is_i64);
+        assert_roundtrip!("19.99", Decimal::new(1999, 2), is_f64);
+        assert_roundtrip!("-0.1", Decimal::new(-1, 1), is_f64);
+        assert_roundtrip!("1.5e-7", Decimal::new(15, 8), is_f64);
+        assert_roundtrip!("1e20", Decimal::from(10u128.pow(20)), is_f64);
+        assert_roundtrip!("18446744073709551615", Decimal::from(u64::MAX), is_u64);
+
+        // Trailing zeros do not affect the conversion into a `Number`.
+        assert_eq!(Number::from(Decimal::new(1000, 3)), int!(1u64));
+
+        // Precision beyond `f64` is lost.
+        assert_eq!(
+            Number::from(Decimal::from_str_exact("0.1234567890123456789").unwrap()),
+            float!(0.123_456_789_012_345_68)
+        );
+
+        for out_of_range in ["1e29", "-1e29", "1e-29"] {
+            let number: Number = out_of_range.parse().unwrap();
+            assert!(
+                Decimal::try_from(number).is_err(),
+                "expected `{out_of_range}` to fail"
+            );
+        }
+    }
 }
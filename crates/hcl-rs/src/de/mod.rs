@@ -3,10 +3,99 @@
 //! The `Deserializer` implementation tries to follow the [HCL JSON Specification][hcl-json-spec]
 //! as close as possible.
 //!
+//! # Unknown attributes and blocks
+//!
+//! Like most serde deserializers, attributes and blocks which do not correspond to a field of
+//! the target struct are ignored by default. Annotate the struct with
+//! `#[serde(deny_unknown_fields)]` to reject them instead, or enable
+//! [strict mode][Deserializer::strict] to reject unknown top-level attributes and blocks
+//! regardless of the struct's serde attributes.
+//!
+//! # Invalid attribute values
+//!
+//! When deserializing into a struct, errors caused by the value of an attribute, e.g. a string
+//! where the struct field expects a number, are reported as [`Error::InvalidAttribute`] which
+//! carries the key of the offending attribute. Keys of attributes within nested blocks are
+//! prefixed with the block identifier and labels, e.g. `server.port`, and with the index of the
+//! block if there are multiple blocks with the same identifier, e.g. `server[1].port`.
+//!
+//! # Deserializing into maps
+//!
+//! Besides structs, a body can be deserialized into any map type like `HashMap` or `BTreeMap`.
+//! Every attribute becomes an entry, and blocks are grouped by identifier and nested by their
+//! labels following the JSON specification.
+//!
+//! ```
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use std::collections::HashMap;
+//!
+//! let input = r#"
+//!     region = "eu-west-1"
+//!
+//!     bucket "logs" {
+//!       versioning = true
+//!     }
+//! "#;
+//!
+//! let map: HashMap<String, hcl::Value> = hcl::from_str(input)?;
+//!
+//! assert_eq!(map["region"], hcl::Value::from("eu-west-1"));
+//! assert_eq!(map["bucket"], hcl::value!({ logs = { versioning = true } }));
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! # Flattened fields
+//!
+//! Struct fields annotated with `#[serde(flatten)]` are supported. This is useful to capture
+//! all attributes and blocks which are not covered by the named fields of a struct:
+//!
+//! ```
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use serde::Deserialize;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     #[serde(flatten)]
+//!     extra: HashMap<String, hcl::Value>,
+//! }
+//!
+//! let config: Config = hcl::from_str(r#"
+//!     name    = "app"
+//!     replicas = 3
+//! "#)?;
+//!
+//! assert_eq!(config.name, "app");
+//! assert_eq!(config.extra["replicas"], hcl::Value::from(3));
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! Serde buffers the input for structs with flattened fields and deserializes them like maps,
+//! which has some consequences:
+//!
+//! - Flattened fields must not use [`Expression`] or types containing it, because expressions
+//!   cannot be reconstructed from the buffered data. Use [`Value`][crate::Value] instead, which
+//!   represents expressions that are not plain values as template strings, e.g. `"${var.foo}"`.
+//! - Neither [strict mode][Deserializer::strict] nor the reporting of
+//!   [invalid attribute values](#invalid-attribute-values) applies to these structs.
+//!
 //! [hcl-json-spec]: https://github.com/hashicorp/hcl/blob/main/json/spec.md
 
-use crate::structure::IntoJsonSpec;
-use crate::{parser, Body, Error, Identifier, Result};
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod rfc3339;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use self::rfc3339::{rfc3339, FromRfc3339};
+
+use crate::structure::{IntoJsonSpec, JsonNode};
+use crate::{parser, Body, Error, Expression, Identifier, Map, Result, Structure};
 use serde::de::value::StringDeserializer;
 use serde::de::{self, Deserializer as _, IntoDeserializer};
 use serde::forward_to_deserialize_any;
@@ -16,6 +105,7 @@ use std::marker::PhantomData;
 /// A structure that deserializes HCL into Rust values.
 pub struct Deserializer {
     body: Body,
+    strict: bool,
 }
 
 impl Deserializer {
@@ -28,7 +118,54 @@ impl Deserializer {
     /// [Error]: ../error/enum.Error.html
     pub fn from_str(input: &str) -> Result<Self> {
         let body = parser::parse(input)?;
-        Ok(Deserializer { body })
+        Ok(Deserializer::from_body(body))
+    }
+
+    /// Creates a HCL deserializer from a HCL [`Body`].
+    pub fn from_body(body: Body) -> Self {
+        Deserializer {
+            body,
+            strict: false,
+        }
+    }
+
+    /// Controls whether deserializing into a struct fails if the HCL body contains attributes or
+    /// blocks that do not correspond to a field of the struct.
+    ///
+    /// Strict mode only applies to the top-level body. Nested blocks and objects are
+    /// deserialized leniently unless the respective struct is annotated with
+    /// `#[serde(deny_unknown_fields)]`.
+    ///
+    /// Strict mode is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::de::Deserializer;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let input = r#"
+    ///     name = "app"
+    ///     nmae = "typo"
+    /// "#;
+    ///
+    /// let lenient = Config::deserialize(Deserializer::from_str(input)?)?;
+    /// assert_eq!(lenient.name, "app");
+    ///
+    /// let err = Config::deserialize(Deserializer::from_str(input)?.strict(true)).unwrap_err();
+    /// assert_eq!(err.to_string(), "unknown field `nmae`, expected `name`");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn strict(mut self, yes: bool) -> Self {
+        self.strict = yes;
+        self
     }
 }
 
@@ -205,7 +342,7 @@ pub fn from_body<T>(body: Body) -> Result<T>
 where
     T: de::DeserializeOwned,
 {
-    T::deserialize(Deserializer { body })
+    T::deserialize(Deserializer::from_body(body))
 }
 
 impl<'de> de::Deserializer<'de> for Deserializer {
@@ -251,9 +388,194 @@ impl<'de> de::Deserializer<'de> for Deserializer {
             .deserialize_enum(name, variants, visitor)
     }
 
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.strict {
+            for structure in &self.body {
+                let key = match structure {
+                    Structure::Attribute(attr) => attr.key(),
+                    Structure::Block(block) => block.identifier(),
+                };
+
+                if !fields.contains(&key) {
+                    return Err(de::Error::unknown_field(key, fields));
+                }
+            }
+        }
+
+        visitor.visit_map(BodyAccess::new(self.body.into_json_nodes()))
+    }
+
     forward_to_deserialize_any! {
         bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
         bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+// Provides access to the structures of a body for deserializing it into a struct.
+//
+// Errors while deserializing attribute values are wrapped in `Error::InvalidAttribute` to report
+// the offending attribute key. For attributes within nested blocks, the key is prefixed with the
+// path of the block, e.g. `server.port`.
+struct BodyAccess {
+    iter: <Map<String, JsonNode> as IntoIterator>::IntoIter,
+    value: Option<(String, JsonNode)>,
+    path: String,
+}
+
+impl BodyAccess {
+    fn new(nodes: Map<String, JsonNode>) -> BodyAccess {
+        BodyAccess::with_path(nodes, String::new())
+    }
+
+    fn with_path(nodes: Map<String, JsonNode>, path: String) -> BodyAccess {
+        BodyAccess {
+            iter: nodes.into_iter(),
+            value: None,
+            path,
+        }
+    }
+
+    fn key_path(&self, key: String) -> String {
+        if self.path.is_empty() {
+            key
+        } else {
+            format!("{}.{key}", self.path)
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for BodyAccess {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, node)) => {
+                let deserializer: StringDeserializer<Error> = key.clone().into_deserializer();
+                self.value = Some((key, node));
+                seed.deserialize(deserializer).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some((key, JsonNode::Expr(expr))) => {
+                seed.deserialize(expr)
+                    .map_err(|err| Error::InvalidAttribute {
+                        key: self.key_path(key),
+                        source: Box::new(err),
+                    })
+            }
+            Some((key, node)) => seed.deserialize(NodeDeserializer::new(node, self.key_path(key))),
+            None => Err(de::Error::custom("invalid map access")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+// Deserializes blocks and block labels as per the HCL JSON specification, like the `Expression`
+// they convert into, while keeping track of the path for reporting invalid attributes.
+struct NodeDeserializer {
+    node: JsonNode,
+    path: String,
+}
+
+impl NodeDeserializer {
+    fn new(node: JsonNode, path: String) -> NodeDeserializer {
+        NodeDeserializer { node, path }
+    }
+}
+
+impl IntoDeserializer<'_, Error> for NodeDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.node {
+            JsonNode::Map(map) => visitor.visit_map(BodyAccess::with_path(map, self.path)),
+            JsonNode::Body(mut bodies) if bodies.len() == 1 => {
+                let nodes = bodies.remove(0).into_json_nodes();
+                visitor.visit_map(BodyAccess::with_path(nodes, self.path))
+            }
+            JsonNode::Body(bodies) => {
+                let path = self.path;
+                let mut seq = de::value::SeqDeserializer::new(bodies.into_iter().enumerate().map(
+                    |(index, body)| {
+                        NodeDeserializer::new(
+                            JsonNode::Body(vec![body]),
+                            format!("{path}[{index}]"),
+                        )
+                    },
+                ));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            JsonNode::Expr(expr) => expr.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.node {
+            JsonNode::Expr(expr) => expr.deserialize_option(visitor),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        Expression::from(self.node).deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
         tuple_struct map struct identifier ignored_any
     }
 }
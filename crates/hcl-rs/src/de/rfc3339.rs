@@ -0,0 +1,123 @@
+use serde::de::{self, Deserializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Deserialize an [RFC 3339][rfc3339] timestamp stored in an HCL string into a date time type.
+///
+/// This function is meant to be used via serde's `deserialize_with` field attribute. The string
+/// must be a complete RFC 3339 timestamp including a UTC offset, e.g. `2024-01-31T12:30:00Z` or
+/// `2024-01-31T12:30:00+01:00`, which is the format produced by Terraform's `timestamp()`
+/// function.
+///
+/// The supported target types depend on the enabled Cargo features:
+///
+/// - `chrono`: `chrono::DateTime<FixedOffset>` and `chrono::DateTime<Utc>`.
+/// - `time`: `time::OffsetDateTime`.
+///
+/// # Errors
+///
+/// Deserialization fails if the value is not a valid RFC 3339 timestamp string. The error message
+/// contains the offending value.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::{DateTime, Utc};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(deserialize_with = "hcl::de::rfc3339")]
+///     created_at: DateTime<Utc>,
+/// }
+///
+/// let config: Config = hcl::from_str(r#"created_at = "2024-01-31T12:30:00+01:00""#)?;
+///
+/// assert_eq!(config.created_at.to_rfc3339(), "2024-01-31T11:30:00+00:00");
+/// # }
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [rfc3339]: https://datatracker.ietf.org/doc/html/rfc3339
+pub fn rfc3339<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromRfc3339,
+{
+    deserializer.deserialize_str(Rfc3339Visitor(PhantomData))
+}
+
+/// Types which can be parsed from an RFC 3339 timestamp.
+///
+/// This trait is sealed and cannot be implemented outside of this crate. See [`rfc3339`] for
+/// the list of implementors.
+pub trait FromRfc3339: Sized + private::Sealed {
+    #[doc(hidden)]
+    fn from_rfc3339(s: &str) -> Result<Self, String>;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::{private, FromRfc3339};
+    use chrono::{DateTime, FixedOffset, Utc};
+
+    impl private::Sealed for DateTime<FixedOffset> {}
+
+    impl FromRfc3339 for DateTime<FixedOffset> {
+        fn from_rfc3339(s: &str) -> Result<Self, String> {
+            DateTime::parse_from_rfc3339(s).map_err(|err| err.to_string())
+        }
+    }
+
+    impl private::Sealed for DateTime<Utc> {}
+
+    impl FromRfc3339 for DateTime<Utc> {
+        fn from_rfc3339(s: &str) -> Result<Self, String> {
+            DateTime::<FixedOffset>::from_rfc3339(s).map(|dt| dt.with_timezone(&Utc))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::{private, FromRfc3339};
+    use time::format_description::well_known::Rfc3339;
+    use time::OffsetDateTime;
+
+    impl private::Sealed for OffsetDateTime {}
+
+    impl FromRfc3339 for OffsetDateTime {
+        fn from_rfc3339(s: &str) -> Result<Self, String> {
+            OffsetDateTime::parse(s, &Rfc3339).map_err(|err| err.to_string())
+        }
+    }
+}
+
+struct Rfc3339Visitor<T>(PhantomData<T>);
+
+impl<T> de::Visitor<'_> for Rfc3339Visitor<T>
+where
+    T: FromRfc3339,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RFC 3339 timestamp")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::from_rfc3339(value)
+            .map_err(|err| E::custom(format_args!("invalid RFC 3339 timestamp `{value}`: {err}")))
+    }
+}
@@ -4,6 +4,7 @@ use crate::eval;
 use serde::{de, ser};
 use std::fmt::{self, Display};
 use std::io;
+use std::path::{Path, PathBuf};
 use std::str::Utf8Error;
 
 /// The result type used by this crate.
@@ -23,6 +24,24 @@ pub enum Error {
     Eval(eval::Error),
     /// Represents errors while parsing HCL.
     Parse(parser::Error),
+    /// Represents an error while deserializing the value of an attribute, e.g. because its type
+    /// does not match the type of the struct field it is deserialized into.
+    InvalidAttribute {
+        /// The key of the attribute which could not be deserialized. For attributes within
+        /// nested blocks, the key is prefixed with the path of the block, e.g. `server.port`.
+        key: String,
+        /// The underlying error.
+        source: Box<Error>,
+    },
+    /// Represents an error which originated from the HCL file at the given path.
+    ///
+    /// See [`Error::with_path`] for more details.
+    File {
+        /// The path of the file which caused the error.
+        path: PathBuf,
+        /// The underlying error.
+        source: Box<Error>,
+    },
 }
 
 impl Error {
@@ -32,6 +51,52 @@ impl Error {
     {
         Error::Message(msg.to_string())
     }
+
+    /// Attaches the path of the file which caused the error.
+    ///
+    /// This is useful when loading multiple HCL files, where errors are otherwise
+    /// indistinguishable once they are collected. The path is prepended to the error message. If
+    /// the error already carries a path, it is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// let files = [("main.tf", "a = 1"), ("variables.tf", "b = ")];
+    ///
+    /// let errors: Vec<hcl::Error> = files
+    ///     .into_iter()
+    ///     .filter_map(|(path, input)| hcl::parse(input).err().map(|err| err.with_path(path)))
+    ///     .collect();
+    ///
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].path(), Some(Path::new("variables.tf")));
+    /// assert!(errors[0].to_string().starts_with("variables.tf: "));
+    /// ```
+    pub fn with_path<P>(self, path: P) -> Error
+    where
+        P: Into<PathBuf>,
+    {
+        let source = match self {
+            Error::File { source, .. } => source,
+            err => Box::new(err),
+        };
+
+        Error::File {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Returns the path of the file which caused the error, if it was attached via
+    /// [`Error::with_path`].
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::File { path, .. } => Some(path),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Error {
@@ -42,6 +107,10 @@ impl Display for Error {
             Error::Message(msg) => write!(f, "{msg}"),
             Error::Eval(err) => write!(f, "eval error: {err}"),
             Error::Parse(err) => write!(f, "{err}"),
+            Error::InvalidAttribute { key, source } => {
+                write!(f, "invalid value for attribute `{key}`: {source}")
+            }
+            Error::File { path, source } => write!(f, "{}: {source}", path.display()),
         }
     }
 }
@@ -70,7 +139,14 @@ impl From<eval::Error> for Error {
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidAttribute { source, .. } | Error::File { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl ser::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
@@ -83,3 +159,28 @@ impl de::Error for Error {
         Error::new(msg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::error::Error as _;
+
+    #[test]
+    fn with_path() {
+        let err = crate::parse("a = ").unwrap_err();
+        let msg = err.to_string();
+
+        let err = err.with_path("modules/vpc/main.tf");
+        assert_eq!(err.path(), Some(Path::new("modules/vpc/main.tf")));
+        assert_eq!(err.to_string(), format!("modules/vpc/main.tf: {msg}"));
+        assert!(matches!(err.source(), Some(source) if source.to_string() == msg));
+
+        // Attaching another path replaces the existing one.
+        let err = err.with_path("main.tf");
+        assert_eq!(err.path(), Some(Path::new("main.tf")));
+        assert_eq!(err.to_string(), format!("main.tf: {msg}"));
+
+        assert_eq!(Error::new("msg").path(), None);
+    }
+}
@@ -1,6 +1,9 @@
 use super::*;
 use std::collections::VecDeque;
 
+// Conditions of conditionals, `for` expressions and template `if` directives must evaluate to a
+// boolean. Unlike some contexts in the go HCL implementation, strings like `"true"` are
+// deliberately not converted.
 pub(super) fn evaluate_bool(expr: &Expression, ctx: &Context) -> EvalResult<bool> {
     match expr.evaluate(ctx)? {
         Value::Bool(value) => Ok(value),
@@ -175,10 +175,23 @@ impl fmt::Display for ParamType {
 #[derive(Debug, Clone)]
 pub struct FuncDef {
     func: Func,
-    params: Vec<ParamType>,
+    params: Vec<Param>,
     variadic_param: Option<ParamType>,
 }
 
+/// A positional function parameter with an optional name.
+#[derive(Debug, Clone)]
+struct Param {
+    name: Option<String>,
+    ty: ParamType,
+}
+
+impl From<ParamType> for Param {
+    fn from(ty: ParamType) -> Self {
+        Param { name: None, ty }
+    }
+}
+
 impl FuncDef {
     /// Creates a new `FuncDef` from a function and its parameters.
     ///
@@ -220,10 +233,17 @@ impl FuncDef {
         let (pos_args, var_args) = args.split_at(params_len);
 
         for (pos, (arg, param)) in pos_args.iter().zip(self.params.iter()).enumerate() {
-            if !param.is_satisfied_by(arg) {
-                return Err(format!(
-                    "expected argument at position {pos} to be of type {param}, got `{arg}`",
-                ));
+            if !param.ty.is_satisfied_by(arg) {
+                let ty = &param.ty;
+
+                return Err(match &param.name {
+                    Some(name) => format!(
+                        "expected argument `{name}` at position {pos} to be of type {ty}, got `{arg}`",
+                    ),
+                    None => format!(
+                        "expected argument at position {pos} to be of type {ty}, got `{arg}`",
+                    ),
+                });
             }
         }
 
@@ -255,7 +275,7 @@ impl FuncDef {
 /// [`.builder()`]: FuncDef::builder
 #[derive(Debug)]
 pub struct FuncDefBuilder {
-    params: Vec<ParamType>,
+    params: Vec<Param>,
     variadic_param: Option<ParamType>,
 }
 
@@ -280,7 +300,56 @@ impl FuncDefBuilder {
     ///     .build(strlen);
     /// ```
     pub fn param(mut self, param: ParamType) -> FuncDefBuilder {
-        self.params.push(param);
+        self.params.push(Param::from(param));
+        self
+    }
+
+    /// Adds a named function parameter.
+    ///
+    /// The name is only used to produce more helpful error messages if an argument does not match
+    /// the parameter type, it does not affect how arguments are passed to the function. Named and
+    /// unnamed parameters can be mixed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl::eval::{Context, FuncArgs, FuncDef, ParamType};
+    /// use hcl::Value;
+    ///
+    /// fn repeat(args: FuncArgs) -> Result<Value, String> {
+    ///     let string = args[0].as_str().unwrap();
+    ///     let count = args[1].as_u64().unwrap();
+    ///     Ok(Value::from(string.repeat(count as usize)))
+    /// }
+    ///
+    /// let func_def = FuncDef::builder()
+    ///     .param_named("string", ParamType::String)
+    ///     .param_named("count", ParamType::Number)
+    ///     .build(repeat);
+    ///
+    /// let mut ctx = Context::new();
+    /// ctx.declare_func("repeat", func_def);
+    ///
+    /// assert_eq!(ctx.evaluate_str(r#"repeat("a", 3)"#)?, Value::from("aaa"));
+    ///
+    /// let err = ctx.evaluate_str(r#"repeat("a", "3")"#).unwrap_err();
+    /// assert!(err
+    ///     .to_string()
+    ///     .contains("expected argument `count` at position 1 to be of type `number`, got `\"3\"`"));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn param_named<N>(mut self, name: N, param: ParamType) -> FuncDefBuilder
+    where
+        N: Into<String>,
+    {
+        self.params.push(Param {
+            name: Some(name.into()),
+            ty: param,
+        });
         self
     }
 
@@ -311,7 +380,7 @@ impl FuncDefBuilder {
     where
         I: IntoIterator<Item = ParamType>,
     {
-        self.params.extend(params);
+        self.params.extend(params.into_iter().map(Param::from));
         self
     }
 
@@ -356,6 +425,39 @@ impl FuncDefBuilder {
 ///
 /// `FuncArgs` behaves exactly like a `Vec<Value>` due to its `Deref` implementation, but exposes
 /// additional methods to iterate over positional and variadic arguments.
+///
+/// # Positional and variadic arguments
+///
+/// The arguments are ordered like the parameters of the [`FuncDef`]: the first arguments are the
+/// values for the positional parameters, followed by zero or more values for the variadic
+/// parameter, if the function definition has one. Since the arguments are validated against the
+/// function definition before the function is called, there is always exactly one argument per
+/// positional parameter. The number of variadic arguments is only known at runtime, so prefer
+/// [`get`][FuncArgs::get] or the iterators over indexing when accessing them.
+///
+/// # Example
+///
+/// ```
+/// use hcl::eval::{FuncArgs, FuncDef, ParamType};
+/// use hcl::Value;
+///
+/// // Joins all variadic arguments using the separator passed as first argument.
+/// fn join(args: FuncArgs) -> Result<Value, String> {
+///     let sep = args[0].as_str().unwrap();
+///
+///     let parts: Vec<&str> = args
+///         .variadic_args()
+///         .map(|arg| arg.as_str().unwrap())
+///         .collect();
+///
+///     Ok(Value::from(parts.join(sep)))
+/// }
+///
+/// let func_def = FuncDef::builder()
+///     .param(ParamType::String)
+///     .variadic_param(ParamType::String)
+///     .build(join);
+/// ```
 #[derive(Debug, Clone)]
 pub struct FuncArgs {
     values: Vec<Value>,
@@ -375,6 +477,28 @@ impl FuncArgs {
         self.values
     }
 
+    /// Returns the total number of arguments, including variadic arguments.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no arguments were passed to the function.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns a reference to the argument at `index`, or `None` if the index is out of bounds.
+    ///
+    /// The index covers positional arguments first, followed by the variadic arguments.
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.values.get(index)
+    }
+
+    /// Returns an iterator over all arguments, including variadic arguments.
+    pub fn iter(&self) -> slice::Iter<'_, Value> {
+        self.values.iter()
+    }
+
     /// Returns an iterator over all positional arguments.
     pub fn positional_args(&self) -> PositionalArgs<'_> {
         PositionalArgs {
@@ -390,6 +514,15 @@ impl FuncArgs {
     }
 }
 
+impl<'a> IntoIterator for &'a FuncArgs {
+    type Item = &'a Value;
+    type IntoIter = slice::Iter<'a, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl ops::Deref for FuncArgs {
     type Target = Vec<Value>;
 
@@ -88,13 +88,14 @@ impl Evaluate for Expression {
             Expression::Array(array) => array.evaluate(ctx).map(Value::Array),
             Expression::Object(object) => object.evaluate(ctx).map(Value::Object),
             Expression::TemplateExpr(expr) => expr.evaluate(ctx),
-            Expression::Variable(ident) => ctx.lookup_var(ident).cloned(),
+            Expression::Variable(ident) => ctx.lookup_var(ident),
             Expression::Traversal(traversal) => traversal.evaluate(ctx),
             Expression::FuncCall(func_call) => func_call.evaluate(ctx),
             Expression::Parenthesis(expr) => expr.evaluate(ctx),
             Expression::Conditional(cond) => cond.evaluate(ctx),
             Expression::Operation(op) => op.evaluate(ctx),
             Expression::ForExpr(expr) => expr.evaluate(ctx),
+            Expression::Raw(raw) => crate::parser::parse_expr(raw)?.evaluate(ctx),
             other => Ok(Value::from(other.clone())),
         }
     }
@@ -406,11 +407,14 @@ impl Evaluate for BinaryOp {
     fn evaluate(&self, ctx: &Context) -> EvalResult<Self::Output> {
         use {BinaryOperator::*, Value::*};
 
-        let op = self.clone().normalize();
-        let lhs = op.lhs_expr.evaluate(ctx)?;
-        let rhs = op.rhs_expr.evaluate(ctx)?;
+        let lhs = self.lhs_expr.evaluate(ctx)?;
+        let rhs = self.rhs_expr.evaluate(ctx)?;
 
-        let value = match (lhs, op.operator, rhs) {
+        if let Some(value) = ctx.eval_binary_op_override(&lhs, self.operator, &rhs)? {
+            return Ok(value);
+        }
+
+        let value = match (lhs, self.operator, rhs) {
             (lhs, Eq, rhs) => Bool(lhs == rhs),
             (lhs, NotEq, rhs) => Bool(lhs != rhs),
             (Bool(lhs), And, Bool(rhs)) => Bool(lhs && rhs),
@@ -67,8 +67,15 @@
 //! # }
 //! ```
 //!
+//! The strip markers (`~`) remove the line breaks directly following the `for` and `endfor`
+//! directives. Every iteration therefore renders exactly one line, and an empty collection does
+//! not leave any blank lines behind.
+//!
 //! If you need to include the literal representation of variable reference, you can escape `${`
-//! with `$${`:
+//! with `$${`. Likewise, `%%{` evaluates to a literal `%{` instead of starting a directive. This
+//! works the same in quoted templates and heredocs. When an evaluated string containing `${` or
+//! `%{` is formatted again, the markers are escaped so that the output does not introduce new
+//! interpolations or directives:
 //!
 //! ```
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -222,6 +229,8 @@ mod error;
 mod expr;
 mod func;
 mod impls;
+#[cfg(feature = "stdlib")]
+pub mod stdlib;
 mod template;
 
 pub use self::error::{Error, ErrorKind, Errors, EvalResult};
@@ -239,6 +248,8 @@ use crate::template::{
 };
 use crate::{Identifier, Map, Result, Value};
 use serde::{de, ser};
+use std::fmt;
+use std::sync::{Arc, OnceLock};
 use vecmap::VecMap;
 
 mod private {
@@ -297,7 +308,10 @@ pub trait Evaluate: private::Sealed {
 #[derive(Debug, Clone)]
 pub struct Context<'a> {
     vars: Map<Identifier, Value>,
+    dyn_vars: VecMap<Identifier, DynVar<'a>>,
+    var_resolver: Option<VarResolver<'a>>,
     funcs: VecMap<FuncName, FuncDef>,
+    binary_ops: VecMap<BinaryOperator, BinaryOpOverride<'a>>,
     parent: Option<&'a Context<'a>>,
     expr: Option<&'a Expression>,
 }
@@ -306,19 +320,96 @@ impl Default for Context<'_> {
     fn default() -> Self {
         Context {
             vars: Map::new(),
+            dyn_vars: VecMap::new(),
+            var_resolver: None,
             funcs: VecMap::new(),
+            binary_ops: VecMap::new(),
             parent: None,
             expr: None,
         }
     }
 }
 
+type DynVarFn<'a> = dyn Fn() -> Value + Send + Sync + 'a;
+
+// A variable whose value is computed by a user-provided callback on first access and cached
+// afterwards.
+#[derive(Clone)]
+struct DynVar<'a> {
+    func: Arc<DynVarFn<'a>>,
+    value: Arc<OnceLock<Value>>,
+}
+
+impl DynVar<'_> {
+    fn value(&self) -> &Value {
+        self.value.get_or_init(|| (self.func)())
+    }
+}
+
+impl fmt::Debug for DynVar<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynVar").field(&self.value.get()).finish()
+    }
+}
+
+type VarResolverFn<'a> = dyn Fn(&Identifier) -> Option<Value> + Send + Sync + 'a;
+
+// A user-provided callback for resolving variables that are not declared in a `Context`.
+#[derive(Clone)]
+struct VarResolver<'a>(Arc<VarResolverFn<'a>>);
+
+impl fmt::Debug for VarResolver<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VarResolver")
+    }
+}
+
+type BinaryOpFn<'a> = dyn Fn(&Value, &Value) -> Option<Result<Value, String>> + Send + Sync + 'a;
+
+// A user-provided callback which overrides the evaluation of a binary operator.
+#[derive(Clone)]
+struct BinaryOpOverride<'a>(Arc<BinaryOpFn<'a>>);
+
+impl fmt::Debug for BinaryOpOverride<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BinaryOpOverride")
+    }
+}
+
 impl<'a> Context<'a> {
     /// Creates an empty `Context`.
     pub fn new() -> Self {
         Context::default()
     }
 
+    /// Creates a `Context` with all functions of the [`stdlib`] module declared under their
+    /// Terraform names.
+    ///
+    /// See [`stdlib::register_stdlib`] for the list of declared functions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::eval::Context;
+    /// use hcl::Value;
+    ///
+    /// let ctx = Context::with_stdlib();
+    ///
+    /// assert_eq!(
+    ///     ctx.evaluate_str(r#"upper(join("-", ["a", "b"]))"#)?,
+    ///     Value::from("A-B"),
+    /// );
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stdlib")]
+    pub fn with_stdlib() -> Self {
+        let mut ctx = Context::new();
+        stdlib::register_stdlib(&mut ctx);
+        ctx
+    }
+
     // Create a new child `Context` which has the current one as parent.
     fn child(&self) -> Context<'_> {
         let mut ctx = Context::new();
@@ -334,6 +425,32 @@ impl<'a> Context<'a> {
         ctx
     }
 
+    // Create a new `Context` which contains the functions and binary operator overrides declared
+    // in the current one and all of its parents, but no variables.
+    pub(crate) fn functions_only(&self) -> Context<'a> {
+        let (mut funcs, mut binary_ops) = self.parent.map_or_else(
+            || (VecMap::new(), VecMap::new()),
+            |parent| {
+                let ctx = parent.functions_only();
+                (ctx.funcs, ctx.binary_ops)
+            },
+        );
+
+        for (name, func) in &self.funcs {
+            funcs.insert(name.clone(), func.clone());
+        }
+
+        for (operator, func) in &self.binary_ops {
+            binary_ops.insert(*operator, func.clone());
+        }
+
+        Context {
+            funcs,
+            binary_ops,
+            ..Context::default()
+        }
+    }
+
     /// Declare a variable from a name and a value.
     ///
     /// # Example
@@ -348,7 +465,100 @@ impl<'a> Context<'a> {
         I: Into<Identifier>,
         T: Into<Value>,
     {
-        self.vars.insert(name.into(), value.into());
+        let name = name.into();
+        self.dyn_vars.remove(&name);
+        self.vars.insert(name, value.into());
+    }
+
+    /// Declare a variable whose value is computed lazily by a callback.
+    ///
+    /// The callback is only called when the variable is referenced during evaluation, which is
+    /// useful for values that are expensive to compute or that come from a remote source. It is
+    /// called at most once: the value is cached in the `Context` and reused for all subsequent
+    /// references. Clones of the `Context` share the cache.
+    ///
+    /// Dynamic variables follow the same scoping rules as variables declared via
+    /// [`declare_var`][Context::declare_var], e.g. they are visible while evaluating nested
+    /// expressions and can be shadowed by the variables of `for` expressions. Declaring a variable
+    /// with the same name again, either dynamic or not, replaces the previous declaration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::eval::Context;
+    /// use hcl::Value;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let calls = AtomicUsize::new(0);
+    ///
+    /// let mut ctx = Context::new();
+    /// ctx.declare_var_dyn("answer", || {
+    ///     calls.fetch_add(1, Ordering::Relaxed);
+    ///     Value::from(42)
+    /// });
+    /// ctx.declare_var_dyn("unused", || unreachable!());
+    ///
+    /// assert_eq!(ctx.evaluate_str("answer + answer")?, Value::from(84));
+    /// assert_eq!(calls.load(Ordering::Relaxed), 1);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn declare_var_dyn<I, F>(&mut self, name: I, func: F)
+    where
+        I: Into<Identifier>,
+        F: Fn() -> Value + Send + Sync + 'a,
+    {
+        let name = name.into();
+        self.vars.shift_remove(&name);
+        self.dyn_vars.insert(
+            name,
+            DynVar {
+                func: Arc::new(func),
+                value: Arc::new(OnceLock::new()),
+            },
+        );
+    }
+
+    /// Sets a callback which is used to resolve variables that are not declared in the `Context`.
+    ///
+    /// This is useful if the variables come from a large external store and should not be
+    /// declared eagerly. The resolver is only called for variables that are actually referenced
+    /// during evaluation.
+    ///
+    /// Variables declared via [`declare_var`][Context::declare_var] take precedence over the
+    /// resolver: the resolver is only consulted if the variable is not declared in the `Context`
+    /// itself. If the resolver returns `None`, evaluation fails with an
+    /// [`UndefinedVar`][ErrorKind::UndefinedVar] error.
+    ///
+    /// Setting a resolver replaces any previously set resolver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::eval::{Context, Evaluate};
+    /// use hcl::expr::TemplateExpr;
+    /// use hcl::Value;
+    ///
+    /// let mut ctx = Context::new();
+    /// ctx.declare_var("greeting", "Hello");
+    /// ctx.set_var_resolver(|name| match name.as_str() {
+    ///     "name" => Some(Value::from("World")),
+    ///     _ => None,
+    /// });
+    ///
+    /// let expr = TemplateExpr::from("${greeting} ${name}!");
+    ///
+    /// assert_eq!(expr.evaluate(&ctx)?, Value::from("Hello World!"));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn set_var_resolver<F>(&mut self, resolver: F)
+    where
+        F: Fn(&Identifier) -> Option<Value> + Send + Sync + 'a,
+    {
+        self.var_resolver = Some(VarResolver(Arc::new(resolver)));
     }
 
     /// Declare a function from a name and a function definition.
@@ -384,12 +594,92 @@ impl<'a> Context<'a> {
         self.funcs.insert(name.into(), func);
     }
 
+    /// Overrides the evaluation of a binary operator.
+    ///
+    /// This is intended for embedding HCL as a DSL with custom semantics. **Overriding operators
+    /// deviates from the HCL specification**, so expressions may evaluate differently than in
+    /// other HCL implementations like Terraform.
+    ///
+    /// The callback receives the evaluated operands and is consulted before the default
+    /// evaluation. It returns:
+    ///
+    /// - `Some(Ok(value))` to use `value` as the result of the operation.
+    /// - `Some(Err(msg))` to fail the evaluation with an error containing `msg`.
+    /// - `None` to fall back to the default HCL semantics, e.g. for operand types the override
+    ///   does not handle.
+    ///
+    /// Overriding the same operator again replaces the previous override.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::eval::Context;
+    /// use hcl::expr::BinaryOperator;
+    /// use hcl::Value;
+    ///
+    /// let mut ctx = Context::new();
+    ///
+    /// // Use `+` to concatenate strings.
+    /// ctx.override_binary_op(BinaryOperator::Plus, |lhs, rhs| match (lhs, rhs) {
+    ///     (Value::String(lhs), Value::String(rhs)) => Some(Ok(Value::from(format!("{lhs}{rhs}")))),
+    ///     _ => None,
+    /// });
+    ///
+    /// assert_eq!(ctx.evaluate_str(r#""foo" + "bar""#)?, Value::from("foobar"));
+    /// assert_eq!(ctx.evaluate_str("1 + 2")?, Value::from(3));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn override_binary_op<F>(&mut self, operator: BinaryOperator, f: F)
+    where
+        F: Fn(&Value, &Value) -> Option<Result<Value, String>> + Send + Sync + 'a,
+    {
+        self.binary_ops
+            .insert(operator, BinaryOpOverride(Arc::new(f)));
+    }
+
+    /// Parses a single HCL expression from a string and evaluates it using this context.
+    ///
+    /// This is a convenience for evaluating user-supplied expressions, e.g. in a REPL. The input
+    /// must be exactly one expression, attributes and blocks of an HCL body are not accepted.
+    ///
+    /// # Errors
+    ///
+    /// This function fails with an error if:
+    ///
+    /// - the string `expr` cannot be parsed as an HCL expression.
+    /// - any condition described in the error section of the [`evaluate` method
+    ///   documentation][Evaluate::evaluate] meets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::eval::Context;
+    /// use hcl::Value;
+    ///
+    /// let mut ctx = Context::new();
+    /// ctx.declare_var("var", hcl::value!({ x = 41 }));
+    ///
+    /// assert_eq!(ctx.evaluate_str("1 + var.x")?, Value::from(42));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate_str(&self, expr: &str) -> Result<Value> {
+        let expr = parser::parse_expr(expr)?;
+        Ok(expr.evaluate(self)?)
+    }
+
     /// Lookup a variable's value.
     ///
     /// When the variable is declared in multiple parent scopes, the innermost variable's value is
-    /// returned.
-    fn lookup_var(&self, name: &Identifier) -> EvalResult<&Value> {
+    /// returned. Variable resolvers are only consulted if the variable is not declared in any
+    /// scope.
+    fn lookup_var(&self, name: &Identifier) -> EvalResult<Value> {
         self.var(name)
+            .cloned()
+            .or_else(|| self.resolve_var(name))
             .ok_or_else(|| self.error(ErrorKind::UndefinedVar(name.clone())))
     }
 
@@ -418,9 +708,44 @@ impl<'a> Context<'a> {
     fn var(&self, name: &Identifier) -> Option<&Value> {
         self.vars
             .get(name)
+            .or_else(|| self.dyn_vars.get(name).map(DynVar::value))
             .or_else(|| self.parent.and_then(|parent| parent.var(name)))
     }
 
+    fn resolve_var(&self, name: &Identifier) -> Option<Value> {
+        self.var_resolver
+            .as_ref()
+            .and_then(|resolver| (resolver.0)(name))
+            .or_else(|| self.parent.and_then(|parent| parent.resolve_var(name)))
+    }
+
+    /// Evaluates the override for a binary operator, if there is any.
+    ///
+    /// When the operator is overridden in multiple parent scopes, the innermost override is used.
+    /// Returns `Ok(None)` if there is no override or if it does not handle the operands.
+    fn eval_binary_op_override(
+        &self,
+        lhs: &Value,
+        operator: BinaryOperator,
+        rhs: &Value,
+    ) -> EvalResult<Option<Value>> {
+        match self.binary_op_override(operator) {
+            Some(f) => (f.0)(lhs, rhs).transpose().map_err(|msg| {
+                self.error(ErrorKind::Message(format!(
+                    "error evaluating binary operator `{operator}`: {msg}"
+                )))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    fn binary_op_override(&self, operator: BinaryOperator) -> Option<&BinaryOpOverride<'a>> {
+        self.binary_ops.get(&operator).or_else(|| {
+            self.parent
+                .and_then(|parent| parent.binary_op_override(operator))
+        })
+    }
+
     fn func(&self, name: &FuncName) -> Option<&FuncDef> {
         self.funcs
             .get(name)
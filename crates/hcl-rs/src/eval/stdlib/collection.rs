@@ -0,0 +1,333 @@
+use super::to_index;
+use crate::eval::{FuncArgs, FuncDef, ParamType};
+use crate::{Map, Value};
+
+/// Returns the [`FuncDef`] for the `length(value)` function.
+///
+/// `length` determines the number of elements of a list or map, or the number of characters in a
+/// string, e.g. `length({ a = 1, b = 2 })` returns `2` and `length("hello")` returns `5`.
+///
+/// Unlike Terraform, which counts grapheme clusters, the length of a string is the number of
+/// Unicode scalar values it contains.
+pub fn length() -> FuncDef {
+    FuncDef::new(
+        length_impl,
+        [ParamType::one_of([
+            ParamType::array_of(ParamType::Any),
+            ParamType::object_of(ParamType::Any),
+            ParamType::String,
+        ])],
+    )
+}
+
+fn length_impl(args: FuncArgs) -> Result<Value, String> {
+    let len = match &args[0] {
+        Value::Array(array) => array.len(),
+        Value::Object(object) => object.len(),
+        Value::String(string) => string.chars().count(),
+        _ => unreachable!(),
+    };
+
+    Ok(Value::from(len))
+}
+
+/// Returns the [`FuncDef`] for the `element(list, index)` function.
+///
+/// `element` retrieves a single element from a list. The index is zero-based and wraps around
+/// if it is greater than or equal to the length of the list, e.g. `element(["a", "b"], 3)`
+/// returns `"b"`.
+///
+/// The function returns an error if the list is empty, or if the index is negative or not a
+/// whole number. To access the last element of a list without knowing its length, use
+/// `element(list, length(list) - 1)`.
+pub fn element() -> FuncDef {
+    FuncDef::new(
+        element_impl,
+        [ParamType::array_of(ParamType::Any), ParamType::Number],
+    )
+}
+
+fn element_impl(args: FuncArgs) -> Result<Value, String> {
+    let list = args[0].as_array().unwrap();
+    let index = to_index(&args[1], "element")?;
+
+    if list.is_empty() {
+        return Err(String::from(
+            "cannot use element function with an empty list",
+        ));
+    }
+
+    Ok(list[index % list.len()].clone())
+}
+
+/// Returns the [`FuncDef`] for the `index(list, value)` function.
+///
+/// `index` finds the zero-based position of the first element of a list which is equal to the
+/// given value.
+///
+/// The function returns an error if the list is empty or if it does not contain the value.
+pub fn index() -> FuncDef {
+    FuncDef::new(
+        index_impl,
+        [ParamType::array_of(ParamType::Any), ParamType::Any],
+    )
+}
+
+fn index_impl(args: FuncArgs) -> Result<Value, String> {
+    let list = args[0].as_array().unwrap();
+    let value = &args[1];
+
+    if list.is_empty() {
+        return Err(String::from("cannot search an empty list"));
+    }
+
+    list.iter()
+        .position(|elem| elem == value)
+        .map(Value::from)
+        .ok_or_else(|| format!("item `{value}` not found"))
+}
+
+/// Returns the [`FuncDef`] for the `contains(list, value)` function.
+///
+/// `contains` returns `true` if the list contains an element which is equal to the given value,
+/// and `false` otherwise.
+///
+/// As in Terraform, the value is converted to the element type of the list before comparing if
+/// the list only contains string, number and bool values, e.g. `contains(["1"], 1)` returns
+/// `true`. Mixed lists of these types are treated as lists of strings if at least one of their
+/// elements is a string. The function returns an error if the value cannot be converted to the
+/// element type, e.g. for `contains([1], "a")`.
+pub fn contains() -> FuncDef {
+    FuncDef::new(
+        contains_impl,
+        [ParamType::array_of(ParamType::Any), ParamType::Any],
+    )
+}
+
+fn contains_impl(args: FuncArgs) -> Result<Value, String> {
+    let list = args[0].as_array().unwrap();
+    let value = &args[1];
+
+    let Some(ty) = primitive_element_type(list).filter(|_| !value.is_null()) else {
+        return Ok(Value::Bool(list.contains(value)));
+    };
+
+    let value = convert_primitive(value, ty)
+        .ok_or_else(|| format!("cannot convert `{value}` to the element type of the list"))?;
+
+    Ok(Value::Bool(list.iter().any(|elem| {
+        convert_primitive(elem, ty).is_some_and(|elem| elem == value)
+    })))
+}
+
+#[derive(Clone, Copy)]
+enum PrimitiveType {
+    Bool,
+    Number,
+    String,
+}
+
+// Returns the type all elements of the list can be converted to, using the same rules as
+// `coalesce`, or `None` if the list contains other values than strings, numbers and bools.
+fn primitive_element_type(list: &[Value]) -> Option<PrimitiveType> {
+    let (first, rest) = list.split_first()?;
+
+    let ty = match first {
+        Value::Bool(_) => PrimitiveType::Bool,
+        Value::Number(_) => PrimitiveType::Number,
+        Value::String(_) => PrimitiveType::String,
+        _ => return None,
+    };
+
+    if rest.iter().all(|value| same_type(first, value)) {
+        Some(ty)
+    } else if list.iter().all(is_primitive) && list.iter().any(Value::is_string) {
+        Some(PrimitiveType::String)
+    } else {
+        None
+    }
+}
+
+fn convert_primitive(value: &Value, ty: PrimitiveType) -> Option<Value> {
+    match (ty, value) {
+        (PrimitiveType::Bool, Value::Bool(_))
+        | (PrimitiveType::Number, Value::Number(_))
+        | (PrimitiveType::String, Value::String(_)) => Some(value.clone()),
+        (PrimitiveType::Bool, Value::String(s)) => match s.as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        (PrimitiveType::Number, Value::String(s)) => s.parse().ok().map(Value::Number),
+        (PrimitiveType::String, Value::Number(num)) => Some(Value::String(num.to_string())),
+        (PrimitiveType::String, Value::Bool(b)) => Some(Value::String(b.to_string())),
+        (_, _) => None,
+    }
+}
+
+/// Returns the [`FuncDef`] for the `lookup(map, key, default)` function.
+///
+/// `lookup` retrieves the value of a single element from a map, given its key. If the map does
+/// not contain the key, the default value is returned instead, e.g.
+/// `lookup({ a = 1 }, "b", 0)` returns `0`.
+///
+/// The default value is optional. As in Terraform, the function returns an error if the map does
+/// not contain the key and no default value was provided, or if more than one default value was
+/// provided.
+pub fn lookup() -> FuncDef {
+    FuncDef::builder()
+        .param(ParamType::object_of(ParamType::Any))
+        .param(ParamType::String)
+        .variadic_param(ParamType::Any)
+        .build(lookup_impl)
+}
+
+fn lookup_impl(args: FuncArgs) -> Result<Value, String> {
+    let map = args[0].as_object().unwrap();
+    let key = args[1].as_str().unwrap();
+    let default = args.get(2);
+
+    if args.len() > 3 {
+        return Err(String::from(
+            "lookup() can only take a single default value",
+        ));
+    }
+
+    map.get(key)
+        .or(default)
+        .cloned()
+        .ok_or_else(|| format!("lookup failed to find key `{key}`"))
+}
+
+/// Returns the [`FuncDef`] for the `keys(map)` function.
+///
+/// `keys` returns a list of the keys of a map. As in Terraform, the keys are sorted
+/// lexicographically, regardless of the order in which they were declared, e.g.
+/// `keys({ b = 1, a = 2 })` returns `["a", "b"]`.
+pub fn keys() -> FuncDef {
+    FuncDef::new(keys_impl, [ParamType::object_of(ParamType::Any)])
+}
+
+fn keys_impl(args: FuncArgs) -> Result<Value, String> {
+    let map = args[0].as_object().unwrap();
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort_unstable();
+    Ok(keys.into_iter().cloned().collect())
+}
+
+/// Returns the [`FuncDef`] for the `values(map)` function.
+///
+/// `values` returns a list of the values of a map. As in Terraform, the values are ordered
+/// lexicographically by their keys, so that they line up with the result of [`keys`], e.g.
+/// `values({ b = 1, a = 2 })` returns `[2, 1]`.
+pub fn values() -> FuncDef {
+    FuncDef::new(values_impl, [ParamType::object_of(ParamType::Any)])
+}
+
+fn values_impl(args: FuncArgs) -> Result<Value, String> {
+    let map = args[0].as_object().unwrap();
+    let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    Ok(entries
+        .into_iter()
+        .map(|(_, value)| value.clone())
+        .collect())
+}
+
+/// Returns the [`FuncDef`] for the variadic `merge(maps...)` function.
+///
+/// `merge` takes an arbitrary number of maps and returns a single map which contains the merged
+/// set of elements from all arguments. The merge is shallow: if more than one map defines the
+/// same key, the value of the last one wins, e.g. `merge({ a = 1, b = 2 }, { b = 3 })` returns
+/// `{ a = 1, b = 3 }`.
+///
+/// Keys keep the position of their first occurrence. `null` arguments are ignored, and calling
+/// `merge` without arguments returns an empty map.
+pub fn merge() -> FuncDef {
+    FuncDef::builder()
+        .variadic_param(ParamType::nullable(ParamType::object_of(ParamType::Any)))
+        .build(merge_impl)
+}
+
+fn merge_impl(args: FuncArgs) -> Result<Value, String> {
+    let mut merged = Map::new();
+
+    for map in args.iter().filter_map(Value::as_object) {
+        merged.extend(map.iter().map(|(key, value)| (key.clone(), value.clone())));
+    }
+
+    Ok(Value::Object(merged))
+}
+
+/// Returns the [`FuncDef`] for the variadic `coalesce(values...)` function.
+///
+/// `coalesce` returns the first argument which is neither `null` nor an empty string. Other
+/// values, like empty arrays and objects, are never skipped, e.g. `coalesce(null, [], ["a"])`
+/// returns `[]`.
+///
+/// All non-null arguments must have the same type. As in Terraform, the only exception to this
+/// rule are mixed string, number and bool arguments if at least one of them is a string: in this
+/// case all arguments are converted to strings before checking for empty strings, e.g.
+/// `coalesce("", 1)` returns `"1"`.
+///
+/// The function returns an error if the arguments have incompatible types or if all of them are
+/// `null` or empty strings.
+pub fn coalesce() -> FuncDef {
+    FuncDef::builder()
+        .variadic_param(ParamType::Any)
+        .build(coalesce_impl)
+}
+
+fn coalesce_impl(args: FuncArgs) -> Result<Value, String> {
+    let values: Vec<&Value> = args.iter().filter(|value| !value.is_null()).collect();
+
+    let convert_to_string = match values.split_first() {
+        None => false,
+        Some((first, rest)) if rest.iter().all(|value| same_type(first, value)) => false,
+        Some(_)
+            if values.iter().all(|value| is_primitive(value))
+                && values.iter().any(|value| value.is_string()) =>
+        {
+            true
+        }
+        Some(_) => return Err(String::from("all arguments must have the same type")),
+    };
+
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::Number(num) if convert_to_string => Value::String(num.to_string()),
+            Value::Bool(b) if convert_to_string => Value::String(b.to_string()),
+            value => value.clone(),
+        })
+        .find(|value| value.as_str() != Some(""))
+        .ok_or_else(|| String::from("no non-null, non-empty-string arguments"))
+}
+
+fn same_type(lhs: &Value, rhs: &Value) -> bool {
+    std::mem::discriminant(lhs) == std::mem::discriminant(rhs)
+}
+
+fn is_primitive(value: &Value) -> bool {
+    matches!(value, Value::Bool(_) | Value::Number(_) | Value::String(_))
+}
+
+/// Returns the [`FuncDef`] for the variadic `coalescelist(lists...)` function.
+///
+/// `coalescelist` returns the first argument which is a non-empty list. `null` arguments are
+/// skipped.
+///
+/// The function returns an error if any of the arguments is neither a list nor `null`, or if all
+/// of them are `null` or empty lists.
+pub fn coalescelist() -> FuncDef {
+    FuncDef::builder()
+        .variadic_param(ParamType::nullable(ParamType::array_of(ParamType::Any)))
+        .build(coalescelist_impl)
+}
+
+fn coalescelist_impl(args: FuncArgs) -> Result<Value, String> {
+    args.iter()
+        .find(|value| value.as_array().is_some_and(|list| !list.is_empty()))
+        .cloned()
+        .ok_or_else(|| String::from("no non-null arguments"))
+}
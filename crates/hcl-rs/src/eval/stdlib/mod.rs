@@ -0,0 +1,124 @@
+//! Implementations of commonly used functions from the Terraform standard library.
+//!
+//! Each function in this module returns a [`FuncDef`][super::FuncDef] which can be declared in a
+//! [`Context`][super::Context] under any name. The functions follow the semantics of their
+//! Terraform counterparts as closely as possible, including the conditions under which they
+//! return errors.
+//!
+//! This module is only available if the `stdlib` Cargo feature is enabled. The `regex` and
+//! `regexall` functions additionally require the `regex` Cargo feature.
+//!
+//! To declare all functions of this module under their Terraform names at once, use
+//! [`register_stdlib`] or [`Context::with_stdlib`][super::Context::with_stdlib].
+//!
+//! # Example
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use hcl::eval::{stdlib, Context};
+//! use hcl::Value;
+//!
+//! let mut ctx = Context::new();
+//! ctx.declare_func("element", stdlib::element());
+//! ctx.declare_var("zones", hcl::value!(["a", "b", "c"]));
+//!
+//! assert_eq!(ctx.evaluate_str("element(zones, 4)")?, Value::from("b"));
+//! #     Ok(())
+//! # }
+//! ```
+
+mod collection;
+mod network;
+#[cfg(feature = "regex")]
+mod regexp;
+mod string;
+#[cfg(test)]
+mod tests;
+
+pub use self::collection::{
+    coalesce, coalescelist, contains, element, index, keys, length, lookup, merge, values,
+};
+pub use self::network::{cidrhost, cidrnetmask, cidrsubnet};
+#[cfg(feature = "regex")]
+pub use self::regexp::{regex, regexall};
+pub use self::string::{
+    join, lower, replace, split, title, trimprefix, trimspace, trimsuffix, upper,
+};
+
+use super::Context;
+use crate::Value;
+
+/// Declares all functions of this module in the given [`Context`] under their Terraform names.
+///
+/// The following functions are declared: `cidrhost`, `cidrnetmask`, `cidrsubnet`, `coalesce`,
+/// `coalescelist`, `contains`, `element`, `index`, `join`, `keys`, `length`, `lookup`, `lower`,
+/// `merge`, `replace`, `split`, `title`, `trimprefix`, `trimspace`, `trimsuffix`, `upper` and
+/// `values`.
+/// If the `regex` Cargo feature is enabled, `regex` and `regexall` are declared as well.
+///
+/// Functions which are already declared in the context under the same name are replaced.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use hcl::eval::{stdlib, Context};
+/// use hcl::Value;
+///
+/// let mut ctx = Context::new();
+/// stdlib::register_stdlib(&mut ctx);
+/// ctx.declare_var("names", hcl::value!(["a", "b", "c"]));
+///
+/// assert_eq!(ctx.evaluate_str("length(names)")?, Value::from(3));
+/// #     Ok(())
+/// # }
+/// ```
+pub fn register_stdlib(ctx: &mut Context) {
+    ctx.declare_func("cidrhost", cidrhost());
+    ctx.declare_func("cidrnetmask", cidrnetmask());
+    ctx.declare_func("cidrsubnet", cidrsubnet());
+    ctx.declare_func("coalesce", coalesce());
+    ctx.declare_func("coalescelist", coalescelist());
+    ctx.declare_func("contains", contains());
+    ctx.declare_func("element", element());
+    ctx.declare_func("index", index());
+    ctx.declare_func("join", join());
+    ctx.declare_func("keys", keys());
+    ctx.declare_func("length", length());
+    ctx.declare_func("lookup", lookup());
+    ctx.declare_func("lower", lower());
+    ctx.declare_func("merge", merge());
+    ctx.declare_func("replace", replace());
+    ctx.declare_func("split", split());
+    ctx.declare_func("title", title());
+    ctx.declare_func("trimprefix", trimprefix());
+    ctx.declare_func("trimspace", trimspace());
+    ctx.declare_func("trimsuffix", trimsuffix());
+    ctx.declare_func("upper", upper());
+    ctx.declare_func("values", values());
+
+    #[cfg(feature = "regex")]
+    {
+        ctx.declare_func("regex", regex());
+        ctx.declare_func("regexall", regexall());
+    }
+}
+
+// Converts a number into a collection index. The number must be a non-negative whole number.
+fn to_index(value: &Value, func: &str) -> Result<usize, String> {
+    let number = value
+        .as_number()
+        .ok_or_else(|| format!("expected a number, got `{value}`"))?;
+
+    if let Some(index) = number.as_u64() {
+        return usize::try_from(index).map_err(|_| format!("index `{number}` is out of range"));
+    }
+
+    if number.is_i64() {
+        Err(format!("cannot use {func} function with a negative index"))
+    } else {
+        Err(format!(
+            "invalid index `{number}`: value must be a whole number"
+        ))
+    }
+}
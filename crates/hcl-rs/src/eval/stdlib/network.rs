@@ -0,0 +1,182 @@
+use crate::eval::{FuncArgs, FuncDef, ParamType};
+use crate::Value;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Returns the [`FuncDef`] for the `cidrhost(prefix, hostnum)` function.
+///
+/// `cidrhost` calculates the full IP address of the host with the given number within an IP
+/// network address prefix in CIDR notation, e.g. `cidrhost("10.12.112.0/20", 16)` returns
+/// `"10.12.112.16"`. Both IPv4 and IPv6 prefixes are supported.
+///
+/// A negative host number counts backwards from the end of the range, e.g.
+/// `cidrhost("10.12.112.0/20", -2)` returns `"10.12.127.254"`. The function returns an error if
+/// the prefix is not a valid CIDR expression or if the host number is out of range for the
+/// prefix.
+pub fn cidrhost() -> FuncDef {
+    FuncDef::new(cidrhost_impl, [ParamType::String, ParamType::Number])
+}
+
+fn cidrhost_impl(args: FuncArgs) -> Result<Value, String> {
+    let cidr = Cidr::parse(args[0].as_str().unwrap())?;
+    let hostnum = to_integer(&args[1])?;
+    let host_bits = cidr.max_len() - cidr.len;
+
+    // Negative host numbers count backwards from the last host, which is numbered `-1`.
+    let offset = hostnum.unsigned_abs() - u128::from(hostnum < 0);
+
+    if offset > ones(host_bits) {
+        return Err(format!(
+            "prefix of {} bits cannot accommodate a host numbered {hostnum}",
+            cidr.len
+        ));
+    }
+
+    let host = if hostnum < 0 {
+        ones(host_bits) - offset
+    } else {
+        offset
+    };
+
+    Ok(Value::from(cidr.to_ip_addr(cidr.addr | host).to_string()))
+}
+
+/// Returns the [`FuncDef`] for the `cidrnetmask(prefix)` function.
+///
+/// `cidrnetmask` converts an IPv4 address prefix in CIDR notation into a subnet mask address,
+/// e.g. `cidrnetmask("172.16.0.0/12")` returns `"255.240.0.0"`.
+///
+/// The function returns an error if the prefix is not a valid CIDR expression or if it is an IPv6
+/// prefix, because IPv6 does not use subnet masks.
+pub fn cidrnetmask() -> FuncDef {
+    FuncDef::new(cidrnetmask_impl, [ParamType::String])
+}
+
+fn cidrnetmask_impl(args: FuncArgs) -> Result<Value, String> {
+    let cidr = Cidr::parse(args[0].as_str().unwrap())?;
+
+    if !cidr.is_ipv4 {
+        return Err(String::from("IPv6 addresses cannot have a netmask"));
+    }
+
+    let mask = ones(32) & !ones(32 - cidr.len);
+    Ok(Value::from(cidr.to_ip_addr(mask).to_string()))
+}
+
+/// Returns the [`FuncDef`] for the `cidrsubnet(prefix, newbits, netnum)` function.
+///
+/// `cidrsubnet` calculates a subnet address within the given IP network address prefix in CIDR
+/// notation. `newbits` is the number of additional bits with which to extend the prefix and
+/// `netnum` is the number of the subnet, which must fit into `newbits` binary digits, e.g.
+/// `cidrsubnet("172.16.0.0/12", 4, 2)` returns `"172.18.0.0/16"` and
+/// `cidrsubnet("fd00:fd12:3456:7890::/56", 16, 162)` returns `"fd00:fd12:3456:7800:a200::/72"`.
+///
+/// The function returns an error if the prefix is not a valid CIDR expression, if the extended
+/// prefix would be longer than the IP address, or if the subnet number is out of range.
+pub fn cidrsubnet() -> FuncDef {
+    FuncDef::new(
+        cidrsubnet_impl,
+        [ParamType::String, ParamType::Number, ParamType::Number],
+    )
+}
+
+fn cidrsubnet_impl(args: FuncArgs) -> Result<Value, String> {
+    let cidr = Cidr::parse(args[0].as_str().unwrap())?;
+    let newbits = to_integer(&args[1])?;
+    let netnum = to_integer(&args[2])?;
+
+    let new_len = u32::try_from(newbits)
+        .ok()
+        .and_then(|newbits| newbits.checked_add(cidr.len))
+        .filter(|new_len| *new_len <= cidr.max_len())
+        .ok_or_else(|| {
+            format!(
+                "insufficient address space to extend prefix of {} by {newbits}",
+                cidr.len
+            )
+        })?;
+
+    let netnum = u128::try_from(netnum)
+        .ok()
+        .filter(|netnum| *netnum <= ones(new_len - cidr.len))
+        .ok_or_else(|| {
+            format!("prefix extension of {newbits} does not accommodate a subnet numbered {netnum}")
+        })?;
+
+    let subnet = Cidr {
+        addr: cidr.addr | netnum.checked_shl(cidr.max_len() - new_len).unwrap_or(0),
+        len: new_len,
+        is_ipv4: cidr.is_ipv4,
+    };
+
+    Ok(Value::from(subnet.to_string()))
+}
+
+// An IP network address prefix. The address is masked to the prefix length.
+struct Cidr {
+    addr: u128,
+    len: u32,
+    is_ipv4: bool,
+}
+
+impl Cidr {
+    fn parse(s: &str) -> Result<Cidr, String> {
+        let invalid = || format!("invalid CIDR expression `{s}`");
+        let (addr, len) = s.split_once('/').ok_or_else(invalid)?;
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let len: u32 = len.parse().map_err(|_| invalid())?;
+
+        let (addr, is_ipv4) = match addr {
+            IpAddr::V4(addr) => (u128::from(u32::from(addr)), true),
+            IpAddr::V6(addr) => (u128::from(addr), false),
+        };
+
+        let mut cidr = Cidr { addr, len, is_ipv4 };
+
+        if len > cidr.max_len() {
+            return Err(invalid());
+        }
+
+        cidr.addr &= !ones(cidr.max_len() - len);
+        Ok(cidr)
+    }
+
+    fn max_len(&self) -> u32 {
+        if self.is_ipv4 {
+            32
+        } else {
+            128
+        }
+    }
+
+    fn to_ip_addr(&self, addr: u128) -> IpAddr {
+        if self.is_ipv4 {
+            // Addresses derived from an IPv4 prefix never exceed 32 bits.
+            IpAddr::V4(Ipv4Addr::from(addr as u32))
+        } else {
+            IpAddr::V6(Ipv6Addr::from(addr))
+        }
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.to_ip_addr(self.addr), self.len)
+    }
+}
+
+// Returns a value with the lowest `bits` bits set.
+fn ones(bits: u32) -> u128 {
+    u128::MAX.checked_shr(128 - bits).unwrap_or(0)
+}
+
+// Converts a number into an integer. The number must be a whole number.
+fn to_integer(value: &Value) -> Result<i128, String> {
+    let number = value.as_number().unwrap();
+
+    number
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| number.as_u64().map(i128::from))
+        .ok_or_else(|| format!("invalid number `{number}`: value must be a whole number"))
+}
@@ -0,0 +1,143 @@
+use crate::eval::{FuncArgs, FuncDef, ParamType};
+use crate::{Map, Value};
+use ::regex::{Captures, Regex};
+
+/// Returns the [`FuncDef`] for the `regex(pattern, string)` function.
+///
+/// `regex` applies a regular expression to a string and returns the first match. The shape of
+/// the result depends on the capture groups in the pattern:
+///
+/// - Without capture groups, the matched substring is returned as string.
+/// - With unnamed capture groups, a list of the captured substrings is returned.
+/// - With named capture groups, an object mapping the group names to the captured substrings is
+///   returned.
+///
+/// Capture groups which do not participate in the match, e.g. optional groups, are `null` in the
+/// result.
+///
+/// The function returns an error if the pattern is invalid, if it mixes named and unnamed
+/// capture groups, or if it does not match the string. Use [`regexall`] to test whether a
+/// string matches without producing an error.
+///
+/// The pattern syntax is the one of the [`regex`](https://docs.rs/regex) crate, which is very
+/// similar to the RE2 syntax used by Terraform.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use hcl::eval::{stdlib, Context};
+///
+/// let mut ctx = Context::new();
+/// ctx.declare_func("regex", stdlib::regex());
+///
+/// assert_eq!(
+///     ctx.evaluate_str(r#"regex("(?P<major>\\d+)\\.(?P<minor>\\d+)", "v1.42")"#)?,
+///     hcl::value!({ major = "1", minor = "42" }),
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+pub fn regex() -> FuncDef {
+    FuncDef::new(regex_impl, [ParamType::String, ParamType::String])
+}
+
+fn regex_impl(args: FuncArgs) -> Result<Value, String> {
+    let (re, shape) = compile(args[0].as_str().unwrap())?;
+    let haystack = args[1].as_str().unwrap();
+
+    re.captures(haystack)
+        .map(|captures| shape.value(&re, &captures))
+        .ok_or_else(|| String::from("pattern did not match any part of the given string"))
+}
+
+/// Returns the [`FuncDef`] for the `regexall(pattern, string)` function.
+///
+/// `regexall` applies a regular expression to a string and returns a list of all
+/// non-overlapping matches. Each element of the list has the same shape as the result of the
+/// [`regex`] function. If the pattern does not match, an empty list is returned.
+///
+/// The function returns an error if the pattern is invalid or if it mixes named and unnamed
+/// capture groups.
+pub fn regexall() -> FuncDef {
+    FuncDef::new(regexall_impl, [ParamType::String, ParamType::String])
+}
+
+fn regexall_impl(args: FuncArgs) -> Result<Value, String> {
+    let (re, shape) = compile(args[0].as_str().unwrap())?;
+    let haystack = args[1].as_str().unwrap();
+
+    Ok(re
+        .captures_iter(haystack)
+        .map(|captures| shape.value(&re, &captures))
+        .collect())
+}
+
+// The shape of the value produced for a single match.
+enum Shape {
+    Match,
+    List,
+    Object,
+}
+
+impl Shape {
+    fn value(&self, re: &Regex, captures: &Captures) -> Value {
+        let capture = |i| {
+            captures
+                .get(i)
+                .map_or(Value::Null, |m| Value::from(m.as_str()))
+        };
+
+        match self {
+            Shape::Match => capture(0),
+            Shape::List => (1..captures.len()).map(capture).collect(),
+            Shape::Object => re
+                .capture_names()
+                .enumerate()
+                .skip(1)
+                .filter_map(|(i, name)| Some((name?.to_owned(), capture(i))))
+                .collect::<Map<_, _>>()
+                .into(),
+        }
+    }
+}
+
+fn compile(pattern: &str) -> Result<(Regex, Shape), String> {
+    let re =
+        Regex::new(pattern).map_err(|err| format!("invalid regular expression pattern: {err}"))?;
+
+    let mut names = re.capture_names().skip(1);
+
+    let shape = match names.next() {
+        None => Shape::Match,
+        Some(first) => {
+            let first_is_named = first.is_some();
+
+            if names.any(|name| name.is_some() != first_is_named) {
+                return Err(String::from(
+                    "invalid regular expression pattern: can't mix both named and unnamed capture groups",
+                ));
+            }
+
+            if first_is_named {
+                Shape::Object
+            } else {
+                Shape::List
+            }
+        }
+    };
+
+    Ok((re, shape))
+}
+
+// Replaces all matches of the regular expression pattern in `string`. The replacement may refer
+// to capture groups via `$1` or `${name}`.
+pub(super) fn replace_all(
+    pattern: &str,
+    string: &str,
+    replacement: &str,
+) -> Result<String, String> {
+    let re =
+        Regex::new(pattern).map_err(|err| format!("invalid regular expression pattern: {err}"))?;
+    Ok(re.replace_all(string, replacement).into_owned())
+}
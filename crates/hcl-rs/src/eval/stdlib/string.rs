@@ -0,0 +1,183 @@
+use crate::eval::{FuncArgs, FuncDef, ParamType};
+use crate::Value;
+
+/// Returns the [`FuncDef`] for the `lower(string)` function.
+///
+/// `lower` converts all cased letters in the given string to lowercase, e.g. `lower("HELLO")`
+/// returns `"hello"`.
+pub fn lower() -> FuncDef {
+    FuncDef::new(lower_impl, [ParamType::String])
+}
+
+fn lower_impl(args: FuncArgs) -> Result<Value, String> {
+    Ok(Value::from(args[0].as_str().unwrap().to_lowercase()))
+}
+
+/// Returns the [`FuncDef`] for the `upper(string)` function.
+///
+/// `upper` converts all cased letters in the given string to uppercase, e.g. `upper("hello")`
+/// returns `"HELLO"`.
+pub fn upper() -> FuncDef {
+    FuncDef::new(upper_impl, [ParamType::String])
+}
+
+fn upper_impl(args: FuncArgs) -> Result<Value, String> {
+    Ok(Value::from(args[0].as_str().unwrap().to_uppercase()))
+}
+
+/// Returns the [`FuncDef`] for the variadic `join(separator, lists...)` function.
+///
+/// `join` produces a string by concatenating all elements of the given lists of strings with the
+/// given separator, e.g. `join(", ", ["a", "b"], ["c"])` returns `"a, b, c"`.
+///
+/// The function returns an error if no list is provided.
+pub fn join() -> FuncDef {
+    FuncDef::builder()
+        .param(ParamType::String)
+        .variadic_param(ParamType::array_of(ParamType::String))
+        .build(join_impl)
+}
+
+fn join_impl(args: FuncArgs) -> Result<Value, String> {
+    let separator = args[0].as_str().unwrap();
+
+    if args.len() < 2 {
+        return Err(String::from("at least one list is required"));
+    }
+
+    let elements: Vec<&str> = args
+        .variadic_args()
+        .flat_map(|list| list.as_array().unwrap())
+        .map(|elem| elem.as_str().unwrap())
+        .collect();
+
+    Ok(Value::from(elements.join(separator)))
+}
+
+/// Returns the [`FuncDef`] for the `split(separator, string)` function.
+///
+/// `split` produces a list by dividing the given string at all occurrences of the given
+/// separator, e.g. `split(",", "a,b,c")` returns `["a", "b", "c"]`.
+///
+/// Splitting an empty string yields a list containing a single empty string. If the separator is
+/// empty, the string is split into its individual characters.
+pub fn split() -> FuncDef {
+    FuncDef::new(split_impl, [ParamType::String, ParamType::String])
+}
+
+fn split_impl(args: FuncArgs) -> Result<Value, String> {
+    let separator = args[0].as_str().unwrap();
+    let string = args[1].as_str().unwrap();
+
+    if separator.is_empty() {
+        return Ok(string.chars().map(String::from).collect());
+    }
+
+    Ok(string.split(separator).collect())
+}
+
+/// Returns the [`FuncDef`] for the `title(string)` function.
+///
+/// `title` converts the first letter of each word in the given string to uppercase, e.g.
+/// `title("hello world")` returns `"Hello World"`. Letters, digits and underscores are part of a
+/// word, every other character separates words.
+pub fn title() -> FuncDef {
+    FuncDef::new(title_impl, [ParamType::String])
+}
+
+fn title_impl(args: FuncArgs) -> Result<Value, String> {
+    let string = args[0].as_str().unwrap();
+    let mut title = String::with_capacity(string.len());
+    let mut at_word_start = true;
+
+    for ch in string.chars() {
+        if at_word_start {
+            title.extend(ch.to_uppercase());
+        } else {
+            title.push(ch);
+        }
+
+        at_word_start = !(ch.is_alphanumeric() || ch == '_');
+    }
+
+    Ok(Value::from(title))
+}
+
+/// Returns the [`FuncDef`] for the `trimspace(string)` function.
+///
+/// `trimspace` removes all leading and trailing whitespace from the given string, e.g.
+/// `trimspace("  hello\n")` returns `"hello"`.
+pub fn trimspace() -> FuncDef {
+    FuncDef::new(trimspace_impl, [ParamType::String])
+}
+
+fn trimspace_impl(args: FuncArgs) -> Result<Value, String> {
+    Ok(Value::from(args[0].as_str().unwrap().trim()))
+}
+
+/// Returns the [`FuncDef`] for the `trimprefix(string, prefix)` function.
+///
+/// `trimprefix` removes the given prefix from the start of the string once, e.g.
+/// `trimprefix("helloworld", "hello")` returns `"world"`. If the string does not start with the
+/// prefix, it is returned unchanged.
+pub fn trimprefix() -> FuncDef {
+    FuncDef::new(trimprefix_impl, [ParamType::String, ParamType::String])
+}
+
+fn trimprefix_impl(args: FuncArgs) -> Result<Value, String> {
+    let string = args[0].as_str().unwrap();
+    let prefix = args[1].as_str().unwrap();
+    Ok(Value::from(string.strip_prefix(prefix).unwrap_or(string)))
+}
+
+/// Returns the [`FuncDef`] for the `trimsuffix(string, suffix)` function.
+///
+/// `trimsuffix` removes the given suffix from the end of the string once, e.g.
+/// `trimsuffix("helloworld", "world")` returns `"hello"`. If the string does not end with the
+/// suffix, it is returned unchanged.
+pub fn trimsuffix() -> FuncDef {
+    FuncDef::new(trimsuffix_impl, [ParamType::String, ParamType::String])
+}
+
+fn trimsuffix_impl(args: FuncArgs) -> Result<Value, String> {
+    let string = args[0].as_str().unwrap();
+    let suffix = args[1].as_str().unwrap();
+    Ok(Value::from(string.strip_suffix(suffix).unwrap_or(string)))
+}
+
+/// Returns the [`FuncDef`] for the `replace(string, substring, replacement)` function.
+///
+/// `replace` searches the given string for all occurrences of the substring and replaces them
+/// with the replacement string, e.g. `replace("1 + 2 + 3", "+", "-")` returns `"1 - 2 - 3"`.
+///
+/// As in Terraform, a substring which is wrapped in forward slashes is treated as a regular
+/// expression, e.g. `replace("hello world", "/w.*d/", "there")` returns `"hello there"`. In this
+/// case the replacement can refer to capture groups via `$1` or `${name}`. Regular expression
+/// replacement requires the `regex` Cargo feature, without it the function returns an error for
+/// such substrings.
+pub fn replace() -> FuncDef {
+    FuncDef::new(
+        replace_impl,
+        [ParamType::String, ParamType::String, ParamType::String],
+    )
+}
+
+fn replace_impl(args: FuncArgs) -> Result<Value, String> {
+    let string = args[0].as_str().unwrap();
+    let substring = args[1].as_str().unwrap();
+    let replacement = args[2].as_str().unwrap();
+
+    let pattern = substring
+        .strip_prefix('/')
+        .and_then(|s| s.strip_suffix('/'));
+
+    match pattern {
+        #[cfg(feature = "regex")]
+        Some(pattern) => super::regexp::replace_all(pattern, string, replacement).map(Value::from),
+        #[cfg(not(feature = "regex"))]
+        Some(_) => Err(String::from(
+            "regular expression replacement requires the `regex` feature",
+        )),
+        None => Ok(Value::from(string.replace(substring, replacement))),
+    }
+}
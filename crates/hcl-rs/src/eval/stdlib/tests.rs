@@ -0,0 +1,761 @@
+use super::*;
+use crate::eval::{Context, ErrorKind, FuncDef};
+use crate::expr::FuncName;
+use pretty_assertions::assert_eq;
+
+fn context<I>(funcs: I) -> Context<'static>
+where
+    I: IntoIterator<Item = (&'static str, FuncDef)>,
+{
+    let mut ctx = Context::new();
+
+    for (name, func) in funcs {
+        ctx.declare_func(name, func);
+    }
+
+    ctx
+}
+
+#[track_caller]
+fn assert_func_error(ctx: &Context, expr: &str, func: &str, msg: &str) {
+    let err = ctx.evaluate_str(expr).unwrap_err();
+
+    match err {
+        crate::Error::Eval(err) => assert_eq!(
+            err.kind(),
+            &ErrorKind::FuncCall(FuncName::new(func), msg.to_owned())
+        ),
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn length_func() {
+    let ctx = context([("length", length())]);
+
+    assert_eq!(
+        ctx.evaluate_str("length([1, 2, 3])").unwrap(),
+        Value::from(3)
+    );
+    assert_eq!(
+        ctx.evaluate_str("length({ a = 1, b = 2 })").unwrap(),
+        Value::from(2)
+    );
+    assert_eq!(ctx.evaluate_str("length([])").unwrap(), Value::from(0));
+    assert_eq!(
+        ctx.evaluate_str(r#"length("héllo")"#).unwrap(),
+        Value::from(5)
+    );
+
+    assert!(ctx.evaluate_str("length(1)").is_err());
+    assert!(ctx.evaluate_str("length(null)").is_err());
+}
+
+#[test]
+fn element_func() {
+    let ctx = context([("element", element())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"element(["a", "b", "c"], 0)"#).unwrap(),
+        Value::from("a")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"element(["a", "b", "c"], 2)"#).unwrap(),
+        Value::from("c")
+    );
+    // The index wraps around.
+    assert_eq!(
+        ctx.evaluate_str(r#"element(["a", "b", "c"], 3)"#).unwrap(),
+        Value::from("a")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"element(["a", "b", "c"], 7)"#).unwrap(),
+        Value::from("b")
+    );
+    assert_eq!(
+        ctx.evaluate_str("element([{ a = 1 }], 1.0)").unwrap(),
+        crate::value!({ a = 1 })
+    );
+
+    assert_func_error(
+        &ctx,
+        "element([], 0)",
+        "element",
+        "cannot use element function with an empty list",
+    );
+    assert_func_error(
+        &ctx,
+        "element([1], -1)",
+        "element",
+        "cannot use element function with a negative index",
+    );
+    assert_func_error(
+        &ctx,
+        "element([1], 0.5)",
+        "element",
+        "invalid index `0.5`: value must be a whole number",
+    );
+}
+
+#[test]
+fn index_func() {
+    let ctx = context([("index", index())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"index(["a", "b", "c"], "b")"#).unwrap(),
+        Value::from(1)
+    );
+    // The first matching element wins.
+    assert_eq!(
+        ctx.evaluate_str(r#"index(["a", "b", "a"], "a")"#).unwrap(),
+        Value::from(0)
+    );
+    assert_eq!(
+        ctx.evaluate_str("index([[1], { a = 2 }], { a = 2 })")
+            .unwrap(),
+        Value::from(1)
+    );
+
+    assert_func_error(
+        &ctx,
+        r#"index(["a", "b"], "c")"#,
+        "index",
+        "item `\"c\"` not found",
+    );
+    // Values of different types are never equal.
+    assert_func_error(&ctx, r#"index(["1"], 1)"#, "index", "item `1` not found");
+    assert_func_error(&ctx, "index([], 1)", "index", "cannot search an empty list");
+}
+
+#[test]
+fn contains_func() {
+    let ctx = context([("contains", contains())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"contains(["a", "b"], "b")"#).unwrap(),
+        Value::from(true)
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"contains(["a", "b"], "c")"#).unwrap(),
+        Value::from(false)
+    );
+    assert_eq!(
+        ctx.evaluate_str("contains([[1], { a = 2 }], { a = 2 })")
+            .unwrap(),
+        Value::from(true)
+    );
+    // The value is converted to the element type of the list.
+    assert_eq!(
+        ctx.evaluate_str(r#"contains(["1"], 1)"#).unwrap(),
+        Value::from(true)
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"contains([1.5, 2], "1.50")"#).unwrap(),
+        Value::from(true)
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"contains([true], "true")"#).unwrap(),
+        Value::from(true)
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"contains(["a", 1, false], "false")"#)
+            .unwrap(),
+        Value::from(true)
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"contains(["a"], null)"#).unwrap(),
+        Value::from(false)
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"contains([["1"]], [1])"#).unwrap(),
+        Value::from(false)
+    );
+    assert!(ctx.evaluate_str(r#"contains([1], "a")"#).is_err());
+    assert_eq!(
+        ctx.evaluate_str("contains([], null)").unwrap(),
+        Value::from(false)
+    );
+
+    assert!(ctx.evaluate_str(r#"contains("a", "a")"#).is_err());
+}
+
+#[test]
+fn lookup_func() {
+    let ctx = context([("lookup", lookup())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"lookup({ a = 1, b = 2 }, "b")"#)
+            .unwrap(),
+        Value::from(2)
+    );
+    // The default is ignored if the key is present.
+    assert_eq!(
+        ctx.evaluate_str(r#"lookup({ a = "x" }, "a", "y")"#)
+            .unwrap(),
+        Value::from("x")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"lookup({ a = 1 }, "b", 0)"#).unwrap(),
+        Value::from(0)
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"lookup({}, "b", null)"#).unwrap(),
+        Value::Null
+    );
+
+    assert_func_error(
+        &ctx,
+        r#"lookup({ a = 1 }, "b")"#,
+        "lookup",
+        "lookup failed to find key `b`",
+    );
+    assert_func_error(
+        &ctx,
+        r#"lookup({ a = 1 }, "b", 0, 1)"#,
+        "lookup",
+        "lookup() can only take a single default value",
+    );
+    assert!(ctx.evaluate_str(r#"lookup(["a"], "a")"#).is_err());
+}
+
+#[test]
+fn keys_and_values_funcs() {
+    let ctx = context([("keys", keys()), ("values", values())]);
+
+    // Keys are sorted lexicographically and values follow the order of their keys.
+    assert_eq!(
+        ctx.evaluate_str(r#"keys({ b = 1, c = 2, "B" = 3, a = 4 })"#)
+            .unwrap(),
+        crate::value!(["B", "a", "b", "c"])
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"values({ b = 1, c = 2, "B" = 3, a = 4 })"#)
+            .unwrap(),
+        crate::value!([3, 4, 1, 2])
+    );
+    assert_eq!(ctx.evaluate_str("keys({})").unwrap(), crate::value!([]));
+    assert_eq!(ctx.evaluate_str("values({})").unwrap(), crate::value!([]));
+
+    assert!(ctx.evaluate_str(r#"keys(["a"])"#).is_err());
+    assert!(ctx.evaluate_str(r#"values("a")"#).is_err());
+}
+
+#[test]
+fn merge_func() {
+    let ctx = context([("merge", merge())]);
+
+    // The last value wins for overlapping keys.
+    assert_eq!(
+        ctx.evaluate_str(
+            r#"merge({ a = 1, b = { x = 1 } }, { b = { y = 2 }, c = 3 }, { a = "z" })"#
+        )
+        .unwrap(),
+        crate::value!({ a = "z", b = { y = 2 }, c = 3 })
+    );
+    assert_eq!(
+        ctx.evaluate_str("merge(null, { a = 1 }, null)").unwrap(),
+        crate::value!({ a = 1 })
+    );
+    assert_eq!(ctx.evaluate_str("merge()").unwrap(), crate::value!({}));
+
+    assert!(ctx.evaluate_str(r#"merge({ a = 1 }, ["b"])"#).is_err());
+}
+
+#[test]
+fn coalesce_func() {
+    let ctx = context([("coalesce", coalesce())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"coalesce("a", "b")"#).unwrap(),
+        Value::from("a")
+    );
+    // Nulls and empty strings are skipped.
+    assert_eq!(
+        ctx.evaluate_str(r#"coalesce(null, "", "b")"#).unwrap(),
+        Value::from("b")
+    );
+    assert_eq!(
+        ctx.evaluate_str("coalesce(null, 1, 2)").unwrap(),
+        Value::from(1)
+    );
+    // Empty collections are not skipped.
+    assert_eq!(
+        ctx.evaluate_str(r#"coalesce(null, [], ["a"])"#).unwrap(),
+        crate::value!([])
+    );
+    // Mixed primitive types are converted to strings if one of them is a string.
+    assert_eq!(
+        ctx.evaluate_str(r#"coalesce("", 1, "b")"#).unwrap(),
+        Value::from("1")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"coalesce(true, "b")"#).unwrap(),
+        Value::from("true")
+    );
+
+    assert_func_error(
+        &ctx,
+        "coalesce(1, true)",
+        "coalesce",
+        "all arguments must have the same type",
+    );
+    assert_func_error(
+        &ctx,
+        r#"coalesce("a", ["b"])"#,
+        "coalesce",
+        "all arguments must have the same type",
+    );
+    assert_func_error(
+        &ctx,
+        r#"coalesce(null, "", null)"#,
+        "coalesce",
+        "no non-null, non-empty-string arguments",
+    );
+    assert_func_error(
+        &ctx,
+        "coalesce()",
+        "coalesce",
+        "no non-null, non-empty-string arguments",
+    );
+}
+
+#[test]
+fn coalescelist_func() {
+    let ctx = context([("coalescelist", coalescelist())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"coalescelist(["a", "b"], ["c"])"#)
+            .unwrap(),
+        crate::value!(["a", "b"])
+    );
+    // Nulls and empty lists are skipped.
+    assert_eq!(
+        ctx.evaluate_str(r#"coalescelist(null, [], [null], ["c"])"#)
+            .unwrap(),
+        crate::value!([null])
+    );
+
+    assert_func_error(
+        &ctx,
+        "coalescelist(null, [], [])",
+        "coalescelist",
+        "no non-null arguments",
+    );
+    assert!(ctx.evaluate_str(r#"coalescelist("a")"#).is_err());
+}
+
+#[test]
+fn lower_and_upper_funcs() {
+    let ctx = context([("lower", lower()), ("upper", upper())]);
+
+    assert_eq!(ctx.evaluate_str(r#"upper("x")"#).unwrap(), Value::from("X"));
+    assert_eq!(
+        ctx.evaluate_str(r#"lower("HeLLo, Wörld")"#).unwrap(),
+        Value::from("hello, wörld")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"upper("straße")"#).unwrap(),
+        Value::from("STRASSE")
+    );
+
+    assert!(ctx.evaluate_str("upper(1)").is_err());
+}
+
+#[test]
+fn join_func() {
+    let ctx = context([("join", join())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"join(",", ["a", "b"])"#).unwrap(),
+        Value::from("a,b")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"join(", ", ["a"], [], ["b", "c"])"#)
+            .unwrap(),
+        Value::from("a, b, c")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"join(",", [])"#).unwrap(),
+        Value::from("")
+    );
+
+    assert_func_error(
+        &ctx,
+        r#"join(",")"#,
+        "join",
+        "at least one list is required",
+    );
+    assert!(ctx.evaluate_str(r#"join(",", [1, 2])"#).is_err());
+}
+
+#[test]
+fn split_func() {
+    let ctx = context([("split", split())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"split(",", "a,b,,c")"#).unwrap(),
+        crate::value!(["a", "b", "", "c"])
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"split(",", "")"#).unwrap(),
+        crate::value!([""])
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"split("", "abc")"#).unwrap(),
+        crate::value!(["a", "b", "c"])
+    );
+
+    assert!(ctx.evaluate_str(r#"split(",", ["a"])"#).is_err());
+}
+
+#[test]
+fn title_func() {
+    let ctx = context([("title", title())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"title("hello world")"#).unwrap(),
+        Value::from("Hello World")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"title("foo-bar_baz 1st élan")"#)
+            .unwrap(),
+        Value::from("Foo-Bar_baz 1st Élan")
+    );
+    assert_eq!(ctx.evaluate_str(r#"title("")"#).unwrap(), Value::from(""));
+}
+
+#[test]
+fn trim_funcs() {
+    let ctx = context([
+        ("trimspace", trimspace()),
+        ("trimprefix", trimprefix()),
+        ("trimsuffix", trimsuffix()),
+    ]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"trimspace(" \t hello\n ")"#).unwrap(),
+        Value::from("hello")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"trimspace("  ")"#).unwrap(),
+        Value::from("")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"trimspace("")"#).unwrap(),
+        Value::from("")
+    );
+
+    // Prefixes and suffixes are only removed once.
+    assert_eq!(
+        ctx.evaluate_str(r#"trimprefix("hellohello", "hello")"#)
+            .unwrap(),
+        Value::from("hello")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"trimsuffix("worldworld", "world")"#)
+            .unwrap(),
+        Value::from("world")
+    );
+
+    // Strings without a match are returned unchanged.
+    assert_eq!(
+        ctx.evaluate_str(r#"trimprefix("hello", "world")"#).unwrap(),
+        Value::from("hello")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"trimsuffix("hello", "")"#).unwrap(),
+        Value::from("hello")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"trimprefix("", "hello")"#).unwrap(),
+        Value::from("")
+    );
+}
+
+#[test]
+fn replace_func() {
+    let ctx = context([("replace", replace())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"replace("1 + 2 + 3", "+", "-")"#)
+            .unwrap(),
+        Value::from("1 - 2 - 3")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"replace("hello", "world", "there")"#)
+            .unwrap(),
+        Value::from("hello")
+    );
+    // A single slash is not a regular expression.
+    assert_eq!(
+        ctx.evaluate_str(r#"replace("a/b", "/", "-")"#).unwrap(),
+        Value::from("a-b")
+    );
+
+    #[cfg(not(feature = "regex"))]
+    assert_func_error(
+        &ctx,
+        r#"replace("hello world", "/w.*d/", "there")"#,
+        "replace",
+        "regular expression replacement requires the `regex` feature",
+    );
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn replace_func_regex() {
+    let ctx = context([("replace", replace())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"replace("hello world", "/w.*d/", "there")"#)
+            .unwrap(),
+        Value::from("hello there")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"replace("2019-02-01", "/(\\d+)-(\\d+)-(\\d+)/", "$3.$2.$1")"#)
+            .unwrap(),
+        Value::from("01.02.2019")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"replace("hello", "/x+/", "y")"#)
+            .unwrap(),
+        Value::from("hello")
+    );
+    assert!(ctx.evaluate_str(r#"replace("hello", "/(/", "y")"#).is_err());
+}
+
+#[test]
+fn register_stdlib_funcs() {
+    let ctx = Context::with_stdlib();
+
+    assert_eq!(ctx.evaluate_str("length([1,2,3])").unwrap(), Value::from(3));
+    assert_eq!(
+        ctx.evaluate_str(r#"join(",", ["a","b"])"#).unwrap(),
+        Value::from("a,b")
+    );
+    assert_eq!(ctx.evaluate_str(r#"upper("x")"#).unwrap(), Value::from("X"));
+    assert_eq!(
+        ctx.evaluate_str(r#"join("-", values(merge({ b = "x" }, { a = lower("Y") })))"#)
+            .unwrap(),
+        Value::from("y-x")
+    );
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn regex_funcs() {
+    let ctx = context([("regex", regex()), ("regexall", regexall())]);
+
+    // Without capture groups the match itself is returned.
+    assert_eq!(
+        ctx.evaluate_str(r#"regex("[a-z]+", "53453453.345345aaabbbccc23454")"#)
+            .unwrap(),
+        Value::from("aaabbbccc")
+    );
+
+    // Numbered groups produce a list.
+    assert_eq!(
+        ctx.evaluate_str(r#"regex("(\\d\\d\\d\\d)-(\\d\\d)-(\\d\\d)", "2019-02-01")"#)
+            .unwrap(),
+        crate::value!(["2019", "02", "01"])
+    );
+
+    // Named groups produce an object.
+    assert_eq!(
+        ctx.evaluate_str(
+            r#"regex("^(?:(?P<scheme>[^:/?#]+):)?(?://(?P<authority>[^/?#]*))?", "https://terraform.io/docs/")"#
+        )
+        .unwrap(),
+        crate::value!({ scheme = "https", authority = "terraform.io" })
+    );
+
+    // Groups which do not participate in the match are null.
+    assert_eq!(
+        ctx.evaluate_str(r#"regex("(a)|(b)", "b")"#).unwrap(),
+        crate::value!([null, "b"])
+    );
+
+    assert_eq!(
+        ctx.evaluate_str(r#"regexall("[a-z]+", "1234abcd5678efgh9")"#)
+            .unwrap(),
+        crate::value!(["abcd", "efgh"])
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"regexall("(?P<key>\\w+)=(?P<value>\\w+)", "a=1 b=2")"#)
+            .unwrap(),
+        crate::value!([{ key = "a", value = "1" }, { key = "b", value = "2" }])
+    );
+
+    // No match.
+    assert_func_error(
+        &ctx,
+        r#"regex("[a-z]+", "1234")"#,
+        "regex",
+        "pattern did not match any part of the given string",
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"regexall("[a-z]+", "1234")"#).unwrap(),
+        crate::value!([])
+    );
+
+    assert_func_error(
+        &ctx,
+        r#"regexall("(?P<a>x)(y)", "xy")"#,
+        "regexall",
+        "invalid regular expression pattern: can't mix both named and unnamed capture groups",
+    );
+    assert!(ctx.evaluate_str(r#"regex("(", "")"#).is_err());
+}
+
+#[test]
+fn cidrhost_func() {
+    let ctx = context([("cidrhost", cidrhost())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrhost("10.12.112.0/20", 16)"#)
+            .unwrap(),
+        Value::from("10.12.112.16")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrhost("10.12.112.0/20", 268)"#)
+            .unwrap(),
+        Value::from("10.12.113.12")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrhost("10.12.112.0/20", -2)"#)
+            .unwrap(),
+        Value::from("10.12.127.254")
+    );
+    // Host bits of the prefix address are ignored.
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrhost("10.12.112.7/20", 0)"#)
+            .unwrap(),
+        Value::from("10.12.112.0")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrhost("fd00:fd12:3456:7890:00a2::/72", 34)"#)
+            .unwrap(),
+        Value::from("fd00:fd12:3456:7890::22")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrhost("::/0", -1)"#).unwrap(),
+        Value::from("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff")
+    );
+
+    assert_func_error(
+        &ctx,
+        r#"cidrhost("10.12.112.0/20", 4096)"#,
+        "cidrhost",
+        "prefix of 20 bits cannot accommodate a host numbered 4096",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrhost("10.12.112.0/20", -4097)"#,
+        "cidrhost",
+        "prefix of 20 bits cannot accommodate a host numbered -4097",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrhost("10.12.112.0", 1)"#,
+        "cidrhost",
+        "invalid CIDR expression `10.12.112.0`",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrhost("10.12.112.0/20", 1.5)"#,
+        "cidrhost",
+        "invalid number `1.5`: value must be a whole number",
+    );
+}
+
+#[test]
+fn cidrnetmask_func() {
+    let ctx = context([("cidrnetmask", cidrnetmask())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrnetmask("172.16.0.0/12")"#).unwrap(),
+        Value::from("255.240.0.0")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrnetmask("10.0.0.0/0")"#).unwrap(),
+        Value::from("0.0.0.0")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrnetmask("10.0.0.1/32")"#).unwrap(),
+        Value::from("255.255.255.255")
+    );
+
+    assert_func_error(
+        &ctx,
+        r#"cidrnetmask("fd00::/8")"#,
+        "cidrnetmask",
+        "IPv6 addresses cannot have a netmask",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrnetmask("10.0.0.0/33")"#,
+        "cidrnetmask",
+        "invalid CIDR expression `10.0.0.0/33`",
+    );
+}
+
+#[test]
+fn cidrsubnet_func() {
+    let ctx = context([("cidrsubnet", cidrsubnet())]);
+
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrsubnet("172.16.0.0/12", 4, 2)"#)
+            .unwrap(),
+        Value::from("172.18.0.0/16")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrsubnet("10.1.2.0/24", 4, 15)"#)
+            .unwrap(),
+        Value::from("10.1.2.240/28")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrsubnet("10.1.2.0/24", 0, 0)"#)
+            .unwrap(),
+        Value::from("10.1.2.0/24")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrsubnet("fd00:fd12:3456:7890::/56", 16, 162)"#)
+            .unwrap(),
+        Value::from("fd00:fd12:3456:7800:a200::/72")
+    );
+    assert_eq!(
+        ctx.evaluate_str(r#"cidrsubnet("::/0", 128, 1)"#).unwrap(),
+        Value::from("::1/128")
+    );
+
+    assert_func_error(
+        &ctx,
+        r#"cidrsubnet("172.16.0.0/12", 21, 0)"#,
+        "cidrsubnet",
+        "insufficient address space to extend prefix of 12 by 21",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrsubnet("172.16.0.0/12", 4, 16)"#,
+        "cidrsubnet",
+        "prefix extension of 4 does not accommodate a subnet numbered 16",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrsubnet("172.16.0.0/12", 4, -1)"#,
+        "cidrsubnet",
+        "prefix extension of 4 does not accommodate a subnet numbered -1",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrsubnet("172.16.0.0/12", -1, 0)"#,
+        "cidrsubnet",
+        "insufficient address space to extend prefix of 12 by -1",
+    );
+    assert_func_error(
+        &ctx,
+        r#"cidrsubnet("not-a-cidr/12", 4, 1)"#,
+        "cidrsubnet",
+        "invalid CIDR expression `not-a-cidr/12`",
+    );
+}
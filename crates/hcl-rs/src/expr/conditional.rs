@@ -3,6 +3,31 @@ use serde::Deserialize;
 
 /// The conditional operator allows selecting from one of two expressions based on the outcome of a
 /// boolean expression.
+///
+/// # Evaluation
+///
+/// The condition must evaluate to a boolean value. No type conversion is applied: numbers,
+/// strings (including `"true"` and `"false"`), `null` and collections are rejected with an
+/// [`ErrorKind::Unexpected`][crate::eval::ErrorKind::Unexpected] error which contains the
+/// offending value. Only the branch selected by the condition is evaluated.
+///
+/// The same rule applies to the `if` clause of `for` expressions and to template `if`
+/// directives.
+///
+/// ```
+/// use hcl::eval::{Context, ErrorKind, Evaluate};
+/// use hcl::expr::Conditional;
+/// use hcl::Value;
+///
+/// let ctx = Context::new();
+///
+/// let cond = Conditional::new(true, "yes", "no");
+/// assert_eq!(cond.evaluate(&ctx).unwrap(), Value::from("yes"));
+///
+/// let cond = Conditional::new(1, "yes", "no");
+/// let err = cond.evaluate(&ctx).unwrap_err();
+/// assert_eq!(err.kind(), &ErrorKind::Unexpected(Value::from(1), "a boolean"));
+/// ```
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Conditional {
     /// A condition expression that evaluates to a boolean value.
@@ -28,4 +53,12 @@ impl Conditional {
             false_expr: false_expr.into(),
         }
     }
+
+    // Returns `true` if the condition expression needs to be wrapped in parenthesis to preserve
+    // the structure of the conditional when it is formatted. The conditional operator is
+    // right-associative, thus only nested conditionals within the condition need parenthesis,
+    // e.g. `(a ? b : c) ? d : e`. Nested conditionals within the branches never do.
+    pub(crate) fn cond_needs_parens(&self) -> bool {
+        matches!(self.cond_expr, Expression::Conditional(_))
+    }
 }
@@ -51,6 +51,7 @@ impl<'de> de::Deserialize<'de> for Expression {
             Conditional,
             Operation,
             ForExpr,
+            Raw,
         }
 
         struct FieldVisitor;
@@ -81,9 +82,10 @@ impl<'de> de::Deserialize<'de> for Expression {
                     11u64 => Ok(Field::Conditional),
                     12u64 => Ok(Field::Operation),
                     13u64 => Ok(Field::ForExpr),
+                    14u64 => Ok(Field::Raw),
                     _ => Err(de::Error::invalid_value(
                         Unexpected::Unsigned(value),
-                        &"variant index 0 <= i < 14",
+                        &"variant index 0 <= i < 15",
                     )),
                 }
             }
@@ -107,6 +109,7 @@ impl<'de> de::Deserialize<'de> for Expression {
                     "Conditional" => Ok(Field::Conditional),
                     "Operation" => Ok(Field::Operation),
                     "ForExpr" => Ok(Field::ForExpr),
+                    "Raw" => Ok(Field::Raw),
                     _ => Err(de::Error::unknown_variant(value, VARIANTS)),
                 }
             }
@@ -130,6 +133,7 @@ impl<'de> de::Deserialize<'de> for Expression {
                     b"Conditional" => Ok(Field::Conditional),
                     b"Operation" => Ok(Field::Operation),
                     b"ForExpr" => Ok(Field::ForExpr),
+                    b"Raw" => Ok(Field::Raw),
                     _ => {
                         let value = &String::from_utf8_lossy(value);
                         Err(de::Error::unknown_variant(value, VARIANTS))
@@ -244,6 +248,7 @@ impl<'de> de::Deserialize<'de> for Expression {
                     (Field::Conditional, v) => v.newtype_variant().map(Expression::Conditional),
                     (Field::Operation, v) => v.newtype_variant().map(Expression::Operation),
                     (Field::ForExpr, v) => v.newtype_variant().map(Expression::ForExpr),
+                    (Field::Raw, v) => v.newtype_variant().map(Expression::Raw),
                 }
             }
         }
@@ -263,6 +268,7 @@ impl<'de> de::Deserialize<'de> for Expression {
             "Conditional",
             "Operation",
             "ForExpr",
+            "Raw",
         ];
 
         deserializer.deserialize_enum("$hcl::Expression", VARIANTS, ExpressionVisitor)
@@ -560,6 +566,7 @@ impl<'de> de::VariantAccess<'de> for Expression {
             Expression::Conditional(v) => seed.deserialize(v.into_deserializer()),
             Expression::Operation(v) => seed.deserialize(*v),
             Expression::ForExpr(v) => seed.deserialize(v.into_deserializer()),
+            Expression::Raw(v) => seed.deserialize(v.into_deserializer()),
             _ => Err(self.invalid_type(&"newtype variant")),
         }
     }
@@ -1247,6 +1254,14 @@ impl<'de> IntoDeserializer<'de, Error> for Variable {
     }
 }
 
+impl<'de> IntoDeserializer<'de, Error> for RawExpression {
+    type Deserializer = StringDeserializer<Error>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self.into_inner().into_deserializer()
+    }
+}
+
 impl<'de> de::Deserialize<'de> for HeredocStripMode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1267,7 +1282,7 @@ impl<'de> IntoDeserializer<'de, Error> for HeredocStripMode {
 impl_variant_name! {
     Expression => {
         Null, Bool, Number, String, Array, Object, TemplateExpr, Variable,
-        Traversal, FuncCall, Parenthesis, Conditional, Operation, ForExpr
+        Traversal, FuncCall, Parenthesis, Conditional, Operation, ForExpr, Raw
     },
     ObjectKey => { Identifier, Expression },
     Operation => { Unary, Binary },
@@ -10,8 +10,8 @@ impl From<expr::Expression> for Expression {
             expr::Expression::Number(num) => num.value_into(),
             expr::Expression::String(string) => string.value_into(),
             expr::Expression::Object(object) => Expression::from_iter(object),
-            expr::Expression::UnaryOp(unary) => UnaryOp::from(*unary).into(),
-            expr::Expression::BinaryOp(binary) => BinaryOp::from(*binary).into(),
+            expr::Expression::UnaryOp(unary) => normalize_unary_op(UnaryOp::from(*unary)),
+            expr::Expression::BinaryOp(binary) => BinaryOpChain::new(*binary).into_expr(),
             expr::Expression::ForExpr(for_expr) => ForExpr::from(*for_expr).into(),
             expr::Expression::StringTemplate(template) => TemplateExpr::from(template).into(),
             expr::Expression::HeredocTemplate(template) => Heredoc::from(*template).into(),
@@ -53,6 +53,8 @@ impl From<Expression> for expr::Expression {
             Expression::Traversal(traversal) => expr::Traversal::from(*traversal).into(),
             Expression::Parenthesis(parens) => expr::Parenthesis::new((*parens).into()).into(),
             Expression::Conditional(cond) => expr::Conditional::from(*cond).into(),
+            // Raw expressions which fail to parse are preserved as string literals.
+            Expression::Raw(raw) => raw.parse().unwrap_or_else(|_| raw.into_inner().into()),
         }
     }
 }
@@ -176,6 +178,18 @@ impl From<expr::BinaryOp> for Operation {
     }
 }
 
+// The parser treats `-5` as a negative number literal and not as a unary operation. Negated
+// non-negative number literals which were constructed in code are normalized in the same way.
+fn normalize_unary_op(op: UnaryOp) -> Expression {
+    match op {
+        UnaryOp {
+            operator: UnaryOperator::Neg,
+            expr: Expression::Number(num),
+        } if num.as_f64().is_some_and(f64::is_sign_positive) => Expression::Number(-num),
+        op => op.into(),
+    }
+}
+
 impl From<expr::UnaryOp> for UnaryOp {
     fn from(value: expr::UnaryOp) -> Self {
         UnaryOp::new(value.operator.value_into(), value.expr)
@@ -184,19 +198,133 @@ impl From<expr::UnaryOp> for UnaryOp {
 
 impl From<UnaryOp> for expr::UnaryOp {
     fn from(value: UnaryOp) -> Self {
-        expr::UnaryOp::new(value.operator, value.expr)
+        let parens = value.operand_needs_parens();
+        expr::UnaryOp::new(value.operator, parenthesize_if(value.expr, parens))
     }
 }
 
 impl From<expr::BinaryOp> for BinaryOp {
     fn from(value: expr::BinaryOp) -> Self {
-        BinaryOp::new(value.lhs_expr, value.operator.value_into(), value.rhs_expr)
+        BinaryOpChain::new(value).into_binary_op()
     }
 }
 
 impl From<BinaryOp> for expr::BinaryOp {
     fn from(value: BinaryOp) -> Self {
-        expr::BinaryOp::new(value.lhs_expr, value.operator, value.rhs_expr)
+        let lhs_parens = value.lhs_needs_parens();
+        let rhs_parens = value.rhs_needs_parens();
+
+        expr::BinaryOp::new(
+            parenthesize_if(value.lhs_expr, lhs_parens),
+            value.operator,
+            parenthesize_if(value.rhs_expr, rhs_parens),
+        )
+    }
+}
+
+// The parser produces right-leaning chains of binary operations without taking operator
+// precedence into account, e.g. `a - b * c + d` is parsed as `a - (b * (c + d))`. Since the
+// `hcl_edit` representation is encoded as-is, this chain is semantically flat.
+//
+// `BinaryOpChain` collects the operands and operators of such a chain from left to right and
+// rebuilds it as a tree which respects operator precedence and left-associativity, so that the
+// nesting of `BinaryOp` values always reflects the evaluation order.
+struct BinaryOpChain {
+    operands: Vec<Expression>,
+    operators: Vec<BinaryOperator>,
+}
+
+impl BinaryOpChain {
+    fn new(op: expr::BinaryOp) -> BinaryOpChain {
+        let mut chain = BinaryOpChain {
+            operands: Vec::new(),
+            operators: Vec::new(),
+        };
+
+        chain.push_binary_op(op);
+        chain
+    }
+
+    fn push_binary_op(&mut self, op: expr::BinaryOp) {
+        self.push_operand(op.lhs_expr);
+        self.operators.push(op.operator.value_into());
+        self.push_operand(op.rhs_expr);
+    }
+
+    fn push_operand(&mut self, expr: expr::Expression) {
+        match expr {
+            expr::Expression::BinaryOp(op) => self.push_binary_op(*op),
+            expr => self.operands.push(expr.into()),
+        }
+    }
+
+    // A conditional which is the last operand of a chain extends over the whole chain, e.g.
+    // `a == b ? c : d` is parsed as `a == (b ? c : d)`, but actually means `(a == b) ? c : d`.
+    fn into_expr(mut self) -> Expression {
+        match self.operands.pop() {
+            Some(Expression::Conditional(cond)) => {
+                let cond = *cond;
+                self.operands.push(cond.cond_expr);
+                let cond_expr = self.into_binary_op();
+                Conditional::new(
+                    Operation::Binary(cond_expr),
+                    cond.true_expr,
+                    cond.false_expr,
+                )
+                .into()
+            }
+            Some(operand) => {
+                self.operands.push(operand);
+                self.into_binary_op().into()
+            }
+            None => unreachable!("binary operation chain without operands"),
+        }
+    }
+
+    fn into_binary_op(self) -> BinaryOp {
+        fn reduce(stack: &mut Vec<Expression>, operator: BinaryOperator) {
+            let rhs_expr = stack.pop().unwrap();
+            let lhs_expr = stack.pop().unwrap();
+            stack.push(BinaryOp::new(lhs_expr, operator, rhs_expr).into());
+        }
+
+        let mut operands = self.operands.into_iter();
+        let mut stack = vec![operands.next().unwrap()];
+        let mut operators: Vec<BinaryOperator> = Vec::with_capacity(self.operators.len());
+
+        for (operator, operand) in self.operators.into_iter().zip(operands) {
+            while let Some(top) = operators.last().copied() {
+                if top.precedence() < operator.precedence() {
+                    break;
+                }
+
+                operators.pop();
+                reduce(&mut stack, top);
+            }
+
+            operators.push(operator);
+            stack.push(operand);
+        }
+
+        while let Some(operator) = operators.pop() {
+            reduce(&mut stack, operator);
+        }
+
+        match stack.pop() {
+            Some(Expression::Operation(op)) => match *op {
+                Operation::Binary(op) => op,
+                Operation::Unary(_) => unreachable!("expected binary operation"),
+            },
+            _ => unreachable!("expected binary operation"),
+        }
+    }
+}
+
+fn parenthesize_if(expr: Expression, parens: bool) -> expr::Expression {
+    if parens {
+        expr::Parenthesis::new(expr.into()).into()
+    } else {
+        expr.into()
     }
 }
 
@@ -4,6 +4,12 @@ use serde::Deserialize;
 
 /// A for expression is a construct for constructing a collection by projecting the items from
 /// another collection.
+///
+/// When evaluated, the elements of the result follow the iteration order of the source
+/// collection: arrays are iterated by index and objects in the insertion order of their keys.
+/// Unlike Terraform, object keys are not sorted lexically. If the result is an object, its keys
+/// appear in the order in which they were first produced, which also applies to grouping mode
+/// where the values of each group keep the iteration order as well.
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct ForExpr {
     /// Optional name of the variable that will be temporarily assigned the key of each element
@@ -9,29 +9,63 @@ mod edit;
 mod for_expr;
 mod func_call;
 mod operation;
+mod raw;
+mod references;
 pub(crate) mod ser;
 mod template_expr;
 mod traversal;
 mod variable;
+mod walk;
 
+use self::references::ReferenceCollector;
 use self::ser::ExpressionSerializer;
 pub use self::{
     conditional::Conditional,
     for_expr::ForExpr,
     func_call::{FuncCall, FuncCallBuilder, FuncName},
     operation::{BinaryOp, BinaryOperator, Operation, UnaryOp, UnaryOperator},
+    raw::RawExpression,
     template_expr::{Heredoc, HeredocStripMode, TemplateExpr},
-    traversal::{Traversal, TraversalBuilder, TraversalOperator},
+    traversal::{traversal, Traversal, TraversalBuilder, TraversalOperator},
     variable::Variable,
 };
+use crate::eval::{Context, Evaluate};
 use crate::format;
 use crate::ser::with_internal_serialization;
 use crate::{Identifier, Number, Result, Value};
+use hcl_primitives::ident::is_ident;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fmt::{self, Display};
 
 /// The object type used in the expression sub-language.
+///
+/// Objects preserve the insertion order of their keys. Use the `entry` method to insert or
+/// update values without looking up the key twice:
+///
+/// ```
+/// use hcl::expr::{Expression, Object, ObjectKey};
+/// use hcl::Identifier;
+///
+/// let mut object = Object::new();
+///
+/// for key in ["b", "a", "b"] {
+///     let count = object
+///         .entry(ObjectKey::from(Identifier::unchecked(key)))
+///         .or_insert(Expression::from(0));
+///
+///     if let Expression::Number(num) = count {
+///         *num = *num + 1.into();
+///     }
+/// }
+///
+/// let expected = Object::from([
+///     (ObjectKey::from(Identifier::unchecked("b")), Expression::from(2)),
+///     (ObjectKey::from(Identifier::unchecked("a")), Expression::from(1)),
+/// ]);
+///
+/// assert_eq!(object, expected);
+/// ```
 pub type Object<K, V> = vecmap::VecMap<K, V>;
 
 /// A type representing the expression sub-language. It is used in HCL attributes to specify
@@ -69,6 +103,13 @@ pub enum Expression {
     Operation(Box<Operation>),
     /// A construct for constructing a collection by projecting the items from another collection.
     ForExpr(Box<ForExpr>),
+    /// Raw HCL source text which is written verbatim by the formatter.
+    ///
+    /// The text is not validated. Converting it into an [`hcl_edit`][crate::edit] expression
+    /// parses it and silently falls back to a string literal containing the text if parsing
+    /// fails. Serializers other than the ones provided by this crate receive the text wrapped in
+    /// a `${...}` interpolation, like other non-literal expressions.
+    Raw(RawExpression),
 }
 
 impl Expression {
@@ -79,6 +120,262 @@ impl Expression {
     {
         with_internal_serialization(|| value.serialize(ExpressionSerializer))
     }
+
+    /// Creates an expression for a plain string literal.
+    ///
+    /// The string is never interpreted as a template: template markers (`${` and `%{`) contained
+    /// in it are escaped (`$${` and `%%{`) when the expression is formatted, so that the string
+    /// round-trips as a literal.
+    ///
+    /// This is equivalent to `Expression::from(&str)` and `Expression::String`, which also
+    /// represent literal strings, but makes the intent explicit. In contrast, strings wrapped in
+    /// a [`TemplateExpr`], as well as [`Value::String`]s containing unescaped template markers,
+    /// are formatted verbatim and are interpreted as templates when the HCL is parsed again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Expression;
+    ///
+    /// let expr = Expression::literal_string("Hello ${name}!");
+    ///
+    /// assert_eq!(hcl::format::to_string(&expr).unwrap(), r#""Hello $${name}!""#);
+    /// ```
+    pub fn literal_string<T>(s: T) -> Expression
+    where
+        T: Into<String>,
+    {
+        Expression::String(s.into())
+    }
+
+    /// Evaluates all function calls with constant arguments using only the functions declared in
+    /// the `Context`, and returns the resulting expression.
+    ///
+    /// Variables declared in the `Context` are ignored. Every subexpression that references a
+    /// variable, or that cannot be evaluated for any other reason (e.g. an undefined function or
+    /// a function call error), is kept as-is instead of producing an error. All other
+    /// subexpressions are folded into their values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::eval::{Context, FuncArgs, FuncDef, ParamType};
+    /// use hcl::expr::{Expression, FuncCall, Traversal, Variable};
+    /// use hcl::Value;
+    ///
+    /// fn upper(args: FuncArgs) -> Result<Value, String> {
+    ///     Ok(Value::from(args[0].as_str().unwrap().to_uppercase()))
+    /// }
+    ///
+    /// let mut ctx = Context::new();
+    /// ctx.declare_func("upper", FuncDef::builder().param(ParamType::String).build(upper));
+    /// ctx.declare_var("var", Value::from_iter([("b", "ignored")]));
+    ///
+    /// let var_b = Expression::from(
+    ///     Traversal::builder(Variable::new("var").unwrap())
+    ///         .attr("b")
+    ///         .build(),
+    /// );
+    ///
+    /// let expr = Expression::from_iter([
+    ///     Expression::from(FuncCall::builder("upper").arg("a").build()),
+    ///     var_b.clone(),
+    /// ]);
+    ///
+    /// let expected = Expression::from_iter([Expression::from("A"), var_b]);
+    ///
+    /// assert_eq!(expr.evaluate_with_functions_only(&ctx), expected);
+    /// ```
+    pub fn evaluate_with_functions_only(&self, ctx: &Context) -> Expression {
+        let ctx = ctx.functions_only();
+        let mut expr = self.clone();
+        // Errors are expected for subexpressions that reference variables. These are left
+        // unevaluated by `evaluate_in_place`, so the errors can be ignored.
+        _ = expr.evaluate_in_place(&ctx);
+        expr
+    }
+
+    /// Tries to evaluate the expression into a [`Value`] using the given `Context`, resolving
+    /// as much of it as possible.
+    ///
+    /// Returns `Ok` with the value if the expression could be fully evaluated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` with the partially resolved expression if the expression could not be fully
+    /// evaluated: subexpressions that could be evaluated are folded into their values, while
+    /// subexpressions that cannot be evaluated (e.g. because they reference an undeclared
+    /// variable or function, or because a function call fails) are kept as-is. The partially
+    /// resolved expression can be passed to `try_into_value` again once more variables or
+    /// functions are available, which allows resolving expressions incrementally.
+    ///
+    /// Template expressions are folded all at once or not at all: if any interpolation or
+    /// directive of a template cannot be evaluated, the whole template is kept as-is, including
+    /// interpolations which could be evaluated on their own.
+    ///
+    /// Use [`Evaluate::evaluate`] instead to obtain the error which prevents the full evaluation
+    /// of an expression.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl::eval::Context;
+    /// use hcl::expr::{Expression, Traversal, Variable};
+    ///
+    /// let input = "[1 + 1, var.name]";
+    /// let expr = Expression::from(input.parse::<hcl::edit::expr::Expression>()?);
+    ///
+    /// let partial = expr.try_into_value(&Context::new()).unwrap_err();
+    /// let var_name = Traversal::new(Variable::new("var")?, ["name"]);
+    /// assert_eq!(partial, Expression::from_iter([Expression::from(2), var_name.into()]));
+    ///
+    /// let mut ctx = Context::new();
+    /// ctx.declare_var("var", hcl::value!({ name = "web" }));
+    ///
+    /// assert_eq!(partial.try_into_value(&ctx), Ok(hcl::value!([2, "web"])));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn try_into_value(self, ctx: &Context) -> Result<Value, Expression> {
+        let mut expr = self;
+
+        match expr.evaluate_in_place(ctx) {
+            Ok(()) => Ok(Value::from(expr)),
+            Err(_) => Err(expr),
+        }
+    }
+
+    /// Returns the variables referenced by the expression.
+    ///
+    /// Each returned expression is either an [`Expression::Variable`] or an
+    /// [`Expression::Traversal`] whose root is a variable, e.g. `var.list`. Variables referenced
+    /// in index expressions and in template interpolations and directives are included as well.
+    /// References are deduplicated and returned in the order of their first occurrence.
+    ///
+    /// Variables bound by `for` expressions and `for` template directives are local to their
+    /// body and thus not reported, but the collection they iterate over is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl::expr::{Expression, Traversal, Variable};
+    ///
+    /// let input = "[for x in var.list : x + var.offset]";
+    /// let expr = Expression::from(input.parse::<hcl::edit::expr::Expression>()?);
+    ///
+    /// let var = Variable::new("var")?;
+    ///
+    /// assert_eq!(
+    ///     expr.referenced_variables(),
+    ///     [
+    ///         Expression::from(Traversal::new(var.clone(), ["list"])),
+    ///         Expression::from(Traversal::new(var, ["offset"])),
+    ///     ]
+    /// );
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn referenced_variables(&self) -> Vec<Expression> {
+        ReferenceCollector::collect(self)
+    }
+
+    /// Recursively applies `f` to the expression and all of its sub-expressions.
+    ///
+    /// The traversal is post-order: `f` is applied to all sub-expressions of an expression,
+    /// from left to right, before it is applied to the expression itself. When `f` is called for
+    /// an expression, its sub-expressions were thus already transformed, which makes it possible
+    /// to implement bottom-up transformations like constant folding in a single pass. Expressions
+    /// returned by `f` are not walked again.
+    ///
+    /// Object keys which are expressions are visited before their values. Templates are not
+    /// walked into, because their interpolations and directives are not represented as
+    /// `Expression` values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::expr::{BinaryOp, BinaryOperator, Expression, Operation};
+    ///
+    /// let mut expr = Expression::from_iter([
+    ///     Expression::from(BinaryOp::new(1, BinaryOperator::Plus, 2)),
+    ///     Expression::from(3),
+    /// ]);
+    ///
+    /// // Fold additions of number literals.
+    /// expr.walk_mut(|expr| {
+    ///     if let Expression::Operation(op) = expr {
+    ///         if let Operation::Binary(BinaryOp {
+    ///             lhs_expr: Expression::Number(lhs),
+    ///             operator: BinaryOperator::Plus,
+    ///             rhs_expr: Expression::Number(rhs),
+    ///         }) = &**op
+    ///         {
+    ///             *expr = Expression::Number(*lhs + *rhs);
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(expr, Expression::from_iter([3, 3]));
+    /// ```
+    pub fn walk_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Expression),
+    {
+        walk::walk_mut(self, &mut f);
+    }
+
+    /// Normalizes the keys of all objects within the expression, including nested objects.
+    ///
+    /// If `prefer_identifiers` is `true`, string literal keys which are valid identifiers are
+    /// converted into [`ObjectKey::Identifier`], e.g. `{ "foo" = 1 }` becomes `{ foo = 1 }`.
+    /// String keys which are not valid identifiers, as well as the strings `"true"`, `"false"`
+    /// and `"null"`, stay string keys, because writing them unquoted would change the meaning of
+    /// the key.
+    ///
+    /// If `prefer_identifiers` is `false`, all [`ObjectKey::Identifier`] keys are converted into
+    /// string literal keys, e.g. `{ foo = 1 }` becomes `{ "foo" = 1 }`.
+    ///
+    /// Keys which are neither identifiers nor string literals are left untouched. If
+    /// normalization makes two keys of an object equal, the value of the latter replaces the
+    /// value of the former.
+    ///
+    /// This is useful to canonicalize objects before formatting them. Object keys are
+    /// normalized in-place, while the order of the object entries is preserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::expr::{Expression, ObjectKey};
+    /// use hcl::Identifier;
+    ///
+    /// let mut expr = Expression::from_iter([
+    ///     (ObjectKey::from("foo"), Expression::from(1)),
+    ///     (ObjectKey::from("not-an-ident?"), Expression::from(2)),
+    ///     (ObjectKey::from(Identifier::unchecked("bar")), Expression::from(3)),
+    /// ]);
+    ///
+    /// expr.normalize_keys(true);
+    /// assert_eq!(expr.to_string(), "{\n  foo = 1\n  \"not-an-ident?\" = 2\n  bar = 3\n}");
+    ///
+    /// expr.normalize_keys(false);
+    /// assert_eq!(expr.to_string(), "{\n  \"foo\" = 1\n  \"not-an-ident?\" = 2\n  \"bar\" = 3\n}");
+    /// ```
+    pub fn normalize_keys(&mut self, prefer_identifiers: bool) {
+        self.walk_mut(|expr| {
+            if let Expression::Object(object) = expr {
+                *object = std::mem::take(object)
+                    .into_iter()
+                    .map(|(key, value)| (key.normalize(prefer_identifiers), value))
+                    .collect();
+            }
+        });
+    }
 }
 
 impl From<Expression> for Value {
@@ -267,6 +564,12 @@ impl From<Variable> for Expression {
     }
 }
 
+impl From<RawExpression> for Expression {
+    fn from(raw: RawExpression) -> Self {
+        Expression::Raw(raw)
+    }
+}
+
 impl Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Formatting an `Expression` as string cannot fail.
@@ -301,6 +604,24 @@ impl From<Identifier> for ObjectKey {
     }
 }
 
+impl ObjectKey {
+    fn normalize(self, prefer_identifier: bool) -> ObjectKey {
+        match self {
+            ObjectKey::Expression(Expression::String(string))
+                if prefer_identifier
+                    && is_ident(&string)
+                    && !matches!(string.as_str(), "true" | "false" | "null") =>
+            {
+                ObjectKey::Identifier(Identifier::unchecked(string))
+            }
+            ObjectKey::Identifier(ident) if !prefer_identifier => {
+                ObjectKey::Expression(Expression::String(ident.into_inner()))
+            }
+            key => key,
+        }
+    }
+}
+
 impl From<ObjectKey> for String {
     fn from(key: ObjectKey) -> Self {
         key.to_string()
@@ -341,3 +662,155 @@ where
 {
     Expression::from_serializable(&value)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(input: &str) -> Expression {
+        let parsed: crate::edit::expr::Expression = input.parse().unwrap();
+        Expression::from(parsed)
+    }
+
+    #[test]
+    fn normalize_keys() {
+        let mut expr = parse(
+            r#"{
+                a = 1
+                "b" = { c = 2, "d.e" = 3 }
+                "f g" = [{ "h" = 4 }]
+                "true" = 5
+                (i) = 6
+                "${j}" = 7
+            }"#,
+        );
+
+        expr.normalize_keys(true);
+
+        let expected = parse(
+            r#"{
+                a = 1
+                b = { c = 2, "d.e" = 3 }
+                "f g" = [{ h = 4 }]
+                "true" = 5
+                (i) = 6
+                "${j}" = 7
+            }"#,
+        );
+
+        assert_eq!(expr, expected);
+
+        expr.normalize_keys(false);
+
+        let expected = parse(
+            r#"{
+                "a" = 1
+                "b" = { "c" = 2, "d.e" = 3 }
+                "f g" = [{ "h" = 4 }]
+                "true" = 5
+                (i) = 6
+                "${j}" = 7
+            }"#,
+        );
+
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn normalize_keys_merges_duplicates() {
+        let mut expr = parse(r#"{ a = 1, "a" = 2 }"#);
+        expr.normalize_keys(true);
+        assert_eq!(expr, parse("{ a = 2 }"));
+    }
+
+    #[test]
+    fn into_edit_expression() {
+        use crate::edit::structure::{Attribute, Body};
+        use crate::edit::Ident;
+
+        let func_call = FuncCall::builder("merge")
+            .arg(Variable::unchecked("defaults"))
+            .arg(
+                ForExpr::new(
+                    Identifier::unchecked("v"),
+                    Variable::unchecked("list"),
+                    TemplateExpr::from("${v}-suffix"),
+                )
+                .with_key_var(Identifier::unchecked("k"))
+                .with_key_expr(Variable::unchecked("k"))
+                .with_cond_expr(BinaryOp::new(
+                    Variable::unchecked("v"),
+                    BinaryOperator::NotEq,
+                    Expression::Null,
+                )),
+            )
+            .build();
+
+        let expr = crate::edit::expr::Expression::from(Expression::from(func_call.clone()));
+        let encoded = r#"merge(defaults, {for k, v in list : k => "${v}-suffix" if v != null})"#;
+
+        assert_eq!(expr.to_string(), encoded);
+        assert_eq!(parse(encoded), Expression::from(func_call));
+
+        let mut body: Body = "name = \"app\"\n".parse().unwrap();
+        body.push(Attribute::new(Ident::new("tags"), expr));
+
+        assert_eq!(
+            body.to_string(),
+            format!("name = \"app\"\ntags = {encoded}\n")
+        );
+    }
+
+    #[test]
+    fn negative_numbers() {
+        let neg = |expr| Expression::from(UnaryOp::new(UnaryOperator::Neg, expr));
+
+        // Parse.
+        assert_eq!(parse("-5"), Expression::from(-5));
+        assert_eq!(parse("- 5.5"), Expression::from(-5.5));
+        assert_eq!(parse("-9223372036854775808"), Expression::from(i64::MIN));
+        assert_eq!(parse("-0"), Expression::from(0));
+        assert_eq!(parse("--5"), neg(Expression::from(-5)));
+        assert_eq!(parse("-x"), neg(Expression::from(Variable::unchecked("x"))));
+
+        // Construction keeps the unary operation, which formats like the negative number.
+        assert_eq!(
+            neg(Expression::from(5)),
+            Expression::Operation(Box::new(Operation::Unary(UnaryOp::new(
+                UnaryOperator::Neg,
+                5
+            ))))
+        );
+        assert_eq!(neg(Expression::from(5)).to_string(), "-5");
+        assert_eq!(
+            parse(&neg(Expression::from(5)).to_string()),
+            Expression::from(-5)
+        );
+
+        // Conversion from `hcl-edit` normalizes negated number literals like the parser.
+        let edit = crate::edit::expr::Expression::from(crate::edit::expr::UnaryOp::new(
+            UnaryOperator::Neg,
+            crate::edit::expr::Expression::from(5),
+        ));
+        assert_eq!(Expression::from(edit), Expression::from(-5));
+
+        // Evaluation.
+        let ctx = Context::new();
+        assert_eq!(
+            neg(Expression::from(-5)).evaluate(&ctx).unwrap(),
+            Value::from(5)
+        );
+        assert_eq!(parse("-(5)").evaluate(&ctx).unwrap(), Value::from(-5));
+
+        // Formatting round-trips.
+        for input in ["-5", "-5.5", "--5", "-(5)", "1 - -5"] {
+            let expr = parse(input);
+            assert_eq!(expr.to_string(), input);
+            assert_eq!(parse(&expr.to_string()), expr);
+
+            let edit = crate::edit::expr::Expression::from(expr.clone());
+            assert_eq!(edit.to_string(), input);
+        }
+    }
+}
@@ -27,6 +27,22 @@ impl From<BinaryOp> for Operation {
 }
 
 /// An operation that applies an operator to one expression.
+///
+/// A number literal preceded by `-`, like `-5`, is parsed as a negative number and not as a
+/// unary operation. A `UnaryOp` which negates a number literal formats exactly like the negative
+/// number and evaluates to the same value:
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use hcl::eval::{Context, Evaluate};
+/// use hcl::expr::{Expression, UnaryOp, UnaryOperator};
+///
+/// let expr = Expression::from(UnaryOp::new(UnaryOperator::Neg, 5));
+/// assert_eq!(expr.to_string(), "-5");
+/// assert_eq!(expr.evaluate(&Context::new())?, hcl::Value::from(-5));
+/// #   Ok(())
+/// # }
+/// ```
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UnaryOp {
     /// The unary operator to use on the expression.
@@ -46,6 +62,16 @@ impl UnaryOp {
             expr: expr.into(),
         }
     }
+
+    // Returns `true` if the operand needs to be wrapped in parenthesis to preserve the structure
+    // of the operation when it is formatted.
+    pub(crate) fn operand_needs_parens(&self) -> bool {
+        match &self.expr {
+            Expression::Conditional(_) => true,
+            Expression::Operation(op) => matches!(**op, Operation::Binary(_)),
+            _ => false,
+        }
+    }
 }
 
 /// An operation that applies an operator to two expressions.
@@ -73,167 +99,121 @@ impl BinaryOp {
         }
     }
 
-    // Normalize binary operation following operator precedence rules.
-    //
-    // The result can be evaluated from left to right without checking operator precendence.
-    pub(crate) fn normalize(self) -> BinaryOp {
-        use Operand::{BinOp, Expr};
-
-        // We only care whether the operand is another binary operation or not. Any other
-        // expression (including unary oparations) is treated the same way and does not require
-        // special precedence rules.
-        enum Operand {
-            BinOp(BinaryOp),
-            Expr(Expression),
-        }
-
-        impl From<Expression> for Operand {
-            fn from(expr: Expression) -> Self {
-                match expr {
-                    Expression::Operation(operation) => match *operation {
-                        Operation::Binary(binary) => Operand::BinOp(binary),
-                        unary => Operand::Expr(Expression::from(unary)),
-                    },
-                    expr => Operand::Expr(expr),
-                }
-            }
-        }
-
-        let lhs = Operand::from(self.lhs_expr);
-        let operator = self.operator;
-        let rhs = Operand::from(self.rhs_expr);
-
-        match (lhs, rhs) {
-            (BinOp(lhs), BinOp(rhs)) => normalize_both(lhs.normalize(), operator, rhs.normalize()),
-            (BinOp(lhs), Expr(rhs)) => normalize_lhs(lhs.normalize(), operator, rhs),
-            (Expr(lhs), BinOp(rhs)) => normalize_rhs(lhs, operator, rhs.normalize()),
-            (Expr(lhs), Expr(rhs)) => BinaryOp::new(lhs, operator, rhs),
-        }
+    // Returns `true` if the left-hand-side expression needs to be wrapped in parenthesis to
+    // preserve the structure of the operation when it is formatted.
+    pub(crate) fn lhs_needs_parens(&self) -> bool {
+        operand_needs_parens(self.operator, &self.lhs_expr, false)
     }
-}
 
-fn normalize_both(lhs: BinaryOp, operator: BinaryOperator, rhs: BinaryOp) -> BinaryOp {
-    if lhs.operator.precedence() < operator.precedence() {
-        // BinaryOp(BinaryOp(lhs.lhs_expr + lhs.rhs_expr) * BinaryOp(rhs.lhs_expr - rhs.rhs_expr))
-        //
-        // => BinaryOp(lhs.lhs_expr + BinaryOp(BinaryOp(lhs.rhs_expr * rhs.lhs_expr) - rhs.rhs_expr))
-        BinaryOp::new(
-            lhs.lhs_expr,
-            lhs.operator,
-            Operation::Binary(normalize_rhs(lhs.rhs_expr, operator, rhs)),
-        )
-    } else if rhs.operator.precedence() < operator.precedence() {
-        // BinaryOp(BinaryOp(lhs.lhs_expr / lhs.rhs_expr) * BinaryOp(rhs.lhs_expr - rhs.rhs_expr))
-        //
-        // => BinaryOp(BinaryOp(BinaryOp(lhs.lhs_expr / lhs.rhs_expr) * rhs.lhs_expr) - rhs.rhs_expr)
-        BinaryOp::new(
-            Operation::Binary(normalize_lhs(lhs, operator, rhs.lhs_expr)),
-            rhs.operator,
-            rhs.rhs_expr,
-        )
-    } else {
-        // Nothing to normalize.
-        BinaryOp::new(Operation::Binary(lhs), operator, Operation::Binary(rhs))
+    // Returns `true` if the right-hand-side expression needs to be wrapped in parenthesis to
+    // preserve the structure of the operation when it is formatted.
+    pub(crate) fn rhs_needs_parens(&self) -> bool {
+        operand_needs_parens(self.operator, &self.rhs_expr, true)
     }
 }
 
-fn normalize_lhs(lhs: BinaryOp, operator: BinaryOperator, rhs_expr: Expression) -> BinaryOp {
-    if lhs.operator.precedence() < operator.precedence() {
-        // BinaryOp(BinaryOp(lhs.lhs_expr + lhs.rhs_expr) / rhs_expr)
-        //
-        // => BinaryOp(lhs.lhs_expr + BinaryOp(lhs.rhs_expr / rhs_expr))
-        BinaryOp::new(
-            lhs.lhs_expr,
-            lhs.operator,
-            Operation::Binary(BinaryOp::new(lhs.rhs_expr, operator, rhs_expr)),
-        )
-    } else {
-        // Nothing to normalize.
-        BinaryOp::new(Operation::Binary(lhs), operator, rhs_expr)
-    }
-}
-
-fn normalize_rhs(lhs_expr: Expression, operator: BinaryOperator, rhs: BinaryOp) -> BinaryOp {
-    if rhs.operator.precedence() < operator.precedence() {
-        // BinaryOp(lhs_expr / BinaryOp(rhs.lhs_expr + rhs.rhs_expr))
-        //
-        // => BinaryOp(BinaryOp(lhs_expr / rhs.lhs_expr) + rhs.rhs_expr)
-        BinaryOp::new(
-            Operation::Binary(BinaryOp::new(lhs_expr, operator, rhs.lhs_expr)),
-            rhs.operator,
-            rhs.rhs_expr,
-        )
-    } else {
-        // Nothing to normalize.
-        BinaryOp::new(lhs_expr, operator, Operation::Binary(rhs))
+// Binary operators are left-associative, thus a nested operation on the right-hand-side needs
+// parenthesis if its operator has the same precedence, e.g. `a - (b - c)`. Operations with lower
+// precedence always need parenthesis, while operations with higher precedence never do.
+fn operand_needs_parens(operator: BinaryOperator, operand: &Expression, rhs: bool) -> bool {
+    match operand {
+        Expression::Conditional(_) => true,
+        Expression::Operation(op) => match &**op {
+            Operation::Binary(binary) => {
+                let (outer, inner) = (operator.precedence(), binary.operator.precedence());
+                inner < outer || (rhs && inner == outer)
+            }
+            Operation::Unary(_) => false,
+        },
+        _ => false,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::expr::Conditional;
     use pretty_assertions::assert_eq;
 
     macro_rules! binop {
         ($l:expr, $op:expr, $r:expr $(,)?) => {
-            BinaryOp::new($l, $op, $r)
+            Expression::from(BinaryOp::new($l, $op, $r))
         };
     }
 
-    macro_rules! assert_normalizes_to {
-        ($op:expr, $expected:expr $(,)?) => {
-            assert_eq!($op.normalize(), $expected);
+    macro_rules! assert_parses_to {
+        ($input:expr, $expected:expr $(,)?) => {
+            let parsed: crate::edit::expr::Expression = $input.parse().unwrap();
+            assert_eq!(Expression::from(parsed), $expected);
         };
     }
 
     #[test]
-    fn normalize_binary_op() {
+    fn parse_binary_op_precedence() {
         use BinaryOperator::{Div, Mod, Mul, Plus};
 
-        assert_normalizes_to!(
-            binop!(binop!(1, Plus, 2), Div, binop!(3, Mul, 4)),
-            binop!(1, Plus, binop!(2, Div, binop!(3, Mul, 4))),
+        assert_parses_to!(
+            "1 + 2 / 3 * 4",
+            binop!(1, Plus, binop!(binop!(2, Div, 3), Mul, 4)),
         );
 
-        assert_normalizes_to!(
-            binop!(binop!(1, Div, 2), Mul, binop!(3, Plus, binop!(4, Mod, 5))),
+        assert_parses_to!(
+            "1 / 2 * 3 + 4 % 5",
             binop!(binop!(binop!(1, Div, 2), Mul, 3), Plus, binop!(4, Mod, 5)),
         );
 
-        assert_normalizes_to!(
-            binop!(binop!(binop!(1, Plus, 2), Mul, 3), Div, 4),
+        assert_parses_to!(
+            "1 + 2 * 3 / 4",
             binop!(1, Plus, binop!(binop!(2, Mul, 3), Div, 4)),
         );
 
-        assert_normalizes_to!(
-            binop!(1, Div, binop!(binop!(2, Plus, 3), Mul, 4)),
+        assert_parses_to!(
+            "1 / 2 + 3 * 4",
             binop!(binop!(1, Div, 2), Plus, binop!(3, Mul, 4)),
         );
     }
 
     #[test]
-    fn normalize_parenthesized() {
+    fn parse_binary_op_associativity() {
+        use BinaryOperator::{Div, Minus, Plus};
+
+        assert_parses_to!("1 - 2 - 3", binop!(binop!(1, Minus, 2), Minus, 3));
+        assert_parses_to!("1 / 2 / 3", binop!(binop!(1, Div, 2), Div, 3));
+        assert_parses_to!(
+            "1 + 2 + 3 + 4",
+            binop!(binop!(binop!(1, Plus, 2), Plus, 3), Plus, 4),
+        );
+    }
+
+    #[test]
+    fn parse_parenthesized() {
         use BinaryOperator::{Div, Mod, Mul, Plus};
 
-        fn parens(op: BinaryOp) -> Expression {
-            Expression::Parenthesis(Box::new(op.into()))
+        fn parens(expr: Expression) -> Expression {
+            Expression::Parenthesis(Box::new(expr))
         }
 
-        let op = binop!(
-            parens(binop!(1, Div, 2)),
-            Mul,
-            parens(binop!(3, Mod, parens(binop!(4, Plus, 5)))),
+        assert_parses_to!(
+            "(1 / 2) * (3 % (4 + 5))",
+            binop!(
+                parens(binop!(1, Div, 2)),
+                Mul,
+                parens(binop!(3, Mod, parens(binop!(4, Plus, 5)))),
+            ),
         );
-
-        assert_normalizes_to!(op.clone(), op);
     }
 
     #[test]
-    fn already_normalized() {
-        use BinaryOperator::Plus;
-
-        let op = binop!(binop!(1, Plus, 2), Plus, binop!(3, Plus, 4));
-
-        assert_normalizes_to!(op.clone(), op);
+    fn parse_binary_op_in_condition() {
+        use BinaryOperator::{And, Eq, Greater};
+
+        assert_parses_to!(
+            "1 == 2 && 3 > 4 ? 5 : 6",
+            Expression::from(Conditional::new(
+                binop!(binop!(1, Eq, 2), And, binop!(3, Greater, 4)),
+                5,
+                6,
+            )),
+        );
     }
 }
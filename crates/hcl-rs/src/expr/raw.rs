@@ -0,0 +1,91 @@
+use serde::Deserialize;
+use std::ops::Deref;
+
+/// A type representing a raw HCL expression which is written verbatim by the
+/// [`Formatter`][crate::format::Formatter].
+///
+/// This is an escape hatch for embedding pre-rendered HCL fragments, e.g. heredocs produced by
+/// another tool, into a [`Body`][crate::Body]. The formatter neither escapes nor validates the
+/// contents. Only the lines after the first one are indented to match the nesting level of the
+/// surrounding structure, so that multi-line raw expressions line up with their neighbours.
+/// Line breaks are normalized to the newline style of the formatter.
+///
+/// It is the caller's responsibility to ensure that the raw expression is valid HCL. Keep in
+/// mind that the added indentation becomes part of the content of non-flush heredocs (`<<EOT`),
+/// so prefer flush heredocs (`<<-EOT`) for raw expressions in nested blocks.
+///
+/// Raw expressions are parsed when they are evaluated or converted into
+/// [`hcl_edit`][crate::edit] expressions. In the latter case, raw expressions which fail to parse
+/// are converted into string literals.
+///
+/// # Example
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use hcl::expr::RawExpression;
+/// use hcl::{Attribute, Block};
+///
+/// let block = Block::builder("resource")
+///     .add_attribute(Attribute::new(
+///         "policy",
+///         RawExpression::new("<<-EOT\n  {\"Version\": \"2012-10-17\"}\nEOT"),
+///     ))
+///     .build();
+///
+/// let expected = r#"
+/// resource {
+///   policy = <<-EOT
+///     {"Version": "2012-10-17"}
+///   EOT
+/// }
+/// "#.trim_start();
+///
+/// assert_eq!(hcl::format::to_string(&block)?, expected);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct RawExpression(String);
+
+impl RawExpression {
+    /// Creates a new `RawExpression` from the given HCL source text.
+    pub fn new<T>(raw: T) -> RawExpression
+    where
+        T: Into<String>,
+    {
+        RawExpression(raw.into())
+    }
+
+    /// Returns the raw HCL source text as `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes `self` and returns the wrapped raw HCL source text.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for RawExpression {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl From<String> for RawExpression {
+    fn from(raw: String) -> Self {
+        RawExpression(raw)
+    }
+}
+
+impl From<&str> for RawExpression {
+    fn from(raw: &str) -> Self {
+        RawExpression(raw.to_owned())
+    }
+}
@@ -0,0 +1,226 @@
+use super::{Expression, ObjectKey, Operation, TraversalOperator};
+use crate::template::{Directive, Element, Template};
+use crate::Identifier;
+
+/// Collects the variable references of an expression while keeping track of the variables bound
+/// by enclosing `for` expressions and `for` template directives.
+#[derive(Default)]
+pub(super) struct ReferenceCollector {
+    bound: Vec<Identifier>,
+    references: Vec<Expression>,
+}
+
+impl ReferenceCollector {
+    pub(super) fn collect(expr: &Expression) -> Vec<Expression> {
+        let mut collector = ReferenceCollector::default();
+        collector.visit_expr(expr);
+        collector.references
+    }
+
+    fn is_bound(&self, ident: &Identifier) -> bool {
+        self.bound.contains(ident)
+    }
+
+    fn add_reference(&mut self, expr: &Expression) {
+        if !self.references.contains(expr) {
+            self.references.push(expr.clone());
+        }
+    }
+
+    fn with_bound_vars<F>(&mut self, key_var: Option<&Identifier>, value_var: &Identifier, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let len = self.bound.len();
+        self.bound.extend(key_var.cloned());
+        self.bound.push(value_var.clone());
+        f(self);
+        self.bound.truncate(len);
+    }
+
+    fn visit_expr(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Null
+            | Expression::Bool(_)
+            | Expression::Number(_)
+            | Expression::String(_) => {}
+            Expression::Variable(var) => {
+                if !self.is_bound(var) {
+                    self.add_reference(expr);
+                }
+            }
+            Expression::Traversal(traversal) => {
+                match &traversal.expr {
+                    Expression::Variable(var) => {
+                        if !self.is_bound(var) {
+                            self.add_reference(expr);
+                        }
+                    }
+                    other => self.visit_expr(other),
+                }
+
+                for operator in &traversal.operators {
+                    if let TraversalOperator::Index(index) = operator {
+                        self.visit_expr(index);
+                    }
+                }
+            }
+            Expression::Array(array) => array.iter().for_each(|expr| self.visit_expr(expr)),
+            Expression::Object(object) => {
+                for (key, value) in object {
+                    if let ObjectKey::Expression(key) = key {
+                        self.visit_expr(key);
+                    }
+
+                    self.visit_expr(value);
+                }
+            }
+            Expression::Raw(raw) => {
+                // Raw expressions which fail to parse cannot reference any variables that would
+                // be resolvable during evaluation.
+                if let Ok(expr) = crate::parser::parse_expr(raw) {
+                    self.visit_expr(&expr);
+                }
+            }
+            Expression::TemplateExpr(expr) => {
+                // Templates which fail to parse cannot reference any variables that would be
+                // resolvable during evaluation.
+                if let Ok(template) = Template::from_expr(expr) {
+                    self.visit_template(&template);
+                }
+            }
+            Expression::FuncCall(func_call) => {
+                func_call.args.iter().for_each(|expr| self.visit_expr(expr));
+            }
+            Expression::Parenthesis(expr) => self.visit_expr(expr),
+            Expression::Conditional(cond) => {
+                self.visit_expr(&cond.cond_expr);
+                self.visit_expr(&cond.true_expr);
+                self.visit_expr(&cond.false_expr);
+            }
+            Expression::Operation(op) => match &**op {
+                Operation::Unary(unary) => self.visit_expr(&unary.expr),
+                Operation::Binary(binary) => {
+                    self.visit_expr(&binary.lhs_expr);
+                    self.visit_expr(&binary.rhs_expr);
+                }
+            },
+            Expression::ForExpr(for_expr) => {
+                // The collection is evaluated in the enclosing scope.
+                self.visit_expr(&for_expr.collection_expr);
+
+                self.with_bound_vars(
+                    for_expr.key_var.as_ref(),
+                    &for_expr.value_var,
+                    |collector| {
+                        if let Some(key_expr) = &for_expr.key_expr {
+                            collector.visit_expr(key_expr);
+                        }
+
+                        collector.visit_expr(&for_expr.value_expr);
+
+                        if let Some(cond_expr) = &for_expr.cond_expr {
+                            collector.visit_expr(cond_expr);
+                        }
+                    },
+                );
+            }
+        }
+    }
+
+    fn visit_template(&mut self, template: &Template) {
+        for element in template.elements() {
+            match element {
+                Element::Literal(_) => {}
+                Element::Interpolation(interp) => self.visit_expr(&interp.expr),
+                Element::Directive(Directive::If(dir)) => {
+                    self.visit_expr(&dir.cond_expr);
+                    self.visit_template(&dir.true_template);
+
+                    if let Some(false_template) = &dir.false_template {
+                        self.visit_template(false_template);
+                    }
+                }
+                Element::Directive(Directive::For(dir)) => {
+                    self.visit_expr(&dir.collection_expr);
+
+                    self.with_bound_vars(dir.key_var.as_ref(), &dir.value_var, |collector| {
+                        collector.visit_template(&dir.template);
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{Traversal, Variable};
+    use pretty_assertions::assert_eq;
+
+    fn references(input: &str) -> Vec<Expression> {
+        let parsed: crate::edit::expr::Expression = input.parse().unwrap();
+        Expression::from(parsed).referenced_variables()
+    }
+
+    fn var(name: &str) -> Expression {
+        Expression::from(Variable::unchecked(name))
+    }
+
+    fn traversal(name: &str, attr: &str) -> Expression {
+        Expression::from(Traversal::new(Variable::unchecked(name), [attr]))
+    }
+
+    #[test]
+    fn for_expr_scoping() {
+        assert_eq!(
+            references("[for x in var.list : x + var.offset]"),
+            [traversal("var", "list"), traversal("var", "offset")],
+        );
+
+        assert_eq!(
+            references("{for k, v in var.map : k => v.name if v.enabled && k != local.skip}"),
+            [traversal("var", "map"), traversal("local", "skip")],
+        );
+
+        // The loop variables are only bound within the for expression itself.
+        assert_eq!(
+            references("concat([for x in xs : x], [x])"),
+            [var("xs"), var("x")],
+        );
+
+        // Bound variables of the outer for expression are visible in nested ones.
+        assert_eq!(
+            references("[for x in var.list : [for y in x.items : y + x.offset + z]]"),
+            [traversal("var", "list"), var("z")],
+        );
+    }
+
+    #[test]
+    fn template_for_directive_scoping() {
+        assert_eq!(
+            references(r#""%{ for item in var.items }${item.name}${sep}%{ endfor }""#),
+            [traversal("var", "items"), var("sep")],
+        );
+    }
+
+    #[test]
+    fn index_expressions() {
+        let parsed: crate::edit::expr::Expression = "var.map[local.key]".parse().unwrap();
+        let expr = Expression::from(parsed);
+
+        assert_eq!(
+            expr.referenced_variables(),
+            [expr.clone(), traversal("local", "key")],
+        );
+
+        assert_eq!(
+            references("[for x in var.list : var.map[x]]"),
+            [
+                traversal("var", "list"),
+                references("var.map[x]")[0].clone()
+            ],
+        );
+    }
+}
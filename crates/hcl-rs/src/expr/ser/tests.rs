@@ -138,3 +138,19 @@ fn custom() {
     assert_expr("bar", Expression::String("bar".into()));
     assert_expr(["foo", "bar"], Expression::from_iter(["foo", "bar"]));
 }
+
+#[test]
+fn raw() {
+    let raw = Expression::Raw(RawExpression::new("var.foo"));
+    assert_expr(raw.clone(), raw.clone());
+    assert_eq!(
+        serde_json::to_value(&raw).unwrap(),
+        serde_json::json!("${var.foo}")
+    );
+
+    let invalid = Expression::Raw(RawExpression::new("not ( valid"));
+    assert_eq!(
+        serde_json::to_value(&invalid).unwrap(),
+        serde_json::json!("${not ( valid}")
+    );
+}
@@ -1,8 +1,47 @@
-use super::Expression;
-use crate::Identifier;
+use super::{Expression, Variable};
+use crate::{edit, format, Error, Identifier};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Traverse an expression to access attributes, object keys or element indices.
+///
+/// # Address strings
+///
+/// A `Traversal` can be parsed from a resource-address-style string like
+/// `aws_instance.web[0].id` via its [`FromStr`] implementation. The address must start with a
+/// variable name, followed by one or more of these operators:
+///
+/// - attribute access: `.name`
+/// - index access with a number or string literal: `[0]`, `["key"]`
+/// - legacy index access: `.0`
+/// - splat operators: `[*]` (full splat) and `.*` (attribute splat)
+///
+/// The [`Display`][fmt::Display] implementation produces the same form, so that parsing the
+/// formatted traversal yields the original traversal again.
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use hcl::expr::{Traversal, Variable};
+///
+/// let traversal: Traversal = r#"aws_instance.web["blue"].tags[*].id"#.parse()?;
+///
+/// let expected = Traversal::builder(Variable::new("aws_instance")?)
+///     .attr("web")
+///     .index("blue")
+///     .attr("tags")
+///     .full_splat()
+///     .attr("id")
+///     .build();
+///
+/// assert_eq!(traversal, expected);
+/// assert_eq!(traversal.to_string(), r#"aws_instance.web["blue"].tags[*].id"#);
+///
+/// // Index expressions other than number and string literals are not supported.
+/// assert!("aws_instance.web[var.index]".parse::<Traversal>().is_err());
+/// #     Ok(())
+/// # }
+/// ```
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Traversal {
     /// The expression that the access operator is applied to.
@@ -26,6 +65,28 @@ impl Traversal {
         }
     }
 
+    // Returns `true` if the traversed expression needs to be wrapped in parenthesis to preserve
+    // the structure of the traversal when it is formatted, e.g. `(a ? b : c).d`. Traversal
+    // operators bind tighter than any operator.
+    pub(crate) fn expr_needs_parens(&self) -> bool {
+        matches!(
+            self.expr,
+            Expression::Conditional(_) | Expression::Operation(_)
+        )
+    }
+
+    // Returns `true` if the traversal is rooted at a variable and only uses operators which are
+    // valid in address strings.
+    fn is_address(&self) -> bool {
+        matches!(self.expr, Expression::Variable(_))
+            && self.operators.iter().all(|operator| match operator {
+                TraversalOperator::Index(expr) => {
+                    matches!(expr, Expression::Number(_) | Expression::String(_))
+                }
+                _ => true,
+            })
+    }
+
     /// Create a new `TraversalBuilder` for the given expression.
     pub fn builder<T>(expr: T) -> TraversalBuilder
     where
@@ -38,9 +99,80 @@ impl Traversal {
     }
 }
 
+impl FromStr for Traversal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expr: edit::expr::Expression = s.parse()?;
+
+        match Expression::from(expr) {
+            Expression::Traversal(traversal) if traversal.is_address() => Ok(*traversal),
+            _ => Err(Error::new(format_args!(
+                "invalid traversal `{s}`: expected a variable followed by attribute, index or splat operators"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Traversal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Formatting a `Traversal` as string cannot fail.
+        let formatted = format::to_string(self).expect("a Traversal failed to format unexpectedly");
+        f.write_str(&formatted)
+    }
+}
+
+/// Create a new [`TraversalBuilder`] for a traversal rooted at the variable `root`.
+///
+/// This is a shorthand for `Traversal::builder(Variable::from(root.into()))`. Like all
+/// conversions into [`Identifier`], invalid characters in `root` are sanitized. The builder can
+/// be converted into an [`Expression`] directly via [`From`], without calling
+/// [`build`][TraversalBuilder::build] first.
+///
+/// # Example
+///
+/// ```
+/// use hcl::expr::{traversal, Expression, TraversalOperator};
+/// use hcl::Identifier;
+///
+/// let expr: Expression = traversal("aws_instance")
+///     .attr("web")
+///     .index(0)
+///     .index("blue")
+///     .attr("tags")
+///     .full_splat()
+///     .attr("id")
+///     .into();
+///
+/// let Expression::Traversal(traversal) = &expr else {
+///     panic!("expected a traversal");
+/// };
+///
+/// assert_eq!(
+///     traversal.operators,
+///     [
+///         TraversalOperator::GetAttr(Identifier::unchecked("web")),
+///         TraversalOperator::Index(Expression::from(0)),
+///         TraversalOperator::Index(Expression::from("blue")),
+///         TraversalOperator::GetAttr(Identifier::unchecked("tags")),
+///         TraversalOperator::FullSplat,
+///         TraversalOperator::GetAttr(Identifier::unchecked("id")),
+///     ]
+/// );
+///
+/// assert_eq!(expr.to_string(), r#"aws_instance.web[0]["blue"].tags[*].id"#);
+/// ```
+pub fn traversal<T>(root: T) -> TraversalBuilder
+where
+    T: Into<Identifier>,
+{
+    Traversal::builder(Variable::from(root.into()))
+}
+
 /// A builder for expression traversals.
 ///
-/// It is constructed via the [`builder`][Traversal::builder] method of the [`Traversal`] type.
+/// It is constructed via the [`builder`][Traversal::builder] method of the [`Traversal`] type or
+/// the [`traversal`] function.
 ///
 /// # Example
 ///
@@ -95,6 +227,12 @@ impl TraversalBuilder {
         self
     }
 
+    /// Add a [legacy index operator][TraversalOperator::LegacyIndex] to the traversal chain.
+    pub fn legacy_index(mut self, index: u64) -> Self {
+        self.operators.push(TraversalOperator::LegacyIndex(index));
+        self
+    }
+
     /// Consume `self` and return a `Traversal`.
     pub fn build(self) -> Traversal {
         Traversal {
@@ -104,6 +242,18 @@ impl TraversalBuilder {
     }
 }
 
+impl From<TraversalBuilder> for Traversal {
+    fn from(builder: TraversalBuilder) -> Self {
+        builder.build()
+    }
+}
+
+impl From<TraversalBuilder> for Expression {
+    fn from(builder: TraversalBuilder) -> Self {
+        Expression::from(builder.build())
+    }
+}
+
 /// The expression traversal operators that are supported by HCL.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub enum TraversalOperator {
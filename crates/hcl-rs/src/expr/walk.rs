@@ -0,0 +1,137 @@
+use super::{Expression, ObjectKey, Operation, TraversalOperator};
+
+/// Applies `f` to all sub-expressions of `expr` in post-order, followed by `expr` itself.
+pub(super) fn walk_mut(expr: &mut Expression, f: &mut dyn FnMut(&mut Expression)) {
+    match expr {
+        Expression::Null
+        | Expression::Bool(_)
+        | Expression::Number(_)
+        | Expression::String(_)
+        | Expression::Variable(_)
+        | Expression::TemplateExpr(_)
+        | Expression::Raw(_) => {}
+        Expression::Array(array) => array.iter_mut().for_each(|expr| walk_mut(expr, f)),
+        Expression::Object(object) => {
+            // Keys cannot be mutated in place, so the object needs to be rebuilt.
+            *object = std::mem::take(object)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    let key = match key {
+                        ObjectKey::Expression(mut key) => {
+                            walk_mut(&mut key, f);
+                            ObjectKey::Expression(key)
+                        }
+                        ident => ident,
+                    };
+
+                    walk_mut(&mut value, f);
+                    (key, value)
+                })
+                .collect();
+        }
+        Expression::Traversal(traversal) => {
+            walk_mut(&mut traversal.expr, f);
+
+            for operator in &mut traversal.operators {
+                if let TraversalOperator::Index(index) = operator {
+                    walk_mut(index, f);
+                }
+            }
+        }
+        Expression::FuncCall(func_call) => {
+            func_call.args.iter_mut().for_each(|expr| walk_mut(expr, f));
+        }
+        Expression::Parenthesis(expr) => walk_mut(expr, f),
+        Expression::Conditional(cond) => {
+            walk_mut(&mut cond.cond_expr, f);
+            walk_mut(&mut cond.true_expr, f);
+            walk_mut(&mut cond.false_expr, f);
+        }
+        Expression::Operation(op) => match &mut **op {
+            Operation::Unary(unary) => walk_mut(&mut unary.expr, f),
+            Operation::Binary(binary) => {
+                walk_mut(&mut binary.lhs_expr, f);
+                walk_mut(&mut binary.rhs_expr, f);
+            }
+        },
+        Expression::ForExpr(for_expr) => {
+            walk_mut(&mut for_expr.collection_expr, f);
+
+            if let Some(key_expr) = &mut for_expr.key_expr {
+                walk_mut(key_expr, f);
+            }
+
+            walk_mut(&mut for_expr.value_expr, f);
+
+            if let Some(cond_expr) = &mut for_expr.cond_expr {
+                walk_mut(cond_expr, f);
+            }
+        }
+    }
+
+    f(expr);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expr::{BinaryOp, BinaryOperator, Conditional, Expression, FuncCall, Operation};
+    use pretty_assertions::assert_eq;
+
+    fn parse(input: &str) -> Expression {
+        let parsed: crate::edit::expr::Expression = input.parse().unwrap();
+        Expression::from(parsed)
+    }
+
+    #[test]
+    fn negate_numbers() {
+        let mut expr = parse(
+            r#"{
+                a = [1, -2.5, "3"]
+                (4 + x) = cond ? max(5, var.list[6]) : { b = 7 }
+                c = [for v in [8] : v * 9 if v > 10]
+            }"#,
+        );
+
+        expr.walk_mut(|expr| {
+            if let Expression::Number(num) = expr {
+                *num = -*num;
+            }
+        });
+
+        let expected = parse(
+            r#"{
+                a = [-1, 2.5, "3"]
+                (-4 + x) = cond ? max(-5, var.list[-6]) : { b = -7 }
+                c = [for v in [-8] : v * -9 if v > -10]
+            }"#,
+        );
+
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn post_order() {
+        let mut expr = Expression::from(Conditional::new(
+            Expression::Bool(true),
+            FuncCall::builder("f").arg(1).build(),
+            Operation::from(BinaryOp::new(2, BinaryOperator::Plus, 3)),
+        ));
+
+        let mut visited = Vec::new();
+
+        expr.walk_mut(|expr| visited.push(expr.to_string()));
+
+        assert_eq!(
+            visited,
+            [
+                "true",
+                "1",
+                "f(1)",
+                "2",
+                "3",
+                "2 + 3",
+                "true ? f(1) : 2 + 3"
+            ]
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use super::{Format, Formatter};
+use crate::expr::{Expression, Object, ObjectKey, Operation, TraversalOperator};
+use crate::structure::{Body, Structure};
+use crate::{Map, Number, Result, Value};
+
+/// Data structures which have a canonical HCL representation: [`Body`], [`Expression`] and
+/// [`Value`].
+///
+/// This trait is sealed to prevent implementation outside of this crate. See [`canonicalize`]
+/// for the canonicalization rules.
+pub trait Canonicalize: Format + Clone {
+    #[doc(hidden)]
+    fn make_canonical(&mut self);
+}
+
+/// Format the given value as a canonical HCL string.
+///
+/// The canonical form is deterministic: two values which only differ in formatting, comments,
+/// the order of object keys or the order of attributes produce the same string. This makes it
+/// suitable for hashing configuration or for detecting semantic changes.
+///
+/// # Canonicalization rules
+///
+/// - Identifier object keys are converted to string keys, so `{ foo = 1 }` and `{ "foo" = 1 }`
+///   are canonically equal. If an object contains both an identifier key and a string key with
+///   the same content, only the entry which comes last is kept, which matches the result of
+///   evaluating the object.
+/// - Object keys are sorted lexicographically by their string value. Object keys which are not
+///   string literals are ordered by their formatted representation.
+/// - The attributes of a body are sorted by key and precede all blocks.
+/// - Blocks are ordered by identifier. Blocks sharing the same identifier keep their relative
+///   order, because the order of repeated blocks is significant.
+/// - Numbers are written in their shortest form: integral values never have a fractional part,
+///   e.g. `1.0` and `1e0` are both written as `1`.
+/// - Arrays and objects are written on a single line, there are no empty lines between
+///   structures, nested structures are indented by two spaces and lines are terminated by `\n`.
+/// - Object keys which are valid identifiers are written unquoted.
+///
+/// The rules are applied recursively to nested blocks and to all nested expressions. Expressions
+/// are not evaluated, and the contents of templates and heredocs are left as is.
+///
+/// # Errors
+///
+/// Formatting a value as canonical string cannot fail.
+///
+/// # Example
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let a = hcl::parse(r#"
+///     b = { z = 1.0, "a" = [1, 2] }
+///     a = "foo"
+/// "#)?;
+///
+/// let b = hcl::parse(r#"
+///     a = "foo" // Comments are not part of the canonical form.
+///
+///     b = {
+///       a = [
+///         1,
+///         2,
+///       ]
+///       z = 1
+///     }
+/// "#)?;
+///
+/// let canonical = hcl::format::canonicalize(&a)?;
+///
+/// assert_eq!(canonical, "a = \"foo\"\nb = { a = [1, 2], z = 1 }\n");
+/// assert_eq!(canonical, hcl::format::canonicalize(&b)?);
+/// #   Ok(())
+/// # }
+/// ```
+pub fn canonicalize<T>(value: &T) -> Result<String>
+where
+    T: Canonicalize,
+{
+    let mut value = value.clone();
+    value.make_canonical();
+
+    let mut formatter = Formatter::builder()
+        .dense(true)
+        .compact(true)
+        .prefer_ident_keys(true)
+        .build_vec();
+
+    value.format_string(&mut formatter)
+}
+
+impl Canonicalize for Body {
+    fn make_canonical(&mut self) {
+        for structure in &mut self.0 {
+            match structure {
+                Structure::Attribute(attr) => attr.expr.make_canonical(),
+                Structure::Block(block) => block.body.make_canonical(),
+            }
+        }
+
+        self.0.sort_by(|a, b| match (a, b) {
+            (Structure::Attribute(a), Structure::Attribute(b)) => a.key().cmp(b.key()),
+            (Structure::Attribute(_), Structure::Block(_)) => std::cmp::Ordering::Less,
+            (Structure::Block(_), Structure::Attribute(_)) => std::cmp::Ordering::Greater,
+            (Structure::Block(a), Structure::Block(b)) => a.identifier().cmp(b.identifier()),
+        });
+    }
+}
+
+impl Canonicalize for Value {
+    fn make_canonical(&mut self) {
+        match self {
+            Value::Number(num) => *num = canonical_number(*num),
+            Value::Array(array) => array.iter_mut().for_each(Canonicalize::make_canonical),
+            Value::Object(object) => canonicalize_map(object),
+            Value::Null | Value::Bool(_) | Value::String(_) => {}
+        }
+    }
+}
+
+impl Canonicalize for Expression {
+    fn make_canonical(&mut self) {
+        match self {
+            Expression::Number(num) => *num = canonical_number(*num),
+            Expression::Array(array) => array.iter_mut().for_each(Canonicalize::make_canonical),
+            Expression::Object(object) => canonicalize_object(object),
+            Expression::Traversal(traversal) => {
+                traversal.expr.make_canonical();
+
+                for operator in &mut traversal.operators {
+                    if let TraversalOperator::Index(index) = operator {
+                        index.make_canonical();
+                    }
+                }
+            }
+            Expression::FuncCall(func_call) => {
+                func_call
+                    .args
+                    .iter_mut()
+                    .for_each(Canonicalize::make_canonical);
+            }
+            Expression::Parenthesis(expr) => expr.make_canonical(),
+            Expression::Conditional(cond) => {
+                cond.cond_expr.make_canonical();
+                cond.true_expr.make_canonical();
+                cond.false_expr.make_canonical();
+            }
+            Expression::Operation(op) => match &mut **op {
+                Operation::Unary(unary) => unary.expr.make_canonical(),
+                Operation::Binary(binary) => {
+                    binary.lhs_expr.make_canonical();
+                    binary.rhs_expr.make_canonical();
+                }
+            },
+            Expression::ForExpr(for_expr) => {
+                for_expr.collection_expr.make_canonical();
+                for_expr.value_expr.make_canonical();
+
+                if let Some(key_expr) = &mut for_expr.key_expr {
+                    key_expr.make_canonical();
+                }
+
+                if let Some(cond_expr) = &mut for_expr.cond_expr {
+                    cond_expr.make_canonical();
+                }
+            }
+            Expression::Null
+            | Expression::Bool(_)
+            | Expression::String(_)
+            | Expression::Variable(_)
+            | Expression::TemplateExpr(_)
+            | Expression::Raw(_) => {}
+        }
+    }
+}
+
+// Integral floats are normally converted to integers when a `Number` is created. This guards
+// against floats which did not pass through that conversion.
+fn canonical_number(num: Number) -> Number {
+    num.as_f64()
+        .filter(|_| num.is_f64())
+        .and_then(Number::from_f64)
+        .unwrap_or(num)
+}
+
+fn canonicalize_map(map: &mut Map<String, Value>) {
+    map.values_mut().for_each(Canonicalize::make_canonical);
+    map.sort_keys();
+}
+
+fn canonicalize_object(object: &mut Object<ObjectKey, Expression>) {
+    let entries = std::mem::take(object);
+
+    // Collecting into the object replaces the value of a key which is already present, so if an
+    // identifier key and a string key collide, the entry which comes last wins.
+    *object = entries
+        .into_iter()
+        .map(|(key, mut value)| {
+            let key = match key {
+                ObjectKey::Identifier(ident) => {
+                    ObjectKey::Expression(Expression::String(ident.into_inner()))
+                }
+                ObjectKey::Expression(mut expr) => {
+                    expr.make_canonical();
+                    ObjectKey::Expression(expr)
+                }
+            };
+
+            value.make_canonical();
+            (key, value)
+        })
+        .collect();
+
+    object.sort_by_cached_key(|key, _| object_key_sort_key(key));
+}
+
+fn object_key_sort_key(key: &ObjectKey) -> String {
+    match key {
+        ObjectKey::Identifier(ident) => ident.to_string(),
+        ObjectKey::Expression(Expression::String(string)) => string.clone(),
+        ObjectKey::Expression(expr) => super::to_string(expr).unwrap_or_default(),
+    }
+}
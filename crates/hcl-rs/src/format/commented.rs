@@ -0,0 +1,344 @@
+use super::private;
+use crate::{Attribute, Block, Structure};
+
+/// Wraps an HCL [`Attribute`][crate::Attribute], [`Block`][crate::Block] or
+/// [`Structure`][crate::Structure] together with comments which are emitted by the
+/// [`Formatter`][super::Formatter].
+///
+/// # Comment placement
+///
+/// - Leading comments are written on their own lines directly before the structure and are
+///   indented like the structure itself. Every line of a leading comment is prefixed with `# `,
+///   empty lines within a comment are rendered as a lone `#`.
+/// - A trailing comment is written on the same line as the end of the structure, separated by a
+///   single space: after the value for attributes and after the closing brace for blocks.
+///   Trailing comments must not contain line breaks, formatting fails otherwise.
+/// - Empty lines which the formatter inserts between attributes and blocks are placed before the
+///   leading comments, so that comments stay attached to the structure they describe.
+///
+/// # Nested structures
+///
+/// The structures within the body of a plain [`Block`] cannot carry comments. To comment
+/// attributes and blocks at any nesting level, add them to a `Commented<Block>` via
+/// [`Commented::with_structure`], or attach comments to structures which are already part of the
+/// block's body via [`Commented::map_structure`]. Structures added via `with_structure` become
+/// part of the wrapped block's body. The comments stay attached to their structures if the
+/// formatter reorders them.
+///
+/// # Example
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use hcl::format::{Commented, Format, Formatter};
+/// use hcl::{Attribute, Block};
+///
+/// let mut formatter = Formatter::default();
+///
+/// let attr = Commented::new(Attribute::new("region", "eu-west-1"))
+///     .with_leading_comment("The AWS region.")
+///     .with_trailing_comment("Must support S3.");
+///
+/// let block = Commented::new(Block::builder("backend").add_label("s3").build())
+///     .with_leading_comment("Remote state.\n\nManaged by the platform team.")
+///     .with_trailing_comment("backend");
+///
+/// let mut formatted = attr.format_string(&mut formatter)?;
+/// formatted.push_str(&block.format_string(&mut formatter)?);
+///
+/// let expected = r#"
+/// ## The AWS region.
+/// region = "eu-west-1" # Must support S3.
+///
+/// ## Remote state.
+/// ##
+/// ## Managed by the platform team.
+/// backend "s3" {} # backend
+/// "#.trim_start();
+///
+/// assert_eq!(formatted, expected);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commented<T: Commentable> {
+    value: T,
+    leading: Vec<String>,
+    trailing: Option<String>,
+    nested: T::Nested,
+}
+
+/// Data structures which can be wrapped in [`Commented`]: [`Attribute`], [`Block`] and
+/// [`Structure`].
+///
+/// This trait is sealed to prevent implementation outside of this crate.
+pub trait Commentable: private::Sealed {
+    #[doc(hidden)]
+    type Nested: std::fmt::Debug + Clone + PartialEq + Eq + Default;
+}
+
+impl Commentable for Attribute {
+    type Nested = ();
+}
+
+impl Commentable for Block {
+    type Nested = BodyComments;
+}
+
+impl Commentable for Structure {
+    type Nested = BodyComments;
+}
+
+// The comments of the structures within a block body. The comments at index `i` belong to the
+// structure at index `i` of the body. Structures without a corresponding entry have no comments.
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BodyComments(Vec<StructureComments>);
+
+impl BodyComments {
+    pub(super) fn get(&self, index: usize) -> Option<&StructureComments> {
+        self.0.get(index)
+    }
+
+    fn set(&mut self, index: usize, comments: StructureComments) {
+        if index >= self.0.len() {
+            self.0.resize_with(index + 1, StructureComments::default);
+        }
+
+        self.0[index] = comments;
+    }
+
+    fn take(&mut self, index: usize) -> StructureComments {
+        self.0
+            .get_mut(index)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+// The comments of a single structure within a block body.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) struct StructureComments {
+    pub(super) leading: Vec<String>,
+    pub(super) trailing: Option<String>,
+    pub(super) nested: BodyComments,
+}
+
+impl<T: Commentable> Commented<T> {
+    /// Creates a new `Commented` value without any comments.
+    pub fn new(value: T) -> Commented<T> {
+        Commented {
+            value,
+            leading: Vec::new(),
+            trailing: None,
+            nested: T::Nested::default(),
+        }
+    }
+
+    /// Adds a leading comment. The comment may span multiple lines.
+    ///
+    /// Leading comments are emitted in the order in which they were added.
+    pub fn with_leading_comment<C>(mut self, comment: C) -> Commented<T>
+    where
+        C: Into<String>,
+    {
+        self.leading.push(comment.into());
+        self
+    }
+
+    /// Sets the trailing comment, replacing any previously set trailing comment.
+    pub fn with_trailing_comment<C>(mut self, comment: C) -> Commented<T>
+    where
+        C: Into<String>,
+    {
+        self.trailing = Some(comment.into());
+        self
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes `self` and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the leading comments.
+    pub fn leading_comments(&self) -> &[String] {
+        &self.leading
+    }
+
+    /// Returns the trailing comment, if any.
+    pub fn trailing_comment(&self) -> Option<&str> {
+        self.trailing.as_deref()
+    }
+}
+
+impl Commented<Block> {
+    /// Appends a structure which may carry comments itself to the body of the wrapped block.
+    ///
+    /// See the [type level documentation](Commented#nested-structures) for more.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl::format::Commented;
+    /// use hcl::{Attribute, Block};
+    ///
+    /// let block = Commented::new(Block::builder("resource").add_label("bucket").build())
+    ///     .with_leading_comment("The bucket.")
+    ///     .with_structure(
+    ///         Commented::new(Attribute::new("name", "logs")).with_trailing_comment("globally unique"),
+    ///     )
+    ///     .with_structure(
+    ///         Commented::new(Block::builder("versioning").build())
+    ///             .with_leading_comment("Versioning settings.")
+    ///             .with_structure(
+    ///                 Commented::new(Attribute::new("enabled", true))
+    ///                     .with_leading_comment("Required for replication."),
+    ///             ),
+    ///     );
+    ///
+    /// let expected = r#"
+    /// ## The bucket.
+    /// resource "bucket" {
+    ///   name = "logs" # globally unique
+    ///
+    ///   ## Versioning settings.
+    ///   versioning {
+    ///     ## Required for replication.
+    ///     enabled = true
+    ///   }
+    /// }
+    /// "#.trim_start();
+    ///
+    /// assert_eq!(hcl::format::to_string(&block)?, expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn with_structure<S>(mut self, structure: S) -> Commented<Block>
+    where
+        S: Into<Commented<Structure>>,
+    {
+        let structure = structure.into();
+        let index = self.value.body.len();
+        self.value.body.0.push(structure.value);
+        self.nested.set(
+            index,
+            StructureComments {
+                leading: structure.leading,
+                trailing: structure.trailing,
+                nested: structure.nested,
+            },
+        );
+        self
+    }
+
+    /// Replaces the structure at `index` in the body of the wrapped block with the result of
+    /// `f`.
+    ///
+    /// `f` receives the structure together with the comments attached to it so far. This allows
+    /// to attach comments to structures which are already part of the block's body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl::format::Commented;
+    /// use hcl::Block;
+    ///
+    /// let block = Block::builder("locals")
+    ///     .add_attribute(("region", "eu-west-1"))
+    ///     .add_attribute(("zone", "a"))
+    ///     .build();
+    ///
+    /// let block = Commented::new(block)
+    ///     .map_structure(0, |region| region.with_trailing_comment("The AWS region."));
+    ///
+    /// let expected = r#"
+    /// locals {
+    ///   region = "eu-west-1" # The AWS region.
+    ///   zone = "a"
+    /// }
+    /// "#.trim_start();
+    ///
+    /// assert_eq!(hcl::format::to_string(&block)?, expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn map_structure<F>(mut self, index: usize, f: F) -> Commented<Block>
+    where
+        F: FnOnce(Commented<Structure>) -> Commented<Structure>,
+    {
+        let value = self.value.body.0.remove(index);
+        let comments = self.nested.take(index);
+
+        let structure = f(Commented {
+            value,
+            leading: comments.leading,
+            trailing: comments.trailing,
+            nested: comments.nested,
+        });
+
+        self.value.body.0.insert(index, structure.value);
+        self.nested.set(
+            index,
+            StructureComments {
+                leading: structure.leading,
+                trailing: structure.trailing,
+                nested: structure.nested,
+            },
+        );
+        self
+    }
+
+    pub(super) fn nested(&self) -> &BodyComments {
+        &self.nested
+    }
+}
+
+impl Commented<Structure> {
+    pub(super) fn nested(&self) -> &BodyComments {
+        &self.nested
+    }
+}
+
+impl From<Commented<Attribute>> for Commented<Structure> {
+    fn from(commented: Commented<Attribute>) -> Self {
+        Commented {
+            value: Structure::Attribute(commented.value),
+            leading: commented.leading,
+            trailing: commented.trailing,
+            nested: BodyComments::default(),
+        }
+    }
+}
+
+impl From<Commented<Block>> for Commented<Structure> {
+    fn from(commented: Commented<Block>) -> Self {
+        Commented {
+            value: Structure::Block(commented.value),
+            leading: commented.leading,
+            trailing: commented.trailing,
+            nested: commented.nested,
+        }
+    }
+}
+
+impl<T: Commentable> From<T> for Commented<T> {
+    fn from(value: T) -> Self {
+        Commented::new(value)
+    }
+}
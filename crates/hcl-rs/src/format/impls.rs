@@ -1,7 +1,9 @@
-use super::{private, Format, Formatter};
+use super::commented::{BodyComments, StructureComments};
+use super::{private, Commented, Format, FormatState, Formatter, FormatterBuilder, StructureOrder};
 use crate::expr::{
     BinaryOp, Conditional, Expression, ForExpr, FuncCall, FuncName, Heredoc, HeredocStripMode,
-    ObjectKey, Operation, TemplateExpr, Traversal, TraversalOperator, UnaryOp, Variable,
+    ObjectKey, Operation, RawExpression, TemplateExpr, Traversal, TraversalOperator, UnaryOp,
+    Variable,
 };
 use crate::structure::{Attribute, Block, BlockLabel, Body, Structure};
 use crate::template::{
@@ -12,6 +14,7 @@ use crate::{Identifier, Number, Result, Value};
 use hcl_primitives::ident::is_ident;
 use hcl_primitives::template::escape_markers;
 use std::io;
+use std::str::FromStr;
 
 impl<T> private::Sealed for &T where T: Format {}
 
@@ -34,11 +37,71 @@ impl Format for Body {
     where
         W: io::Write,
     {
-        for structure in self {
-            structure.format(fmt)?;
+        format_body(fmt, self, &BodyComments::default())
+    }
+}
+
+// Formats the structures of `body`, each together with its comments from `comments`.
+fn format_body<W>(fmt: &mut Formatter<W>, body: &Body, comments: &BodyComments) -> Result<()>
+where
+    W: io::Write,
+{
+    if fmt.config.order == StructureOrder::Preserve && !fmt.config.sort_attributes {
+        for (index, structure) in body.iter().enumerate() {
+            format_structure(fmt, structure, comments.get(index))?;
         }
 
-        Ok(())
+        return Ok(());
+    }
+
+    let mut structures: Vec<(&Structure, Option<&StructureComments>)> = match fmt.config.order {
+        StructureOrder::Preserve => body
+            .iter()
+            .enumerate()
+            .map(|(index, structure)| (structure, comments.get(index)))
+            .collect(),
+        order => {
+            let attributes_first = order == StructureOrder::AttributesFirst;
+            let (first, second): (Vec<_>, Vec<_>) = body
+                .iter()
+                .enumerate()
+                .map(|(index, structure)| (structure, comments.get(index)))
+                .partition(|(structure, _)| structure.is_attribute() == attributes_first);
+            first.into_iter().chain(second).collect()
+        }
+    };
+
+    if fmt.config.sort_attributes {
+        // Only consecutive attributes are sorted, blocks keep their position.
+        for attributes in structures.split_mut(|(structure, _)| structure.is_block()) {
+            attributes.sort_by_key(|(structure, _)| structure.as_attribute().map(Attribute::key));
+        }
+    }
+
+    for (structure, comments) in structures {
+        format_structure(fmt, structure, comments)?;
+    }
+
+    Ok(())
+}
+
+fn format_structure<W>(
+    fmt: &mut Formatter<W>,
+    structure: &Structure,
+    comments: Option<&StructureComments>,
+) -> Result<()>
+where
+    W: io::Write,
+{
+    match comments {
+        Some(comments) => format_commented_structure(
+            fmt,
+            structure,
+            &comments.leading,
+            comments.trailing.as_deref(),
+            &comments.nested,
+        ),
+        None => structure.format(fmt),
     }
 }
 
@@ -63,11 +126,7 @@ impl Format for Attribute {
     where
         W: io::Write,
     {
-        fmt.begin_attribute()?;
-        self.key.format(fmt)?;
-        fmt.begin_attribute_value()?;
-        self.expr.format(fmt)?;
-        fmt.end_attribute()
+        format_attribute(fmt, self, None)
     }
 }
 
@@ -78,17 +137,115 @@ impl Format for Block {
     where
         W: io::Write,
     {
-        fmt.begin_block()?;
-        self.identifier.format(fmt)?;
+        format_block(fmt, self, &BodyComments::default(), None)
+    }
+}
 
-        for label in &self.labels {
-            fmt.write_bytes(b" ")?;
-            label.format(fmt)?;
-        }
+fn format_attribute<W>(
+    fmt: &mut Formatter<W>,
+    attr: &Attribute,
+    trailing_comment: Option<&str>,
+) -> Result<()>
+where
+    W: io::Write,
+{
+    fmt.begin_attribute()?;
+
+    // Attribute keys parsed with quoted attribute keys enabled may not be valid identifiers.
+    // Quote them to avoid emitting invalid HCL.
+    if is_ident(&attr.key) {
+        attr.key.format(fmt)?;
+    } else {
+        fmt.write_quoted_string_escaped(&attr.key)?;
+    }
+
+    fmt.begin_attribute_value()?;
+    attr.expr.format(fmt)?;
+    fmt.end_attribute(trailing_comment)
+}
+
+fn format_block<W>(
+    fmt: &mut Formatter<W>,
+    block: &Block,
+    comments: &BodyComments,
+    trailing_comment: Option<&str>,
+) -> Result<()>
+where
+    W: io::Write,
+{
+    fmt.begin_block()?;
+    block.identifier.format(fmt)?;
+
+    for label in &block.labels {
+        fmt.write_bytes(b" ")?;
+        label.format(fmt)?;
+    }
+
+    fmt.begin_block_body()?;
+    format_body(fmt, &block.body, comments)?;
+    fmt.end_block(trailing_comment)
+}
+
+impl private::Sealed for Commented<Attribute> {}
+
+impl Format for Commented<Attribute> {
+    fn format<W>(&self, fmt: &mut Formatter<W>) -> Result<()>
+    where
+        W: io::Write,
+    {
+        fmt.write_leading_comments(self.leading_comments(), FormatState::AttributeStart)?;
+        format_attribute(fmt, self.value(), self.trailing_comment())
+    }
+}
+
+impl private::Sealed for Commented<Block> {}
+
+impl Format for Commented<Block> {
+    fn format<W>(&self, fmt: &mut Formatter<W>) -> Result<()>
+    where
+        W: io::Write,
+    {
+        fmt.write_leading_comments(self.leading_comments(), FormatState::BlockStart)?;
+        format_block(fmt, self.value(), self.nested(), self.trailing_comment())
+    }
+}
+
+impl private::Sealed for Commented<Structure> {}
+
+impl Format for Commented<Structure> {
+    fn format<W>(&self, fmt: &mut Formatter<W>) -> Result<()>
+    where
+        W: io::Write,
+    {
+        format_commented_structure(
+            fmt,
+            self.value(),
+            self.leading_comments(),
+            self.trailing_comment(),
+            self.nested(),
+        )
+    }
+}
 
-        fmt.begin_block_body()?;
-        self.body.format(fmt)?;
-        fmt.end_block()
+fn format_commented_structure<W>(
+    fmt: &mut Formatter<W>,
+    structure: &Structure,
+    leading_comments: &[String],
+    trailing_comment: Option<&str>,
+    nested: &BodyComments,
+) -> Result<()>
+where
+    W: io::Write,
+{
+    match structure {
+        Structure::Attribute(attr) => {
+            fmt.write_leading_comments(leading_comments, FormatState::AttributeStart)?;
+            format_attribute(fmt, attr, trailing_comment)
+        }
+        Structure::Block(block) => {
+            fmt.write_leading_comments(leading_comments, FormatState::BlockStart)?;
+            format_block(fmt, block, nested, trailing_comment)
+        }
     }
 }
 
@@ -118,20 +275,20 @@ impl Format for Expression {
             Expression::Bool(b) => Ok(fmt.write_bool(*b)?),
             Expression::Number(num) => num.format(fmt),
             Expression::String(string) => string.format(fmt),
+            Expression::Array(array) if array.iter().all(is_scalar_expr) => {
+                format_scalar_array(fmt, array)
+            }
             Expression::Array(array) => format_array(fmt, array.iter()),
             Expression::Object(object) => format_object(fmt, object.iter()),
             Expression::TemplateExpr(expr) => expr.format(fmt),
             Expression::Variable(var) => var.format(fmt),
             Expression::Traversal(traversal) => traversal.format(fmt),
             Expression::FuncCall(func_call) => func_call.format(fmt),
-            Expression::Parenthesis(expr) => {
-                fmt.write_bytes(b"(")?;
-                expr.format(fmt)?;
-                fmt.write_bytes(b")")
-            }
+            Expression::Parenthesis(expr) => format_operand(fmt, expr, true),
             Expression::Conditional(cond) => cond.format(fmt),
             Expression::Operation(op) => op.format(fmt),
             Expression::ForExpr(expr) => expr.format(fmt),
+            Expression::Raw(raw) => raw.format(fmt),
         }
     }
 }
@@ -154,6 +311,9 @@ impl Format for Value {
                     fmt.write_quoted_string_escaped(string)
                 }
             }
+            Value::Array(array) if array.iter().all(is_scalar_value) => {
+                format_scalar_array(fmt, array)
+            }
             Value::Array(array) => format_array(fmt, array.iter()),
             Value::Object(object) => format_object(fmt, object.iter().map(|(k, v)| (StrKey(k), v))),
         }
@@ -167,7 +327,7 @@ impl Format for Number {
     where
         W: io::Write,
     {
-        fmt.write_string_fragment(&self.to_string())
+        fmt.write_number(self)
     }
 }
 
@@ -211,7 +371,10 @@ impl Format for TemplateExpr {
         W: io::Write,
     {
         match self {
-            TemplateExpr::QuotedString(string) => fmt.write_quoted_string(string),
+            TemplateExpr::QuotedString(string) => match respace_template(fmt, string) {
+                Some(respaced) => fmt.write_quoted_string(&respaced),
+                None => fmt.write_quoted_string(string),
+            },
             TemplateExpr::Heredoc(heredoc) => heredoc.format(fmt),
         }
     }
@@ -226,11 +389,15 @@ impl Format for Heredoc {
     {
         fmt.write_string_fragment(self.strip.as_str())?;
         fmt.write_string_fragment(&self.delimiter)?;
-        fmt.write_bytes(b"\n")?;
-        fmt.write_string_fragment(&self.template)?;
+        fmt.write_newline()?;
+
+        match respace_template(fmt, &self.template) {
+            Some(respaced) => fmt.write_multiline_fragment(&respaced)?,
+            None => fmt.write_multiline_fragment(&self.template)?,
+        }
 
         if !self.template.ends_with('\n') {
-            fmt.write_bytes(b"\n")?;
+            fmt.write_newline()?;
         }
 
         match self.strip {
@@ -262,6 +429,17 @@ impl Format for Variable {
     }
 }
 
+impl private::Sealed for RawExpression {}
+
+impl Format for RawExpression {
+    fn format<W>(&self, fmt: &mut Formatter<W>) -> Result<()>
+    where
+        W: io::Write,
+    {
+        fmt.write_raw(self)
+    }
+}
+
 impl private::Sealed for Traversal {}
 
 impl Format for Traversal {
@@ -269,7 +447,7 @@ impl Format for Traversal {
     where
         W: io::Write,
     {
-        self.expr.format(fmt)?;
+        format_operand(fmt, &self.expr, self.expr_needs_parens())?;
         for operator in &self.operators {
             operator.format(fmt)?;
         }
@@ -358,7 +536,7 @@ impl Format for Conditional {
         W: io::Write,
     {
         fmt.with_compact_mode(|fmt| {
-            self.cond_expr.format(fmt)?;
+            format_operand(fmt, &self.cond_expr, self.cond_needs_parens())?;
             fmt.write_bytes(b" ? ")?;
             self.true_expr.format(fmt)?;
             fmt.write_bytes(b" : ")?;
@@ -389,7 +567,7 @@ impl Format for UnaryOp {
         W: io::Write,
     {
         fmt.write_string_fragment(self.operator.as_str())?;
-        self.expr.format(fmt)
+        format_operand(fmt, &self.expr, self.operand_needs_parens())
     }
 }
 
@@ -400,11 +578,11 @@ impl Format for BinaryOp {
     where
         W: io::Write,
     {
-        self.lhs_expr.format(fmt)?;
+        format_operand(fmt, &self.lhs_expr, self.lhs_needs_parens())?;
         fmt.write_bytes(b" ")?;
         fmt.write_string_fragment(self.operator.as_str())?;
         fmt.write_bytes(b" ")?;
-        self.rhs_expr.format(fmt)
+        format_operand(fmt, &self.rhs_expr, self.rhs_needs_parens())
     }
 }
 
@@ -586,6 +764,74 @@ where
     fmt.end_array()
 }
 
+// Fast path for arrays which only contain null, boolean and number values and thus never need to
+// adjust the formatter state while formatting their elements. The element separator is computed
+// once up front instead of going through `begin_array_value`/`end_array_value` for every element.
+//
+// The output is identical to the output of `format_array`.
+fn format_scalar_array<W, T>(fmt: &mut Formatter<W>, array: &[T]) -> Result<()>
+where
+    W: io::Write,
+    T: Format,
+{
+    let Some((first, rest)) = array.split_first() else {
+        return format_array(fmt, array.iter());
+    };
+
+    let compact = fmt.compact_arrays();
+    let mut separator = Vec::new();
+
+    if compact {
+        separator.extend_from_slice(b", ");
+    } else {
+        separator.extend_from_slice(fmt.config.newline.as_bytes());
+
+        for _ in 0..=fmt.current_indent {
+            separator.extend_from_slice(fmt.config.indent);
+        }
+    }
+
+    fmt.write_bytes(b"[")?;
+
+    if !compact {
+        // Skip the leading comma.
+        fmt.write_bytes(&separator)?;
+    }
+
+    first.format(fmt)?;
+
+    for value in rest {
+        if !compact {
+            fmt.write_bytes(b",")?;
+        }
+
+        fmt.write_bytes(&separator)?;
+        value.format(fmt)?;
+    }
+
+    if !compact {
+        fmt.write_newline()?;
+        fmt.write_indent(fmt.current_indent)?;
+    }
+
+    fmt.has_value = true;
+    fmt.first_element = false;
+    fmt.write_bytes(b"]")
+}
+
+// Strings are deliberately excluded: their formatting cost is dominated by escaping, so they
+// do not benefit from the fast path.
+fn is_scalar_expr(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::Null | Expression::Bool(_) | Expression::Number(_)
+    )
+}
+
+fn is_scalar_value(value: &Value) -> bool {
+    matches!(value, Value::Null | Value::Bool(_) | Value::Number(_))
+}
+
 fn format_object<W, K, V>(
     fmt: &mut Formatter<W>,
     object: impl Iterator<Item = (K, V)>,
@@ -608,6 +854,19 @@ where
     fmt.end_object()
 }
 
+fn format_operand<W>(fmt: &mut Formatter<W>, expr: &Expression, parens: bool) -> Result<()>
+where
+    W: io::Write,
+{
+    if parens {
+        fmt.write_bytes(b"(")?;
+        expr.format(fmt)?;
+        fmt.write_bytes(b")")
+    } else {
+        expr.format(fmt)
+    }
+}
+
 fn format_strip<W, F>(fmt: &mut Formatter<W>, strip: Strip, f: F) -> Result<()>
 where
     W: io::Write,
@@ -631,11 +890,44 @@ where
     W: io::Write,
     F: FnOnce(&mut Formatter<W>) -> Result<()>,
 {
+    let space = fmt.config.space_interpolations;
+
     fmt.write_bytes(b"${")?;
-    format_strip(fmt, strip, f)?;
+    format_strip(fmt, strip, |fmt| {
+        if space {
+            fmt.write_bytes(b" ")?;
+            f(fmt)?;
+            fmt.write_bytes(b" ")
+        } else {
+            f(fmt)
+        }
+    })?;
     fmt.write_bytes(b"}")
 }
 
+// Template strings of parsed quoted strings and heredocs are usually written verbatim. If
+// `space_interpolations` is enabled, the string is parsed into a `Template` instead and formatted
+// to adjust the whitespace within its interpolation sequences.
+//
+// Returns `None` if the option is disabled or the string is not a valid template, in which case
+// the caller should fall back to writing the original string.
+fn respace_template<W>(fmt: &Formatter<W>, s: &str) -> Option<String>
+where
+    W: io::Write,
+{
+    if !fmt.config.space_interpolations || !is_templated(s) {
+        return None;
+    }
+
+    let template = Template::from_str(s).ok()?;
+    let mut formatter = FormatterBuilder {
+        config: fmt.config.clone(),
+    }
+    .build_vec();
+
+    template.format_string(&mut formatter).ok()
+}
+
 fn format_directive<W, F>(fmt: &mut Formatter<W>, strip: Strip, f: F) -> Result<()>
 where
     W: io::Write,
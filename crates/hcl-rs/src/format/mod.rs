@@ -34,11 +34,15 @@
 //! # }
 //! ```
 
+mod canonical;
+mod commented;
 mod escape;
 mod impls;
 
+pub use self::canonical::{canonicalize, Canonicalize};
+pub use self::commented::{Commentable, Commented};
 use self::escape::{CharEscape, ESCAPE};
-use crate::Result;
+use crate::{Error, Number, Result};
 use hcl_primitives::template::escape_markers;
 use std::io;
 
@@ -99,22 +103,73 @@ enum FormatState {
     BlockBodyStart,
 }
 
+/// The newline style used by the [`Formatter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Newline {
+    /// Line feed (`\n`), the default.
+    #[default]
+    Lf,
+    /// Carriage return followed by a line feed (`\r\n`).
+    Crlf,
+}
+
+impl Newline {
+    /// Returns the newline as a byte slice.
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Newline::Lf => b"\n",
+            Newline::Crlf => b"\r\n",
+        }
+    }
+}
+
+/// Controls the order in which the [`Formatter`] emits the attributes and blocks of a body.
+///
+/// Moving attributes relative to blocks does not change the meaning of a body. The relative
+/// order of the attributes and the relative order of the blocks are always preserved, because
+/// the order of repeated blocks may be significant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StructureOrder {
+    /// Emit attributes and blocks in their original order, the default.
+    #[default]
+    Preserve,
+    /// Emit all attributes before the blocks.
+    AttributesFirst,
+    /// Emit all blocks before the attributes.
+    BlocksFirst,
+}
+
+#[derive(Clone)]
 struct FormatConfig<'a> {
     indent: &'a [u8],
+    newline: Newline,
     dense: bool,
     compact_arrays: bool,
     compact_objects: bool,
     prefer_ident_keys: bool,
+    space_interpolations: bool,
+    order: StructureOrder,
+    sort_attributes: bool,
+    true_literal: &'a str,
+    false_literal: &'a str,
+    null_literal: &'a str,
 }
 
 impl<'a> Default for FormatConfig<'a> {
     fn default() -> Self {
         FormatConfig {
             indent: b"  ",
+            newline: Newline::Lf,
             dense: false,
             compact_arrays: false,
             compact_objects: false,
             prefer_ident_keys: false,
+            space_interpolations: false,
+            order: StructureOrder::Preserve,
+            sort_attributes: false,
+            true_literal: "true",
+            false_literal: "false",
+            null_literal: "null",
         }
     }
 }
@@ -198,6 +253,33 @@ impl<'a> FormatterBuilder<'a> {
         self
     }
 
+    /// Set the newline style used for line breaks in the output.
+    ///
+    /// The default is [`Newline::Lf`]. Line breaks within heredoc templates are normalized to the
+    /// configured newline style as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::format::{Format, Formatter, Newline};
+    /// use hcl::structure::Block;
+    ///
+    /// let mut formatter = Formatter::builder()
+    ///     .newline(Newline::Crlf)
+    ///     .build_vec();
+    ///
+    /// let block = Block::builder("block").add_attribute(("foo", 1)).build();
+    ///
+    /// assert_eq!(block.format_string(&mut formatter)?, "block {\r\n  foo = 1\r\n}\r\n");
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.config.newline = newline;
+        self
+    }
+
     /// If set, blocks are not visually separated by empty lines from attributes and adjacent
     /// blocks.
     ///
@@ -250,6 +332,8 @@ impl<'a> FormatterBuilder<'a> {
     /// ```hcl
     /// array = [1, 2, 3]
     /// ```
+    ///
+    /// Empty arrays are always formatted as `[]`, regardless of this setting.
     pub fn compact_arrays(mut self, yes: bool) -> Self {
         self.config.compact_arrays = yes;
         self
@@ -272,6 +356,8 @@ impl<'a> FormatterBuilder<'a> {
     /// ```hcl
     /// object = { one = "foo", two = "bar", three = "baz" }
     /// ```
+    ///
+    /// Empty objects are always formatted as `{}`, regardless of this setting.
     pub fn compact_objects(mut self, yes: bool) -> Self {
         self.config.compact_objects = yes;
         self
@@ -305,6 +391,173 @@ impl<'a> FormatterBuilder<'a> {
         self
     }
 
+    /// Controls the whitespace within template interpolations.
+    ///
+    /// By default, interpolations are formatted without inner spacing:
+    ///
+    /// ```hcl
+    /// greeting = "Hello, ${name}!"
+    /// ```
+    ///
+    /// When interpolation spacing is enabled, the interpolated expression is surrounded by
+    /// single spaces, similar to template directives:
+    ///
+    /// ```hcl
+    /// greeting = "Hello, ${ name }!"
+    /// ```
+    ///
+    /// This applies to [`Template`][crate::template::Template] values as well as to the quoted
+    /// strings and heredocs of [`TemplateExpr`][crate::expr::TemplateExpr] values, e.g. those of a
+    /// parsed [`Body`][crate::Body]. Template expressions which fail to parse as a template are
+    /// written verbatim.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::expr::Variable;
+    /// use hcl::format::{Format, Formatter};
+    /// use hcl::template::Template;
+    ///
+    /// let template = Template::new()
+    ///     .add_literal("Hello, ")
+    ///     .add_interpolation(Variable::new("name")?)
+    ///     .add_literal("!");
+    ///
+    /// let mut formatter = Formatter::builder()
+    ///     .space_interpolations(true)
+    ///     .build_vec();
+    ///
+    /// assert_eq!(template.format_string(&mut formatter)?, "Hello, ${ name }!");
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn space_interpolations(mut self, yes: bool) -> Self {
+        self.config.space_interpolations = yes;
+        self
+    }
+
+    /// Controls the order of attributes and blocks within a body.
+    ///
+    /// By default, attributes and blocks are emitted in their original order. See
+    /// [`StructureOrder`] for the available options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::format::{Format, Formatter, StructureOrder};
+    /// use hcl::Body;
+    ///
+    /// let body = Body::builder()
+    ///     .add_block(hcl::Block::builder("lifecycle").build())
+    ///     .add_attribute(("count", 2))
+    ///     .build();
+    ///
+    /// let mut formatter = Formatter::builder()
+    ///     .order(StructureOrder::AttributesFirst)
+    ///     .build_vec();
+    ///
+    /// assert_eq!(body.format_string(&mut formatter)?, "count = 2\n\nlifecycle {}\n");
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn order(mut self, order: StructureOrder) -> Self {
+        self.config.order = order;
+        self
+    }
+
+    /// If set, attributes are sorted lexicographically by key.
+    ///
+    /// Sorting applies to the top-level body and recursively to the bodies of all nested blocks.
+    /// Blocks are never reordered: only consecutive attributes between blocks are sorted as a
+    /// group. Combined with [`order`][FormatterBuilder::order], all attributes of a body are
+    /// sorted together.
+    ///
+    /// This is useful to reduce the noise in diffs of machine-generated configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::format::{Format, Formatter};
+    ///
+    /// let body = hcl::parse("b = 2\na = 1\nblock {\n  d = 4\n  c = 3\n}\n")?;
+    ///
+    /// let mut formatter = Formatter::builder()
+    ///     .sort_attributes(true)
+    ///     .build_vec();
+    ///
+    /// assert_eq!(
+    ///     body.format_string(&mut formatter)?,
+    ///     "a = 1\nb = 2\n\nblock {\n  c = 3\n  d = 4\n}\n"
+    /// );
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn sort_attributes(mut self, yes: bool) -> Self {
+        self.config.sort_attributes = yes;
+        self
+    }
+
+    /// Set the keywords which are emitted for the boolean values `true` and `false`.
+    ///
+    /// The defaults are `true` and `false`. This is only useful for formatting HCL-like dialects
+    /// which use different keywords: changing them produces non-standard HCL, which does not
+    /// round-trip through the parser anymore because the custom keywords are not recognized as
+    /// boolean literals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::format::{Format, Formatter};
+    /// use hcl::structure::Attribute;
+    ///
+    /// let mut formatter = Formatter::builder()
+    ///     .bool_literals("yes", "no")
+    ///     .build_vec();
+    ///
+    /// let attr = Attribute::new("enabled", true);
+    ///
+    /// assert_eq!(attr.format_string(&mut formatter)?, "enabled = yes\n");
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn bool_literals(mut self, true_literal: &'a str, false_literal: &'a str) -> Self {
+        self.config.true_literal = true_literal;
+        self.config.false_literal = false_literal;
+        self
+    }
+
+    /// Set the keyword which is emitted for `null` values.
+    ///
+    /// The default is `null`. Like [`bool_literals`][FormatterBuilder::bool_literals], this is
+    /// only useful for HCL-like dialects: changing it produces output which is not valid HCL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::format::{Format, Formatter};
+    /// use hcl::structure::Attribute;
+    /// use hcl::Value;
+    ///
+    /// let mut formatter = Formatter::builder()
+    ///     .null_literal("nil")
+    ///     .build_vec();
+    ///
+    /// let attr = Attribute::new("value", Value::Null);
+    ///
+    /// assert_eq!(attr.format_string(&mut formatter)?, "value = nil\n");
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn null_literal(mut self, null_literal: &'a str) -> Self {
+        self.config.null_literal = null_literal;
+        self
+    }
+
     /// Consumes the `FormatterBuilder` and turns it into a `Formatter` which writes HCL to the
     /// provided writer.
     pub fn build<W>(self, writer: W) -> Formatter<'a, W>
@@ -394,6 +647,41 @@ where
     pub fn into_inner(self) -> W {
         self.writer
     }
+
+    /// Writes raw HCL source text to the underlying writer without escaping it.
+    ///
+    /// The first line is written as is, all subsequent lines are indented to match the current
+    /// nesting level of the formatter. Line breaks are normalized to the configured newline
+    /// style. Empty lines are not indented.
+    ///
+    /// Since lines are indented regardless of their content, the body of a non-flush heredoc
+    /// (`<<EOT`) written at a nesting level greater than zero gains leading whitespace which
+    /// becomes part of the template. Use flush heredocs (`<<-EOT`) to avoid this.
+    ///
+    /// This is used to format [`Expression::Raw`][crate::Expression::Raw] values. It is the
+    /// caller's responsibility to ensure that the raw text is valid HCL in the position it is
+    /// written to.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if writing to the underlying writer fails.
+    pub fn write_raw(&mut self, raw: &str) -> Result<()> {
+        for (i, line) in raw.split('\n').enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if i > 0 {
+                self.write_newline()?;
+
+                if !line.is_empty() {
+                    self.write_indent(self.current_indent)?;
+                }
+            }
+
+            self.write_bytes(line.as_bytes())?;
+        }
+
+        Ok(())
+    }
 }
 
 // Internal formatter API.
@@ -401,19 +689,19 @@ impl<'a, W> Formatter<'a, W>
 where
     W: io::Write,
 {
-    /// Writes `null` to the writer.
+    /// Writes the configured `null` literal to the writer.
     fn write_null(&mut self) -> Result<()> {
-        self.write_bytes(b"null")
+        self.write_bytes(self.config.null_literal.as_bytes())
     }
 
-    /// Writes a boolean value to the writer.
+    /// Writes a boolean value to the writer, using the configured literals.
     fn write_bool(&mut self, value: bool) -> Result<()> {
         let s = if value {
-            b"true" as &[u8]
+            self.config.true_literal
         } else {
-            b"false" as &[u8]
+            self.config.false_literal
         };
-        self.write_bytes(s)
+        self.write_bytes(s.as_bytes())
     }
 
     /// Writes an integer value to the writer.
@@ -426,6 +714,12 @@ where
         self.write_bytes(s.as_bytes())
     }
 
+    /// Writes a number to the writer.
+    fn write_number(&mut self, value: &Number) -> Result<()> {
+        write!(self.writer, "{value}")?;
+        Ok(())
+    }
+
     /// Writes a quoted string to the writer.
     fn write_quoted_string(&mut self, s: &str) -> Result<()> {
         self.write_bytes(b"\"")?;
@@ -491,13 +785,14 @@ where
         if self.first_element {
             self.first_element = false;
             if !self.compact_arrays() {
-                self.write_bytes(b"\n")?;
+                self.write_newline()?;
                 self.write_indent(self.current_indent)?;
             }
         } else if self.compact_arrays() {
             self.write_bytes(b", ")?;
         } else {
-            self.write_bytes(b",\n")?;
+            self.write_bytes(b",")?;
+            self.write_newline()?;
             self.write_indent(self.current_indent)?;
         }
 
@@ -512,11 +807,15 @@ where
 
     /// Signals the end of an array to the formatter.
     fn end_array(&mut self) -> Result<()> {
+        // An empty array never clears the flag itself. Reset it so that the array is not mistaken
+        // for the first element of an enclosing array or object.
+        self.first_element = false;
+
         if !self.compact_arrays() {
             self.current_indent -= 1;
 
             if self.has_value {
-                self.write_bytes(b"\n")?;
+                self.write_newline()?;
                 self.write_indent(self.current_indent)?;
             }
         }
@@ -541,13 +840,13 @@ where
             if self.compact_objects() {
                 self.write_bytes(b" ")?;
             } else {
-                self.write_bytes(b"\n")?;
+                self.write_newline()?;
                 self.write_indent(self.current_indent)?;
             }
         } else if self.compact_objects() {
             self.write_bytes(b", ")?;
         } else {
-            self.write_bytes(b"\n")?;
+            self.write_newline()?;
             self.write_indent(self.current_indent)?;
         }
 
@@ -566,6 +865,9 @@ where
 
     /// Signals the end of an object to the formatter.
     fn end_object(&mut self) -> Result<()> {
+        // See `end_array`.
+        self.first_element = false;
+
         if self.compact_objects() {
             if self.has_value {
                 self.write_bytes(b" ")?;
@@ -574,7 +876,7 @@ where
             self.current_indent -= 1;
 
             if self.has_value {
-                self.write_bytes(b"\n")?;
+                self.write_newline()?;
                 self.write_indent(self.current_indent)?;
             }
         }
@@ -594,9 +896,10 @@ where
     }
 
     /// Signals the end of an attribute to the formatter.
-    fn end_attribute(&mut self) -> Result<()> {
+    fn end_attribute(&mut self, trailing_comment: Option<&str>) -> Result<()> {
         self.state = FormatState::AttributeEnd;
-        self.write_bytes(b"\n")
+        self.write_trailing_comment(trailing_comment)?;
+        self.write_newline()
     }
 
     /// Signals the start of a block to the formatter.
@@ -613,11 +916,54 @@ where
     }
 
     /// Signals the end of a block to the formatter.
-    fn end_block(&mut self) -> Result<()> {
+    fn end_block(&mut self, trailing_comment: Option<&str>) -> Result<()> {
         self.state = FormatState::BlockEnd;
         self.current_indent -= 1;
         self.write_indent(self.current_indent)?;
-        self.write_bytes(b"}\n")
+        self.write_bytes(b"}")?;
+        self.write_trailing_comment(trailing_comment)?;
+        self.write_newline()
+    }
+
+    // Writes comment lines preceding an attribute or block. Updates the state to `next_state`.
+    fn write_leading_comments(
+        &mut self,
+        comments: &[String],
+        next_state: FormatState,
+    ) -> Result<()> {
+        self.maybe_write_newline(next_state)?;
+
+        for line in comments.iter().flat_map(|comment| comment.lines()) {
+            self.write_indent(self.current_indent)?;
+            self.write_comment(line)?;
+            self.write_newline()?;
+        }
+
+        Ok(())
+    }
+
+    fn write_trailing_comment(&mut self, comment: Option<&str>) -> Result<()> {
+        let Some(comment) = comment else {
+            return Ok(());
+        };
+
+        if comment.contains(['\n', '\r']) {
+            return Err(Error::new("trailing comments must not contain line breaks"));
+        }
+
+        self.write_bytes(b" ")?;
+        self.write_comment(comment)
+    }
+
+    fn write_comment(&mut self, comment: &str) -> Result<()> {
+        let comment = comment.trim_end();
+
+        if comment.is_empty() {
+            self.write_bytes(b"#")
+        } else {
+            self.write_bytes(b"# ")?;
+            self.write_string_fragment(comment)
+        }
     }
 
     // Conditionally writes a newline character depending on the formatter configuration and the
@@ -637,7 +983,7 @@ where
         };
 
         if newline {
-            self.write_bytes(b"\n")?;
+            self.write_newline()?;
         }
 
         self.state = next_state;
@@ -655,7 +1001,7 @@ where
     fn write_indented(&mut self, n: usize, s: &str) -> Result<()> {
         for (i, line) in s.lines().enumerate() {
             if i > 0 {
-                self.write_bytes(b"\n")?;
+                self.write_newline()?;
             }
 
             self.write_indent(n)?;
@@ -663,7 +1009,30 @@ where
         }
 
         if s.ends_with('\n') {
-            self.write_bytes(b"\n")?;
+            self.write_newline()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the configured newline to the writer.
+    fn write_newline(&mut self) -> Result<()> {
+        self.write_bytes(self.config.newline.as_bytes())
+    }
+
+    /// Writes a string fragment which may contain newlines to the writer. Newlines are written
+    /// using the configured newline style.
+    fn write_multiline_fragment(&mut self, s: &str) -> Result<()> {
+        if self.config.newline == Newline::Lf {
+            return self.write_string_fragment(s);
+        }
+
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.write_newline()?;
+            }
+
+            self.write_string_fragment(line.strip_suffix('\r').unwrap_or(line))?;
         }
 
         Ok(())
@@ -66,7 +66,7 @@ pub use expr::{
 };
 
 pub use ident::Identifier;
-pub use parser::parse;
+pub use parser::{parse, parse_attribute, parse_block, parse_with_raw_values, RawValue};
 
 #[doc(inline)]
 pub use ser::{to_string, to_vec, to_writer};
@@ -1,7 +1,9 @@
-use crate::edit;
-use crate::structure::Body;
+use crate::edit::{self, Span};
+use crate::expr::Expression;
+use crate::structure::{Attribute, Block, Body};
 use crate::template::Template;
 use crate::Result;
+use std::ops::Range;
 
 /// Parse a `hcl::Body` from a `&str`.
 ///
@@ -49,7 +51,216 @@ pub fn parse(input: &str) -> Result<Body> {
     Ok(body.into())
 }
 
+/// The raw source text of an attribute value.
+///
+/// Values of this type are returned by [`parse_with_raw_values`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawValue<'a> {
+    /// The identifiers and labels of the enclosing blocks, followed by the attribute key.
+    pub path: Vec<String>,
+    /// The source text of the attribute value, without surrounding whitespace and comments.
+    pub source: &'a str,
+    /// The byte range of the attribute value within the input.
+    pub span: Range<usize>,
+}
+
+/// Parse a `hcl::Body` from a `&str` and capture the raw source text of every attribute value.
+///
+/// This is useful for tooling which works with the parsed body but needs to preserve the
+/// original formatting of individual attribute values. The raw values are returned in document
+/// order, including the attributes of nested blocks.
+///
+/// The source text is borrowed from the input and not copied, so the additional memory cost is
+/// one [`RawValue`] per attribute, which mainly consists of its path. If the complete source
+/// including whitespace and comments must be preserved, consider using the [`hcl-edit`] crate
+/// instead.
+///
+/// [`hcl-edit`]: https://docs.rs/hcl-edit
+///
+/// # Example
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let input = r#"
+///     service "api" {
+///       ports = [ 80,   443 ] # Odd formatting.
+///     }
+/// "#;
+///
+/// let (body, raw_values) = hcl::parse_with_raw_values(input)?;
+///
+/// assert_eq!(body, hcl::parse(input)?);
+/// assert_eq!(raw_values[0].path, ["service", "api", "ports"]);
+/// assert_eq!(raw_values[0].source, "[ 80,   443 ]");
+/// #   Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function fails with an error if the `input` cannot be parsed as HCL.
+pub fn parse_with_raw_values(input: &str) -> Result<(Body, Vec<RawValue<'_>>)> {
+    let body: edit::structure::Body = input.parse()?;
+    let mut raw_values = Vec::new();
+    collect_raw_values(input, &body, &mut Vec::new(), &mut raw_values);
+    Ok((body.into(), raw_values))
+}
+
+fn collect_raw_values<'a>(
+    input: &'a str,
+    body: &edit::structure::Body,
+    path: &mut Vec<String>,
+    raw_values: &mut Vec<RawValue<'a>>,
+) {
+    for structure in body {
+        match structure {
+            edit::structure::Structure::Attribute(attr) => {
+                // Spans are always present on parsed bodies.
+                if let Some(span) = attr.value.span() {
+                    let mut path = path.clone();
+                    path.push(attr.key.to_string());
+
+                    raw_values.push(RawValue {
+                        path,
+                        source: &input[span.clone()],
+                        span,
+                    });
+                }
+            }
+            edit::structure::Structure::Block(block) => {
+                let len = path.len();
+                path.push(block.ident.to_string());
+                path.extend(block.labels.iter().map(|label| label.as_str().to_owned()));
+                collect_raw_values(input, &block.body, path, raw_values);
+                path.truncate(len);
+            }
+        }
+    }
+}
+
+/// Parse a single `hcl::Attribute` from a `&str`.
+///
+/// The input must contain exactly one attribute, surrounding whitespace and comments are
+/// allowed.
+///
+/// # Example
+///
+/// ```
+/// use hcl::Attribute;
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let attr = hcl::parse_attribute(r#"name = "foo""#)?;
+///
+/// assert_eq!(attr, Attribute::new("name", "foo"));
+/// #   Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function fails with an error if the `input` cannot be parsed as a single HCL attribute,
+/// for example because it contains a block or more than one structure.
+pub fn parse_attribute(input: &str) -> Result<Attribute> {
+    let attr = edit::parser::parse_attribute(input)?;
+    Ok(attr.into())
+}
+
+/// Parse a single `hcl::Block` from a `&str`.
+///
+/// The input must contain exactly one block, surrounding whitespace and comments are allowed.
+///
+/// # Example
+///
+/// ```
+/// use hcl::Block;
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let block = hcl::parse_block(r#"
+///     resource "aws_s3_bucket" "bucket" {
+///       bucket = "my-bucket"
+///     }
+/// "#)?;
+///
+/// let expected = Block::builder("resource")
+///     .add_label("aws_s3_bucket")
+///     .add_label("bucket")
+///     .add_attribute(("bucket", "my-bucket"))
+///     .build();
+///
+/// assert_eq!(block, expected);
+/// #   Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function fails with an error if the `input` cannot be parsed as a single HCL block, for
+/// example because it contains an attribute or more than one structure.
+pub fn parse_block(input: &str) -> Result<Block> {
+    let block = edit::parser::parse_block(input)?;
+    Ok(block.into())
+}
+
+pub(crate) fn parse_expr(input: &str) -> Result<Expression> {
+    let expr: edit::expr::Expression = input.parse()?;
+    Ok(expr.into())
+}
+
 pub fn parse_template(input: &str) -> Result<Template> {
     let template: edit::template::Template = input.parse()?;
     Ok(template.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn raw_values() {
+        let input = indoc! {r#"
+            name = "app" // Trailing comment.
+            service "api" {
+              replicas = /* inline */ 1 +   2
+              script = <<EOT
+            echo hello
+            EOT
+              nested {
+                tags = {
+                  env = "prod"
+                }
+              }
+            }
+        "#};
+
+        let (body, raw_values) = parse_with_raw_values(input).unwrap();
+
+        assert_eq!(body, parse(input).unwrap());
+
+        let raw_values: Vec<_> = raw_values
+            .into_iter()
+            .map(|raw| {
+                assert_eq!(&input[raw.span], raw.source);
+                (raw.path.join("."), raw.source)
+            })
+            .collect();
+
+        assert_eq!(
+            raw_values,
+            [
+                ("name".to_owned(), "\"app\""),
+                ("service.api.replicas".to_owned(), "1 +   2"),
+                ("service.api.script".to_owned(), "<<EOT\necho hello\nEOT"),
+                (
+                    "service.api.nested.tags".to_owned(),
+                    "{\n      env = \"prod\"\n    }"
+                ),
+            ]
+        );
+    }
+}
@@ -261,6 +261,97 @@
 //!
 //! assert_eq!(serialized, expected);
 //! ```
+//!
+//! ## Declaring blocks in the type definition
+//!
+//! Instead of annotating every field with `serialize_with`, the [`Block`] and [`LabeledBlock`]
+//! wrapper types can be used directly as field types. Fields wrapped in one of them are
+//! serialized as blocks, all other fields are serialized as attributes. Since the wrappers are
+//! part of the type, this also works for deeply nested structures and collections:
+//!
+//! - `Block<T>` produces a block without labels. If `T` is a sequence, one block is produced per
+//!   element.
+//! - `LabeledBlock<T>` produces one block per entry of the map-like `T`, using the entry's key as
+//!   label.
+//! - Nesting `LabeledBlock` inside the map values of another `LabeledBlock` adds a label per
+//!   nesting level.
+//!
+//! ```
+//! use hcl::expr::{Traversal, Variable};
+//! use hcl::ser::{Block, LabeledBlock};
+//! use indexmap::{indexmap, IndexMap};
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Config {
+//!     provider: LabeledBlock<IndexMap<String, Provider>>,
+//!     resource: LabeledBlock<IndexMap<String, LabeledBlock<IndexMap<String, Instance>>>>,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct Provider {
+//!     region: String,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct Instance {
+//!     ami: String,
+//!     subnet_id: Traversal,
+//!     ebs_block_device: Block<Vec<EbsBlockDevice>>,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct EbsBlockDevice {
+//!     device_name: String,
+//!     volume_size: u32,
+//! }
+//!
+//! let instance = Instance {
+//!     ami: "ami-0123456789".into(),
+//!     subnet_id: Traversal::builder(Variable::new("aws_subnet").unwrap())
+//!         .attr("main")
+//!         .attr("id")
+//!         .build(),
+//!     ebs_block_device: Block::new(vec![
+//!         EbsBlockDevice { device_name: "/dev/sdb".into(), volume_size: 10 },
+//!         EbsBlockDevice { device_name: "/dev/sdc".into(), volume_size: 20 },
+//!     ]),
+//! };
+//!
+//! let config = Config {
+//!     provider: LabeledBlock::new(indexmap! {
+//!         "aws".into() => Provider { region: "eu-west-1".into() },
+//!     }),
+//!     resource: LabeledBlock::new(indexmap! {
+//!         "aws_instance".into() => LabeledBlock::new(indexmap! {
+//!             "web".into() => instance,
+//!         }),
+//!     }),
+//! };
+//!
+//! let expected = r#"
+//! provider "aws" {
+//!   region = "eu-west-1"
+//! }
+//!
+//! resource "aws_instance" "web" {
+//!   ami = "ami-0123456789"
+//!   subnet_id = aws_subnet.main.id
+//!
+//!   ebs_block_device {
+//!     device_name = "/dev/sdb"
+//!     volume_size = 10
+//!   }
+//!
+//!   ebs_block_device {
+//!     device_name = "/dev/sdc"
+//!     volume_size = 20
+//!   }
+//! }
+//! "#.trim_start();
+//!
+//! assert_eq!(hcl::to_string(&config).unwrap(), expected);
+//! ```
 
 pub(crate) mod blocks;
 
@@ -15,6 +15,37 @@ use std::iter;
 ///
 /// Use [`Attribute::new`] to construct an [`Attribute`] from a value that is convertible to this
 /// crate's [`Expression`] type.
+///
+/// Attributes can also be converted from `(key, value)` tuples. Tuples are accepted everywhere
+/// an `Into<Attribute>` or `Into<Structure>` is expected, which is convenient when building
+/// structures in loops and macros.
+///
+/// # Example
+///
+/// ```
+/// use hcl::{Attribute, Body, Structure};
+///
+/// let attr = Attribute::from(("region", "eu-west-1"));
+/// assert_eq!(attr, Attribute::new("region", "eu-west-1"));
+///
+/// let ports = [80, 443];
+/// let attrs: Vec<Attribute> = ports
+///     .iter()
+///     .enumerate()
+///     .map(|(i, port)| Attribute::from((format!("port_{i}"), *port)))
+///     .collect();
+///
+/// assert_eq!(attrs[1], Attribute::new("port_1", 443));
+///
+/// // A tuple converts into an attribute structure directly.
+/// assert_eq!(
+///     Structure::from(("enabled", true)),
+///     Structure::Attribute(Attribute::new("enabled", true)),
+/// );
+///
+/// let body = Body::from([("foo", 1), ("bar", 2)]);
+/// assert_eq!(body.attributes().count(), 2);
+/// ```
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Attribute {
     /// The HCL attribute's key.
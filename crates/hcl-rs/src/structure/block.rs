@@ -1,5 +1,6 @@
 //! Types to represent and build HCL blocks.
 
+use super::body::same_block_key;
 use super::{Attribute, Body, BodyBuilder, Structure};
 use crate::Identifier;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,49 @@ use std::borrow::Cow;
 ///   body
 /// }
 /// ```
+///
+/// Besides using [`Block::builder`], blocks can be converted from `(identifier, body)` and
+/// `(identifier, labels, body)` tuples. The labels can be any iterator of values convertible into
+/// a [`BlockLabel`], and the body can be a [`Body`] or any iterator of values convertible into a
+/// [`Structure`], including `(key, value)` attribute tuples.
+///
+/// # Example
+///
+/// ```
+/// use hcl::{Attribute, Block, BlockLabel, Body};
+///
+/// let block = Block::from(("provider", [("region", "eu-west-1")]));
+///
+/// assert_eq!(
+///     block,
+///     Block::builder("provider")
+///         .add_attribute(("region", "eu-west-1"))
+///         .build()
+/// );
+///
+/// let block = Block::from((
+///     "resource",
+///     vec![BlockLabel::from("aws_s3_bucket"), BlockLabel::from("logs")],
+///     Body::builder().add_attribute(("bucket", "logs")).build(),
+/// ));
+///
+/// assert_eq!(
+///     block,
+///     Block::builder("resource")
+///         .add_labels(["aws_s3_bucket", "logs"])
+///         .add_attribute(("bucket", "logs"))
+///         .build()
+/// );
+///
+/// // Convenient for building blocks in loops.
+/// let buckets: Vec<Block> = ["logs", "assets"]
+///     .into_iter()
+///     .map(|name| Block::from(("resource", ["aws_s3_bucket", name], [("bucket", name)])))
+///     .collect();
+///
+/// assert_eq!(buckets[1].labels[1].as_str(), "assets");
+/// assert_eq!(buckets[1].body.attributes().next(), Some(&Attribute::new("bucket", "assets")));
+/// ```
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Block {
     /// The block identifier.
@@ -61,6 +105,53 @@ impl Block {
     pub fn body(&self) -> &Body {
         &self.body
     }
+
+    /// Returns `true` if the block's body contains an attribute with the given key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Block;
+    ///
+    /// let block = Block::builder("resource")
+    ///     .add_attribute(("count", 2))
+    ///     .build();
+    ///
+    /// assert!(block.has_attribute("count"));
+    /// assert!(!block.has_attribute("for_each"));
+    /// ```
+    pub fn has_attribute(&self, key: &str) -> bool {
+        self.body.contains_attribute(key)
+    }
+
+    /// Compares two blocks for equality, ignoring the order of attributes within their bodies.
+    ///
+    /// The blocks must have the same identifier and labels. See [`Body::eq_ignore_order`] for
+    /// how the block bodies are compared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Block;
+    ///
+    /// let a = Block::builder("resource")
+    ///     .add_label("aws_instance")
+    ///     .add_attribute(("ami", "ami-123"))
+    ///     .add_attribute(("count", 2))
+    ///     .build();
+    ///
+    /// let b = Block::builder("resource")
+    ///     .add_label("aws_instance")
+    ///     .add_attribute(("count", 2))
+    ///     .add_attribute(("ami", "ami-123"))
+    ///     .build();
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_ignore_order(&b));
+    /// ```
+    pub fn eq_ignore_order(&self, other: &Block) -> bool {
+        same_block_key(self, other) && self.body.eq_ignore_order(&other.body)
+    }
 }
 
 impl<I, B> From<(I, B)> for Block
@@ -4,10 +4,11 @@ use super::iter::{
     Attributes, AttributesMut, Blocks, BlocksMut, IntoAttributes, IntoBlocks, Iter, IterMut,
 };
 use super::ser::BodySerializer;
-use super::{Attribute, Block, Structure};
+use super::{Attribute, Block, BlockLabel, Structure};
 use crate::ser::with_internal_serialization;
-use crate::Result;
+use crate::{Map, Result, Value};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Represents an HCL config file body.
 ///
@@ -30,11 +31,102 @@ impl Body {
         self.0
     }
 
+    /// Returns the structures of the `Body` as a slice.
+    ///
+    /// The structures are in the order in which they were added to the `Body` or, for parsed
+    /// bodies, in the order in which they appear in the HCL input.
+    pub fn structures(&self) -> &[Structure] {
+        &self.0
+    }
+
+    /// Returns the structures of the `Body` as a mutable slice.
+    ///
+    /// This allows in-place transformations of individual structures as well as reordering them,
+    /// but not adding or removing structures. The order of the structures is preserved unless it
+    /// is changed explicitly via the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hcl::{Attribute, Block, Body, Structure};
+    ///
+    /// let mut body = Body::from([
+    ///     Structure::Attribute(Attribute::new("a", 1)),
+    ///     Structure::Block(Block::new("b")),
+    /// ]);
+    ///
+    /// if let Structure::Attribute(attr) = &mut body.structures_mut()[0] {
+    ///     attr.expr = 2.into();
+    /// }
+    ///
+    /// body.structures_mut().reverse();
+    ///
+    /// assert_eq!(body.structures(), [
+    ///     Structure::Block(Block::new("b")),
+    ///     Structure::Attribute(Attribute::new("a", 2)),
+    /// ]);
+    /// ```
+    pub fn structures_mut(&mut self) -> &mut [Structure] {
+        &mut self.0
+    }
+
     /// Creates a new [`BodyBuilder`] to start building a new `Body`.
     pub fn builder() -> BodyBuilder {
         BodyBuilder::default()
     }
 
+    /// Constructs a new, empty `Body` with at least the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Body {
+        Body(Vec::with_capacity(capacity))
+    }
+
+    /// Returns `true` if the body contains no structures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of structures in the body, also referred to as its 'length'.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Clears the body, removing all structures.
+    ///
+    /// This has no effect on the allocated capacity, which makes it possible to reuse the `Body`
+    /// when building many bodies in a row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::{Attribute, Body};
+    ///
+    /// let mut body = Body::with_capacity(2);
+    ///
+    /// for i in 0..3 {
+    ///     body.clear();
+    ///     body.extend([Attribute::new("a", i), Attribute::new("b", i * 2)]);
+    ///
+    ///     assert_eq!(body.len(), 2);
+    ///     assert_eq!(hcl::to_string(&body).unwrap(), format!("a = {}\nb = {}\n", i, i * 2));
+    /// }
+    ///
+    /// body.clear();
+    /// assert!(body.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Reserves capacity for at least `additional` more structures to be inserted into the
+    /// `Body`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
     /// An iterator visiting all structures within the `Body`. The iterator element type is `&'a
     /// Structure`.
     ///
@@ -234,6 +326,629 @@ impl Body {
     pub fn into_blocks(self) -> IntoBlocks {
         IntoBlocks::new(self)
     }
+
+    /// Returns `true` if the body contains an attribute with the given key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Body;
+    ///
+    /// let body = Body::builder().add_attribute(("foo", "bar")).build();
+    ///
+    /// assert!(body.contains_attribute("foo"));
+    /// assert!(!body.contains_attribute("bar"));
+    /// ```
+    pub fn contains_attribute(&self, key: &str) -> bool {
+        self.attributes().any(|attr| attr.key() == key)
+    }
+
+    /// Returns `true` if the body contains at least one block with the given identifier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::{Block, Body};
+    ///
+    /// let body = Body::builder().add_block(Block::new("provider")).build();
+    ///
+    /// assert!(body.contains_block("provider"));
+    /// assert!(!body.contains_block("terraform"));
+    /// ```
+    pub fn contains_block(&self, identifier: &str) -> bool {
+        self.blocks().any(|block| block.identifier() == identifier)
+    }
+
+    /// Returns a reference to the attribute with the given key, if present.
+    ///
+    /// HCL does not allow multiple attributes with the same key within a body. If the `Body`
+    /// contains duplicate keys nonetheless, the first matching attribute is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::{Attribute, Body};
+    ///
+    /// let body = Body::builder().add_attribute(("foo", "bar")).build();
+    ///
+    /// assert_eq!(body.get_attribute("foo"), Some(&Attribute::new("foo", "bar")));
+    /// assert_eq!(body.get_attribute("bar"), None);
+    /// ```
+    pub fn get_attribute(&self, key: &str) -> Option<&Attribute> {
+        self.attributes().find(|attr| attr.key() == key)
+    }
+
+    /// Returns a mutable reference to the attribute with the given key, if present.
+    ///
+    /// Like [`get_attribute`][Body::get_attribute], this returns the first matching attribute
+    /// if the `Body` contains duplicate keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::{Attribute, Body, Expression};
+    ///
+    /// let mut body = Body::builder().add_attribute(("foo", "bar")).build();
+    ///
+    /// if let Some(attr) = body.get_attribute_mut("foo") {
+    ///     attr.expr = Expression::from("baz");
+    /// }
+    ///
+    /// assert_eq!(body.get_attribute("foo"), Some(&Attribute::new("foo", "baz")));
+    /// ```
+    pub fn get_attribute_mut(&mut self, key: &str) -> Option<&mut Attribute> {
+        self.attributes_mut().find(|attr| attr.key() == key)
+    }
+
+    /// Returns a reference to the first block with the given identifier, if present.
+    ///
+    /// Use [`get_blocks`][Body::get_blocks] to visit all blocks with the identifier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::{Block, Body};
+    ///
+    /// let body = Body::builder()
+    ///     .add_block(Block::builder("provider").add_label("aws").build())
+    ///     .add_block(Block::builder("provider").add_label("google").build())
+    ///     .build();
+    ///
+    /// let provider = body.get_block("provider").unwrap();
+    /// assert_eq!(provider.labels()[0].as_str(), "aws");
+    /// assert!(body.get_block("terraform").is_none());
+    /// ```
+    pub fn get_block(&self, identifier: &str) -> Option<&Block> {
+        self.blocks().find(|block| block.identifier() == identifier)
+    }
+
+    /// An iterator visiting all blocks with the given identifier in the order in which they
+    /// appear within the `Body`. The iterator element type is `&'a Block`.
+    ///
+    /// Use [`blocks_by_identifier`][Body::blocks_by_identifier] to group all blocks by their
+    /// identifier at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::{Block, Body};
+    ///
+    /// let body = Body::builder()
+    ///     .add_block(Block::builder("variable").add_label("region").build())
+    ///     .add_block(Block::builder("output").add_label("id").build())
+    ///     .add_block(Block::builder("variable").add_label("zone").build())
+    ///     .build();
+    ///
+    /// let names: Vec<&str> = body
+    ///     .get_blocks("variable")
+    ///     .map(|block| block.labels()[0].as_str())
+    ///     .collect();
+    ///
+    /// assert_eq!(names, ["region", "zone"]);
+    /// ```
+    pub fn get_blocks<'a>(&'a self, identifier: &'a str) -> impl Iterator<Item = &'a Block> + 'a {
+        self.blocks()
+            .filter(move |block| block.identifier() == identifier)
+    }
+
+    /// Groups all blocks within the `Body` by their identifier.
+    ///
+    /// The groups are ordered by the first occurrence of their identifier within the body, and
+    /// the blocks within each group retain the order in which they appear in the body.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let body = hcl::parse(r#"
+    ///     variable "region" {}
+    ///     resource "aws_instance" "web" {}
+    ///     variable "zone" {}
+    ///     resource "aws_s3_bucket" "assets" {}
+    ///     resource "aws_s3_bucket" "logs" {}
+    /// "#)?;
+    ///
+    /// let blocks = body.blocks_by_identifier();
+    ///
+    /// let counts: Vec<(&str, usize)> = blocks
+    ///     .iter()
+    ///     .map(|(identifier, blocks)| (*identifier, blocks.len()))
+    ///     .collect();
+    ///
+    /// assert_eq!(counts, [("variable", 2), ("resource", 3)]);
+    /// assert_eq!(blocks["variable"][1].labels()[0].as_str(), "zone");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn blocks_by_identifier(&self) -> Map<&str, Vec<&Block>> {
+        let mut map: Map<&str, Vec<&Block>> = Map::new();
+
+        for block in self.blocks() {
+            map.entry(block.identifier()).or_default().push(block);
+        }
+
+        map
+    }
+
+    /// Converts the body into a [`Value`] which follows the [HCL JSON specification][json-spec].
+    ///
+    /// This is the same representation which is used when a `Body` is deserialized via
+    /// [`hcl::from_body`][crate::from_body] and the canonical form for converting HCL into other
+    /// data formats: since `Value` implements [`Serialize`][serde::Serialize], it can be passed
+    /// to any serde serializer, e.g. to produce JSON, YAML or TOML.
+    ///
+    /// The conversion rules are:
+    ///
+    /// - Attributes become object entries, keyed by the attribute key.
+    /// - Blocks become nested objects: the block identifier and each of the labels add one
+    ///   level of nesting, with the block body as the innermost object.
+    /// - Blocks with the same identifier and labels are merged into an array of their bodies,
+    ///   in the order in which they appear. A single block is represented by its body object.
+    /// - Expressions which cannot be represented as a literal value, like variable references,
+    ///   function calls or operations, become strings containing the expression wrapped in an
+    ///   interpolation, e.g. `"${var.foo}"`. To resolve them instead, evaluate the body first
+    ///   via [`hcl::eval::from_str`][crate::eval::from_str].
+    ///
+    /// Note that some data formats do not support all values, e.g. TOML cannot represent
+    /// `null`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let body = hcl::parse(r#"
+    ///     region = "eu-west-1"
+    ///
+    ///     ingress { port = 80 }
+    ///     ingress { port = 443 }
+    ///
+    ///     resource "aws_s3_bucket" "logs" {
+    ///       bucket = var.name
+    ///     }
+    /// "#)?;
+    ///
+    /// let json = serde_json::to_value(body.into_value())?;
+    ///
+    /// let expected = serde_json::json!({
+    ///     "region": "eu-west-1",
+    ///     "ingress": [{ "port": 80 }, { "port": 443 }],
+    ///     "resource": { "aws_s3_bucket": { "logs": { "bucket": "${var.name}" } } }
+    /// });
+    ///
+    /// assert_eq!(json, expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [json-spec]: https://github.com/hashicorp/hcl/blob/main/json/spec.md
+    pub fn into_value(self) -> Value {
+        Value::from(self)
+    }
+
+    /// Constructs a `Body` from a value that follows the [HCL JSON specification][json-spec].
+    ///
+    /// This is the inverse of [`Body::into_value`]. Since the HCL JSON specification relies on a
+    /// schema to tell attributes and blocks apart, the following mapping is used instead:
+    ///
+    /// - The value must be an object. Each of its keys must be a valid identifier.
+    /// - A key with an object value becomes a block. If the object is non-empty and all of its
+    ///   values are objects again, its keys are treated as block labels and the nested objects
+    ///   are processed recursively. Otherwise the object is the block body.
+    /// - A key with a non-empty array value whose elements are all objects becomes one block per
+    ///   element, each element being a block body.
+    /// - Any other key becomes an attribute.
+    /// - Strings in attribute values are templates: a string consisting of a single
+    ///   interpolation like `"${var.name}"` becomes the interpolated expression, other strings
+    ///   containing interpolations or directives become template expressions. Escaped template
+    ///   markers (`$${` and `%%{`) in plain strings are unescaped.
+    ///
+    /// Consequently, attributes with object values or arrays of objects are turned into blocks,
+    /// and blocks whose body only consists of nested blocks are treated as labelled blocks.
+    ///
+    /// The value can be obtained from any serializable type, e.g. a `serde_json::Value`, via
+    /// [`hcl::to_value`][crate::to_value].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value is not an object, if a key cannot be used as
+    /// identifier, or if a string contains an invalid template.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::Body;
+    ///
+    /// let json = serde_json::json!({
+    ///     "region": "eu-west-1",
+    ///     "ingress": [{ "port": 80 }, { "port": 443 }],
+    ///     "resource": { "aws_s3_bucket": { "logs": { "bucket": "${var.name}" } } }
+    /// });
+    ///
+    /// let body = Body::from_json_value(hcl::to_value(json)?)?;
+    ///
+    /// let expected = hcl::parse(r#"
+    ///     region = "eu-west-1"
+    ///
+    ///     ingress { port = 80 }
+    ///     ingress { port = 443 }
+    ///
+    ///     resource "aws_s3_bucket" "logs" {
+    ///       bucket = var.name
+    ///     }
+    /// "#)?;
+    ///
+    /// assert_eq!(body, expected);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [json-spec]: https://github.com/hashicorp/hcl/blob/main/json/spec.md
+    pub fn from_json_value(value: Value) -> Result<Body> {
+        super::json_spec::body_from_json_value(value)
+    }
+
+    /// Compares two bodies for semantic equality, ignoring the order of structures where it is
+    /// usually insignificant.
+    ///
+    /// Two bodies are semantically equal if:
+    ///
+    /// - they contain the same attributes with equal expressions, in any order.
+    /// - they contain the same blocks, in any order. Blocks are keyed by their identifier and
+    ///   labels. Quoted and bare identifier labels with the same content are considered equal.
+    ///   The bodies of blocks are compared recursively using `semantic_eq`.
+    ///
+    /// The relative order of blocks that share the same identifier and labels (e.g. multiple
+    /// `ingress` blocks) **is** considered significant, as is the order of elements within
+    /// expressions like arrays and objects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hcl::{Block, Body};
+    ///
+    /// let a = Body::builder()
+    ///     .add_attribute(("foo", 1))
+    ///     .add_block(Block::builder("resource").add_label("a").build())
+    ///     .add_block(Block::builder("resource").add_label("b").build())
+    ///     .build();
+    ///
+    /// let b = Body::builder()
+    ///     .add_block(Block::builder("resource").add_label("b").build())
+    ///     .add_attribute(("foo", 1))
+    ///     .add_block(Block::builder("resource").add_label("a").build())
+    ///     .build();
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.semantic_eq(&b));
+    /// ```
+    pub fn semantic_eq(&self, other: &Body) -> bool {
+        eq_by_key(self.iter(), other.iter(), |lhs, rhs| match (lhs, rhs) {
+            (Structure::Attribute(lhs), Structure::Attribute(rhs)) => lhs.expr == rhs.expr,
+            (Structure::Block(lhs), Structure::Block(rhs)) => lhs.body.semantic_eq(&rhs.body),
+            (_, _) => false,
+        })
+    }
+
+    /// Compares two bodies for equality, ignoring the order of attributes.
+    ///
+    /// Two bodies are equal according to this method if:
+    ///
+    /// - they contain the same attributes, in any order. Attributes with the same key (which is
+    ///   invalid HCL, but representable) are matched in the order in which they appear.
+    /// - they contain the same blocks **in the same order**. Blocks are equal if they have the
+    ///   same identifier and labels, and if their bodies are equal according to
+    ///   `eq_ignore_order`. Quoted and bare identifier labels with the same content are
+    ///   considered equal.
+    ///
+    /// The position of attributes relative to blocks is not significant. The order of elements
+    /// within expressions like arrays and objects is always significant.
+    ///
+    /// Use [`semantic_eq`][Body::semantic_eq] to additionally ignore the order of blocks with
+    /// different identifiers or labels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hcl::{Block, Body};
+    ///
+    /// let a = Body::builder()
+    ///     .add_attribute(("foo", 1))
+    ///     .add_attribute(("bar", 2))
+    ///     .add_block(Block::new("a"))
+    ///     .add_block(Block::new("b"))
+    ///     .build();
+    ///
+    /// let b = Body::builder()
+    ///     .add_block(Block::new("a"))
+    ///     .add_attribute(("bar", 2))
+    ///     .add_block(Block::new("b"))
+    ///     .add_attribute(("foo", 1))
+    ///     .build();
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_ignore_order(&b));
+    ///
+    /// let c = Body::builder()
+    ///     .add_attribute(("foo", 1))
+    ///     .add_attribute(("bar", 2))
+    ///     .add_block(Block::new("b"))
+    ///     .add_block(Block::new("a"))
+    ///     .build();
+    ///
+    /// assert!(!a.eq_ignore_order(&c));
+    /// assert!(a.semantic_eq(&c));
+    /// ```
+    pub fn eq_ignore_order(&self, other: &Body) -> bool {
+        let attrs_eq = eq_by_key(
+            self.iter().filter(|structure| structure.is_attribute()),
+            other.iter().filter(|structure| structure.is_attribute()),
+            |lhs, rhs| lhs == rhs,
+        );
+
+        attrs_eq
+            && self.blocks().count() == other.blocks().count()
+            && self
+                .blocks()
+                .zip(other.blocks())
+                .all(|(lhs, rhs)| lhs.eq_ignore_order(rhs))
+    }
+
+    /// Merges `other` into this body using [`MergeStrategy::Append`].
+    ///
+    /// Attributes of `other` replace attributes with the same key in `self`, keeping their
+    /// original position. Attributes with new keys and all blocks of `other` are appended.
+    ///
+    /// Attributes and blocks are keyed separately: an attribute never replaces a block with the
+    /// same name and vice versa, so both are kept.
+    ///
+    /// See [`merge_with`][Body::merge_with] for merging blocks recursively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hcl::{Block, Body};
+    ///
+    /// let mut body = Body::builder()
+    ///     .add_attribute(("foo", 1))
+    ///     .add_block(Block::builder("resource").add_label("a").build())
+    ///     .build();
+    ///
+    /// body.merge(
+    ///     Body::builder()
+    ///         .add_attribute(("foo", 2))
+    ///         .add_attribute(("bar", 3))
+    ///         .add_block(Block::builder("resource").add_label("a").build())
+    ///         .build(),
+    /// );
+    ///
+    /// let expected = Body::builder()
+    ///     .add_attribute(("foo", 2))
+    ///     .add_block(Block::builder("resource").add_label("a").build())
+    ///     .add_attribute(("bar", 3))
+    ///     .add_block(Block::builder("resource").add_label("a").build())
+    ///     .build();
+    ///
+    /// assert_eq!(body, expected);
+    /// ```
+    pub fn merge(&mut self, other: Body) {
+        self.merge_with(other, MergeStrategy::Append);
+    }
+
+    /// Merges `other` into this body, using `strategy` to handle blocks that have the same
+    /// identifier and labels as a block in `self`.
+    ///
+    /// Attributes are handled like in [`merge`][Body::merge]. With
+    /// [`MergeStrategy::Recursive`], the n-th block of `other` with a given identifier and labels
+    /// is merged into the n-th block of `self` with the same identifier and labels, if any. This
+    /// keeps repeated blocks (e.g. multiple `ingress` blocks) distinct. Blocks without a
+    /// counterpart are appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hcl::{Block, Body};
+    /// use hcl::structure::MergeStrategy;
+    ///
+    /// let mut body = Body::builder()
+    ///     .add_block(
+    ///         Block::builder("resource")
+    ///             .add_label("a")
+    ///             .add_attribute(("foo", 1))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    ///
+    /// body.merge_with(
+    ///     Body::builder()
+    ///         .add_block(
+    ///             Block::builder("resource")
+    ///                 .add_label("a")
+    ///                 .add_attribute(("bar", 2))
+    ///                 .build(),
+    ///         )
+    ///         .build(),
+    ///     MergeStrategy::Recursive,
+    /// );
+    ///
+    /// let expected = Body::builder()
+    ///     .add_block(
+    ///         Block::builder("resource")
+    ///             .add_label("a")
+    ///             .add_attribute(("foo", 1))
+    ///             .add_attribute(("bar", 2))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    ///
+    /// assert_eq!(body, expected);
+    /// ```
+    pub fn merge_with(&mut self, other: Body, strategy: MergeStrategy) {
+        // Positions of the structures in `self` by key. Structures appended during the merge are
+        // recorded as well, so that later structures with the same key can be merged into them.
+        let mut positions: HashMap<StructureKey<'_>, Vec<usize>> = HashMap::new();
+
+        for (index, structure) in self.iter().enumerate() {
+            positions
+                .entry(StructureKey::new(structure))
+                .or_default()
+                .push(index);
+        }
+
+        // The number of preceding blocks with the same key in `other`.
+        let mut seen: HashMap<StructureKey<'_>, usize> = HashMap::new();
+        let mut len = self.0.len();
+
+        // The position in `self` each structure of `other` is merged into, or `None` if it is
+        // appended. Computed upfront because `other` is consumed and `self` is mutated below.
+        let targets: Vec<Option<usize>> = other
+            .iter()
+            .map(|structure| {
+                let key = StructureKey::new(structure);
+
+                let target = match (structure, strategy) {
+                    (Structure::Attribute(_), _) => {
+                        positions.get(&key).and_then(|indices| indices.first())
+                    }
+                    (Structure::Block(_), MergeStrategy::Append) => None,
+                    (Structure::Block(_), MergeStrategy::Recursive) => {
+                        let nth = seen.entry(key.clone()).or_default();
+                        let index = positions.get(&key).and_then(|indices| indices.get(*nth));
+                        *nth += 1;
+                        index
+                    }
+                }
+                .copied();
+
+                if target.is_none() {
+                    positions.entry(key).or_default().push(len);
+                    len += 1;
+                }
+
+                target
+            })
+            .collect();
+
+        for (structure, target) in other.into_iter().zip(targets) {
+            match (structure, target) {
+                (Structure::Attribute(attr), Some(index)) => {
+                    self.0[index] = Structure::Attribute(attr);
+                }
+                (Structure::Block(block), Some(index)) => {
+                    if let Structure::Block(existing) = &mut self.0[index] {
+                        existing.body.merge_with(block.body, strategy);
+                    }
+                }
+                (structure, None) => self.0.push(structure),
+            }
+        }
+    }
+}
+
+// The key of a structure. For attributes, the key is the attribute key. For blocks, the key
+// consists of identifier and labels. Quoted and bare identifier labels with the same content
+// produce equal keys.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum StructureKey<'a> {
+    Attribute(&'a str),
+    Block(&'a str, Vec<&'a str>),
+}
+
+impl<'a> StructureKey<'a> {
+    fn new(structure: &'a Structure) -> Self {
+        match structure {
+            Structure::Attribute(attr) => StructureKey::Attribute(&attr.key),
+            Structure::Block(block) => StructureKey::Block(
+                &block.identifier,
+                block.labels.iter().map(BlockLabel::as_str).collect(),
+            ),
+        }
+    }
+}
+
+// Returns `true` if `lhs` and `rhs` contain the same number of structures and every structure of
+// `lhs` is equal according to `eq` to the structure of `rhs` with the same key and the same number
+// of preceding structures with that key. The order of structures with different keys is
+// insignificant, the relative order of structures sharing a key is significant.
+fn eq_by_key<'a>(
+    mut lhs: impl Iterator<Item = &'a Structure>,
+    rhs: impl Iterator<Item = &'a Structure>,
+    eq: impl Fn(&Structure, &Structure) -> bool,
+) -> bool {
+    // Index the structures of `rhs` by key, preserving their relative order.
+    let mut index: HashMap<StructureKey<'_>, Vec<&Structure>> = HashMap::new();
+    let mut rhs_len = 0;
+
+    for structure in rhs {
+        index
+            .entry(StructureKey::new(structure))
+            .or_default()
+            .push(structure);
+        rhs_len += 1;
+    }
+
+    let mut seen: HashMap<StructureKey<'_>, usize> = HashMap::new();
+    let mut lhs_len = 0;
+
+    // Since each structure of `lhs` is matched to a distinct structure of `rhs`, this is a
+    // bijection if both sides have the same length.
+    lhs.all(|structure| {
+        lhs_len += 1;
+        let key = StructureKey::new(structure);
+        let nth = seen.entry(key.clone()).or_default();
+        let candidate = index.get(&key).and_then(|candidates| candidates.get(*nth));
+        *nth += 1;
+
+        candidate.is_some_and(|candidate| eq(structure, candidate))
+    }) && lhs_len == rhs_len
+}
+
+// Returns `true` if two blocks have the same identifier and labels. Quoted and bare identifier
+// labels with the same content are considered equal.
+pub(super) fn same_block_key(lhs: &Block, rhs: &Block) -> bool {
+    lhs.identifier == rhs.identifier
+        && lhs.labels.len() == rhs.labels.len()
+        && lhs
+            .labels
+            .iter()
+            .zip(&rhs.labels)
+            .all(|(lhs, rhs)| lhs.as_str() == rhs.as_str())
+}
+
+/// Controls how [`Body::merge_with`] handles blocks that have the same identifier and labels as
+/// a block in the body being merged into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Append all blocks, even if a block with the same identifier and labels already exists.
+    /// This is the default.
+    #[default]
+    Append,
+    /// Recursively merge the bodies of blocks with the same identifier and labels.
+    Recursive,
 }
 
 impl<T> From<T> for Body
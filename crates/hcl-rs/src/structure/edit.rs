@@ -1,5 +1,6 @@
 use super::*;
 use crate::edit::structure;
+use hcl_primitives::ident::is_ident;
 
 impl From<structure::Body> for Body {
     fn from(value: structure::Body) -> Self {
@@ -42,7 +43,16 @@ impl From<structure::Attribute> for Attribute {
 
 impl From<Attribute> for structure::Attribute {
     fn from(value: Attribute) -> Self {
-        structure::Attribute::new(value.key, value.expr)
+        let quoted = !is_ident(&value.key);
+        let mut attr = structure::Attribute::new(value.key, value.expr);
+
+        if quoted {
+            // Keys which are not valid identifiers were parsed from quoted attribute keys and
+            // must be written quoted again. Quoting a key never fails.
+            let _ = attr.set_quoted_key(true);
+        }
+
+        attr
     }
 }
 
@@ -1,6 +1,11 @@
-use super::{Block, Body, Structure};
-use crate::{Expression, Identifier, Map, Value};
+use super::{Attribute, Block, BlockLabel, Body, Structure};
+use crate::expr::{Object, ObjectKey, TemplateExpr};
+use crate::template::{Element, Template};
+use crate::util::is_templated;
+use crate::{Error, Expression, Identifier, Map, Result, Value};
+use hcl_primitives::template::unescape_markers;
 use indexmap::map::Entry;
+use std::str::FromStr;
 
 /// A trait to convert an HCL structure into its [JSON representation][json-spec].
 ///
@@ -135,3 +140,117 @@ impl JsonNode {
         }
     }
 }
+
+/// Converts a value that follows the HCL JSON specification into a `Body`.
+///
+/// This is the inverse of `IntoJsonSpec` for bodies, see `Body::from_json_value` for the exact
+/// mapping.
+pub(crate) fn body_from_json_value(value: Value) -> Result<Body> {
+    match value {
+        Value::Object(object) => body_from_json_object(object),
+        other => Err(Error::new(format!("expected a JSON object, got `{other}`"))),
+    }
+}
+
+fn body_from_json_object(object: Map<String, Value>) -> Result<Body> {
+    let mut structures = Vec::with_capacity(object.len());
+
+    for (key, value) in object {
+        let identifier = Identifier::new(key)?;
+
+        match value {
+            Value::Object(object) => {
+                blocks_from_json_object(&mut structures, &identifier, &mut Vec::new(), object)?;
+            }
+            Value::Array(array) if is_block_array(&array) => {
+                for value in array {
+                    let block = Block {
+                        identifier: identifier.clone(),
+                        labels: Vec::new(),
+                        body: body_from_json_value(value)?,
+                    };
+
+                    structures.push(block.into());
+                }
+            }
+            value => {
+                let expr = expr_from_json_value(value)?;
+                structures.push(Attribute::new(identifier, expr).into());
+            }
+        }
+    }
+
+    Ok(Body(structures))
+}
+
+// Nested objects whose values are all objects again are treated as labelling levels, every other
+// object is treated as a block body.
+fn blocks_from_json_object(
+    structures: &mut Vec<Structure>,
+    identifier: &Identifier,
+    labels: &mut Vec<BlockLabel>,
+    object: Map<String, Value>,
+) -> Result<()> {
+    let is_label_level = !object.is_empty() && object.values().all(Value::is_object);
+
+    if !is_label_level {
+        let block = Block {
+            identifier: identifier.clone(),
+            labels: labels.clone(),
+            body: body_from_json_object(object)?,
+        };
+
+        structures.push(block.into());
+        return Ok(());
+    }
+
+    for (label, value) in object {
+        if let Value::Object(object) = value {
+            labels.push(BlockLabel::from(label));
+            blocks_from_json_object(structures, identifier, labels, object)?;
+            labels.pop();
+        }
+    }
+
+    Ok(())
+}
+
+fn is_block_array(array: &[Value]) -> bool {
+    !array.is_empty() && array.iter().all(Value::is_object)
+}
+
+fn expr_from_json_value(value: Value) -> Result<Expression> {
+    let expr = match value {
+        Value::String(string) => expr_from_json_string(string)?,
+        Value::Array(array) => Expression::Array(
+            array
+                .into_iter()
+                .map(expr_from_json_value)
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(object) => Expression::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| Ok((ObjectKey::from(key), expr_from_json_value(value)?)))
+                .collect::<Result<Object<_, _>>>()?,
+        ),
+        other => Expression::from(other),
+    };
+
+    Ok(expr)
+}
+
+// Strings are templates in the HCL JSON specification. Templates which consist of a single
+// interpolation are unwrapped into the interpolated expression.
+fn expr_from_json_string(string: String) -> Result<Expression> {
+    if !is_templated(&string) {
+        return Ok(Expression::String(unescape_markers(&string).into_owned()));
+    }
+
+    let template = Template::from_str(&string)?;
+
+    match template.elements() {
+        [Element::Interpolation(interp)] => Ok(interp.expr.clone()),
+        _ => Ok(TemplateExpr::from(string).into()),
+    }
+}
@@ -56,13 +56,14 @@ mod ser;
 #[cfg(test)]
 mod tests;
 
-pub(crate) use self::json_spec::IntoJsonSpec;
+pub(crate) use self::json_spec::{IntoJsonSpec, JsonNode};
 pub use self::{
     attribute::Attribute,
     block::{Block, BlockBuilder, BlockLabel},
-    body::{Body, BodyBuilder},
+    body::{Body, BodyBuilder, MergeStrategy},
 };
-use crate::Value;
+use crate::expr::Expression;
+use crate::{Identifier, Value};
 use serde::Deserialize;
 
 /// Represents an HCL structure.
@@ -158,3 +159,13 @@ impl From<Block> for Structure {
         Structure::Block(block)
     }
 }
+
+impl<K, V> From<(K, V)> for Structure
+where
+    K: Into<Identifier>,
+    V: Into<Expression>,
+{
+    fn from(attr: (K, V)) -> Structure {
+        Structure::Attribute(attr.into())
+    }
+}
@@ -1,6 +1,7 @@
-use super::{Block, Body};
-use crate::expr::{Heredoc, HeredocStripMode, TemplateExpr, Traversal, Variable};
+use super::{Attribute, Block, Body, MergeStrategy, Structure};
+use crate::expr::{Expression, Heredoc, HeredocStripMode, TemplateExpr, Traversal, Variable};
 use crate::{value, Identifier, Value};
+use indoc::indoc;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -66,3 +67,516 @@ fn body_into_value() {
 
     assert_eq!(Value::from(body), expected);
 }
+
+#[test]
+fn body_into_value_json() {
+    let body = crate::parse(
+        r#"
+        terraform {
+          required_version = ">= 1.0"
+        }
+
+        resource "aws_security_group" "web" {
+          ingress {
+            from_port = 80
+          }
+
+          ingress {
+            from_port = 443
+          }
+        }
+
+        resource "aws_security_group" "db" {
+          ingress {
+            from_port = 5432
+          }
+        }
+
+        module "a" {
+          count = length(var.zones)
+        }
+
+        module "a" {
+          enabled = true
+          tags    = null
+        }
+        "#,
+    )
+    .unwrap();
+
+    let json = serde_json::to_value(body.into_value()).unwrap();
+
+    let expected = serde_json::json!({
+        "terraform": {
+            "required_version": ">= 1.0"
+        },
+        "resource": {
+            "aws_security_group": {
+                "web": {
+                    "ingress": [
+                        { "from_port": 80 },
+                        { "from_port": 443 }
+                    ]
+                },
+                "db": {
+                    "ingress": { "from_port": 5432 }
+                }
+            }
+        },
+        "module": {
+            "a": [
+                { "count": "${length(var.zones)}" },
+                { "enabled": true, "tags": null }
+            ]
+        }
+    });
+
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn body_from_json_value() {
+    let json = serde_json::json!({
+        "terraform": {
+            "required_version": ">= 1.0"
+        },
+        "variable": {
+            "zones": {
+                "type": "${list(string)}",
+                "default": ["a", "b"]
+            }
+        },
+        "resource": {
+            "aws_security_group": {
+                "web": {
+                    "name": "web-${var.env}",
+                    "description": "Literal $${marker}",
+                    "ingress": [
+                        { "from_port": 80 },
+                        { "from_port": 443 }
+                    ],
+                    "tags": [],
+                    "ports": [80, 443],
+                    "count": 2,
+                    "enabled": true,
+                    "owner": null
+                }
+            }
+        },
+        "locals": {}
+    });
+
+    let body = Body::from_json_value(crate::to_value(json).unwrap()).unwrap();
+
+    let expected = crate::parse(
+        r#"
+        terraform {
+          required_version = ">= 1.0"
+        }
+
+        variable "zones" {
+          type    = list(string)
+          default = ["a", "b"]
+        }
+
+        resource "aws_security_group" "web" {
+          name        = "web-${var.env}"
+          description = "Literal $${marker}"
+
+          ingress {
+            from_port = 80
+          }
+
+          ingress {
+            from_port = 443
+          }
+
+          tags    = []
+          ports   = [80, 443]
+          count   = 2
+          enabled = true
+          owner   = null
+        }
+
+        locals {}
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(body, expected);
+
+    assert!(Body::from_json_value(Value::from("foo")).is_err());
+    assert!(Body::from_json_value(crate::value!({ "not an ident" = 1 })).is_err());
+    assert!(Body::from_json_value(crate::value!({ attr = "${foo" })).is_err());
+}
+
+#[test]
+fn body_semantic_eq() {
+    let ingress = |port| {
+        Block::builder("ingress")
+            .add_attribute(("port", port))
+            .build()
+    };
+
+    let ordered = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_attribute(("bar", 2))
+        .add_block(
+            Block::builder("resource")
+                .add_label("aws_instance")
+                .add_label("a")
+                .add_attribute(("ami", "x"))
+                .add_block(ingress(80))
+                .add_block(ingress(443))
+                .build(),
+        )
+        .add_block(
+            Block::builder("resource")
+                .add_label("aws_instance")
+                .add_label("b")
+                .build(),
+        )
+        .build();
+
+    // Same structures, different order of attributes and blocks with different keys.
+    let reordered = Body::builder()
+        .add_block(
+            Block::builder("resource")
+                .add_label("aws_instance")
+                .add_label("b")
+                .build(),
+        )
+        .add_attribute(("bar", 2))
+        .add_block(
+            Block::builder("resource")
+                .add_label("aws_instance")
+                .add_label("a")
+                .add_block(ingress(80))
+                .add_attribute(("ami", "x"))
+                .add_block(ingress(443))
+                .build(),
+        )
+        .add_attribute(("foo", 1))
+        .build();
+
+    assert_ne!(ordered, reordered);
+    assert!(ordered.semantic_eq(&reordered));
+    assert!(reordered.semantic_eq(&ordered));
+
+    // The order of blocks with the same identifier and labels is significant.
+    let swapped = Body::builder()
+        .add_block(ingress(443))
+        .add_block(ingress(80))
+        .build();
+    let sorted = Body::builder()
+        .add_block(ingress(80))
+        .add_block(ingress(443))
+        .build();
+
+    assert!(!swapped.semantic_eq(&sorted));
+
+    // Differing attribute values or additional structures are detected.
+    let attr = Body::builder().add_attribute(("foo", 1)).build();
+    let changed_attr = Body::builder().add_attribute(("foo", 2)).build();
+    let extra_block = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_block(Block::new("foo"))
+        .build();
+
+    assert!(!attr.semantic_eq(&changed_attr));
+    assert!(!attr.semantic_eq(&extra_block));
+    assert!(!extra_block.semantic_eq(&attr));
+    assert!(attr.semantic_eq(&attr.clone()));
+}
+
+#[test]
+fn body_eq_ignore_order() {
+    let resource = |name, attrs: &[(&str, i64)]| {
+        let mut builder = Block::builder("resource").add_label("aws_instance");
+        builder = builder.add_label(name);
+
+        for (key, value) in attrs {
+            builder = builder.add_attribute((*key, *value));
+        }
+
+        builder.build()
+    };
+
+    let ordered = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_attribute(("bar", 2))
+        .add_block(resource("a", &[("count", 1), ("index", 0)]))
+        .add_block(resource("b", &[]))
+        .build();
+
+    // Attributes are reordered, also relative to blocks, at all nesting levels.
+    let reordered = Body::builder()
+        .add_attribute(("bar", 2))
+        .add_block(resource("a", &[("index", 0), ("count", 1)]))
+        .add_attribute(("foo", 1))
+        .add_block(resource("b", &[]))
+        .build();
+
+    assert_ne!(ordered, reordered);
+    assert!(ordered.eq_ignore_order(&reordered));
+    assert!(reordered.eq_ignore_order(&ordered));
+
+    // The order of blocks is significant, even if their keys differ.
+    let swapped_blocks = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_attribute(("bar", 2))
+        .add_block(resource("b", &[]))
+        .add_block(resource("a", &[("count", 1), ("index", 0)]))
+        .build();
+
+    assert!(!ordered.eq_ignore_order(&swapped_blocks));
+    assert!(ordered.semantic_eq(&swapped_blocks));
+
+    // Changed values are detected, also within nested blocks.
+    let changed_attr = Body::builder()
+        .add_attribute(("foo", 2))
+        .add_attribute(("bar", 2))
+        .add_block(resource("a", &[("count", 1), ("index", 0)]))
+        .add_block(resource("b", &[]))
+        .build();
+    let changed_nested = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_attribute(("bar", 2))
+        .add_block(resource("a", &[("count", 2), ("index", 0)]))
+        .add_block(resource("b", &[]))
+        .build();
+
+    assert!(!ordered.eq_ignore_order(&changed_attr));
+    assert!(!ordered.eq_ignore_order(&changed_nested));
+
+    // Attributes with duplicate keys must occur equally often and are matched in order, like in
+    // `semantic_eq`.
+    let dup = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_attribute(("foo", 1))
+        .build();
+    let dup_changed = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_attribute(("foo", 2))
+        .build();
+
+    let dup_reordered = Body::builder()
+        .add_attribute(("foo", 2))
+        .add_attribute(("foo", 1))
+        .build();
+
+    let dup_interleaved = Body::builder()
+        .add_attribute(("foo", 1))
+        .add_attribute(("bar", 2))
+        .add_attribute(("foo", 2))
+        .build();
+    let dup_interleaved_reordered = Body::builder()
+        .add_attribute(("bar", 2))
+        .add_attribute(("foo", 1))
+        .add_attribute(("foo", 2))
+        .build();
+
+    assert!(dup.eq_ignore_order(&dup.clone()));
+    assert!(!dup.eq_ignore_order(&dup_changed));
+    assert!(!dup_changed.eq_ignore_order(&dup_reordered));
+    assert!(!dup_changed.semantic_eq(&dup_reordered));
+    assert!(dup_interleaved.eq_ignore_order(&dup_interleaved_reordered));
+
+    // `eq_ignore_order` implies `semantic_eq`.
+    let bodies = [
+        &dup,
+        &dup_changed,
+        &dup_reordered,
+        &dup_interleaved,
+        &dup_interleaved_reordered,
+    ];
+
+    for lhs in bodies {
+        for rhs in bodies {
+            assert!(!lhs.eq_ignore_order(rhs) || lhs.semantic_eq(rhs));
+        }
+    }
+
+    // Blocks with different labels are not equal.
+    assert!(!resource("a", &[]).eq_ignore_order(&resource("b", &[])));
+}
+
+#[test]
+fn body_merge() {
+    let base = indoc! {r#"
+        region = "eu-west-1"
+        enabled = true
+
+        resource "aws_security_group" "web" {
+          name = "web"
+
+          ingress {
+            from_port = 80
+          }
+
+          ingress {
+            from_port = 443
+          }
+        }
+    "#};
+
+    let overlay = indoc! {r#"
+        enabled = false
+        tags = "web"
+        resource = "attr"
+
+        resource "aws_security_group" "web" {
+          description = "web ingress"
+
+          ingress {
+            to_port = 80
+          }
+        }
+    "#};
+
+    let parse = |input: &str| crate::parse(input).unwrap();
+
+    let mut appended = parse(base);
+    appended.merge(parse(overlay));
+
+    let expected = indoc! {r#"
+        region = "eu-west-1"
+        enabled = false
+
+        resource "aws_security_group" "web" {
+          name = "web"
+
+          ingress {
+            from_port = 80
+          }
+
+          ingress {
+            from_port = 443
+          }
+        }
+
+        tags = "web"
+        resource = "attr"
+
+        resource "aws_security_group" "web" {
+          description = "web ingress"
+
+          ingress {
+            to_port = 80
+          }
+        }
+    "#};
+
+    let formatted = crate::to_string(&appended).unwrap();
+    assert_eq!(formatted, expected);
+    assert_eq!(parse(&formatted), appended);
+
+    let mut merged = parse(base);
+    merged.merge_with(parse(overlay), MergeStrategy::Recursive);
+
+    // The first overlay `ingress` block is merged into the first base `ingress` block, the
+    // attribute named `resource` does not collide with the `resource` block.
+    let expected = indoc! {r#"
+        region = "eu-west-1"
+        enabled = false
+
+        resource "aws_security_group" "web" {
+          name = "web"
+
+          ingress {
+            from_port = 80
+            to_port = 80
+          }
+
+          ingress {
+            from_port = 443
+          }
+
+          description = "web ingress"
+        }
+
+        tags = "web"
+        resource = "attr"
+    "#};
+
+    let formatted = crate::to_string(&merged).unwrap();
+    assert_eq!(formatted, expected);
+    assert_eq!(parse(&formatted), merged);
+
+    // Merging an empty body is a no-op.
+    let mut unchanged = parse(base);
+    unchanged.merge_with(Body::default(), MergeStrategy::Recursive);
+    assert_eq!(unchanged, parse(base));
+
+    // Surplus repeated blocks are appended, each as a distinct block.
+    let mut surplus = parse("ingress { a = 1 }\n");
+    surplus.merge_with(
+        parse("ingress { b = 1 }\ningress { b = 2 }\ningress { b = 3 }\n"),
+        MergeStrategy::Recursive,
+    );
+
+    let expected = parse(indoc! {r"
+        ingress {
+          a = 1
+          b = 1
+        }
+
+        ingress {
+          b = 2
+        }
+
+        ingress {
+          b = 3
+        }
+    "});
+
+    assert_eq!(surplus, expected);
+}
+
+#[test]
+fn body_lookup() {
+    let mut body = crate::parse(indoc! {r#"
+        region = "eu-west-1"
+
+        provider "aws" {
+          region = var.region
+        }
+
+        variable "region" {}
+        variable "zone" {}
+    "#})
+    .unwrap();
+
+    // Duplicate keys are rejected by the parser, but representable in a `Body`: the first
+    // attribute wins.
+    body.0
+        .push(Structure::Attribute(Attribute::new("region", "us-east-1")));
+
+    let region = body.get_attribute("region").unwrap();
+    assert_eq!(region.expr, Expression::from("eu-west-1"));
+    assert!(body.get_attribute("provider").is_none());
+    assert!(body.get_attribute("zone").is_none());
+
+    body.get_attribute_mut("region").unwrap().expr = Expression::from("eu-central-1");
+    let regions: Vec<_> = body.attributes().map(|attr| &attr.expr).collect();
+    assert_eq!(
+        regions,
+        [
+            &Expression::from("eu-central-1"),
+            &Expression::from("us-east-1")
+        ]
+    );
+    assert!(body.get_attribute_mut("missing").is_none());
+
+    let provider = body.get_block("provider").unwrap();
+    assert_eq!(provider.labels()[0].as_str(), "aws");
+    assert!(provider.body().get_attribute("region").is_some());
+    assert!(body.get_block("region").is_none());
+
+    let variables: Vec<_> = body
+        .get_blocks("variable")
+        .map(|block| block.labels()[0].as_str())
+        .collect();
+    assert_eq!(variables, ["region", "zone"]);
+    assert_eq!(body.get_blocks("output").count(), 0);
+}
@@ -85,8 +85,9 @@
 mod edit;
 
 use crate::de::FromStrVisitor;
+use crate::eval::{Context, Evaluate};
 use crate::expr::{Expression, TemplateExpr};
-use crate::{format, parser, Error, Identifier, Result};
+use crate::{format, parser, Error, Identifier, Result, Value};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 use std::str::FromStr;
@@ -133,6 +134,55 @@ impl Template {
     pub fn elements_mut(&mut self) -> &mut [Element] {
         &mut self.elements
     }
+
+    /// Evaluates the template with the given variables and returns the rendered string.
+    ///
+    /// This is a shorthand for creating a [`Context`][crate::eval::Context], declaring all
+    /// variables in it and evaluating the template. The variables can be provided by anything
+    /// that yields name-value pairs, e.g. an array of tuples or a [`Map`][crate::Map]. No
+    /// functions are available via this shortcut, use
+    /// [`Evaluate::evaluate`][crate::eval::Evaluate::evaluate] with a custom `Context` to render
+    /// templates which call functions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template references undeclared variables or functions, or if
+    /// evaluating any of its elements fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use hcl::Template;
+    /// use std::str::FromStr;
+    ///
+    /// let template = Template::from_str("Hello ${name}, you have ${count} new messages.")?;
+    ///
+    /// let rendered = template.render([
+    ///     ("name", hcl::Value::from("Alice")),
+    ///     ("count", hcl::Value::from(3)),
+    /// ])?;
+    ///
+    /// assert_eq!(rendered, "Hello Alice, you have 3 new messages.");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn render<I, K, V>(&self, vars: I) -> Result<String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Identifier>,
+        V: Into<Value>,
+    {
+        let mut ctx = Context::new();
+
+        for (name, value) in vars {
+            ctx.declare_var(name, value);
+        }
+
+        self.evaluate(&ctx).map_err(Into::into)
+    }
 }
 
 // Builder methods.
@@ -324,6 +374,10 @@ impl From<ForDirective> for Directive {
 
 /// The template `if` directive is the template equivalent of the conditional expression, allowing
 /// selection of one of two sub-templates based on the condition result.
+///
+/// The condition can be any expression, including function calls and operations, as long as it
+/// evaluates to a boolean. When rendering the template, only the selected sub-template is
+/// evaluated, using the same context as the condition.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct IfDirective {
     /// The condition expression.
@@ -5,16 +5,25 @@ mod from;
 mod ser;
 
 use std::fmt;
+use std::str::FromStr;
 
 use serde::{de::DeserializeOwned, ser::Serialize};
 
 use self::{de::ValueDeserializer, ser::ValueSerializer};
-use crate::{format, Number, Result};
+use crate::eval::{Context, Evaluate};
+use crate::{edit, format, Error, Expression, Number, Result};
 
 /// The map type used for HCL objects.
 pub type Map<K, V> = indexmap::IndexMap<K, V>;
 
 /// Represents any valid HCL value.
+///
+/// The methods [`sort_keys_recursive`][Value::sort_keys_recursive],
+/// [`node_count`][Value::node_count], [`max_depth`][Value::max_depth] and
+/// [`flatten`][Value::flatten] traverse nested arrays and objects without recursion and can be
+/// used on arbitrarily deep values. This does not apply to other operations: dropping, cloning,
+/// comparing and formatting a `Value` are recursive and may overflow the stack for very deeply
+/// nested values.
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub enum Value {
     /// Represents a HCL null value.
@@ -195,11 +204,395 @@ impl Value {
     }
 
     /// Takes the value out of the `Value`, leaving a `Null` in its place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Value;
+    ///
+    /// let mut value = hcl::value!({ list = [1, 2] });
+    ///
+    /// let list = value.as_object_mut().unwrap().get_mut("list").unwrap().take();
+    ///
+    /// assert_eq!(list, hcl::value!([1, 2]));
+    /// assert_eq!(value, hcl::value!({ list = null }));
+    /// ```
     pub fn take(&mut self) -> Value {
         std::mem::replace(self, Value::Null)
     }
+
+    /// Replaces the value with `value`, returning the previous value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Value;
+    ///
+    /// let mut value = Value::from("old");
+    ///
+    /// assert_eq!(value.replace("new"), Value::from("old"));
+    /// assert_eq!(value, Value::from("new"));
+    /// ```
+    pub fn replace<T>(&mut self, value: T) -> Value
+    where
+        T: Into<Value>,
+    {
+        std::mem::replace(self, value.into())
+    }
+
+    /// Inserts a key-value pair into an object, returning the value previously stored under the
+    /// key, if any. A `Null` value is turned into an empty object first.
+    ///
+    /// Existing keys keep their position, new keys are appended to the end of the object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Value` is neither an object nor `Null`. The `Value` is left
+    /// unchanged in this case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::Value;
+    ///
+    /// let mut value = hcl::value!({ name = "web", count = 1 });
+    ///
+    /// assert_eq!(value.insert("count", 2)?, Some(Value::from(1)));
+    /// assert_eq!(value.insert("enabled", true)?, None);
+    ///
+    /// assert_eq!(value, hcl::value!({ name = "web", count = 2, enabled = true }));
+    ///
+    /// let mut value = Value::Null;
+    /// value.insert("key", "value")?;
+    ///
+    /// assert_eq!(value, hcl::value!({ key = "value" }));
+    ///
+    /// let mut value = hcl::value!([1, 2]);
+    /// assert!(value.insert("key", "value").is_err());
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Result<Option<Value>>
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        if self.is_null() {
+            *self = Value::Object(Map::new());
+        }
+
+        match self {
+            Value::Object(object) => Ok(object.insert(key.into(), value.into())),
+            other => Err(Error::new(format!(
+                "cannot insert a key into a non-object value of type `{}`",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Removes a key from an object, returning the value stored under the key, if any.
+    ///
+    /// The order of the remaining keys is preserved. Returns `None` if the `Value` is not an
+    /// object.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Value;
+    ///
+    /// let mut value = hcl::value!({ a = 1, b = 2, c = 3 });
+    ///
+    /// assert_eq!(value.remove("a"), Some(Value::from(1)));
+    /// assert_eq!(value.remove("a"), None);
+    ///
+    /// assert_eq!(value, hcl::value!({ b = 2, c = 3 }));
+    /// ```
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.as_object_mut()
+            .and_then(|object| object.shift_remove(key))
+    }
+
+    /// Appends a value to the back of an array. A `Null` value is turned into an empty array
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Value` is neither an array nor `Null`. The `Value` is left
+    /// unchanged in this case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hcl::Value;
+    ///
+    /// let mut value = hcl::value!({ ports = [80] });
+    ///
+    /// let ports = value.as_object_mut().unwrap().get_mut("ports").unwrap();
+    /// ports.push(443)?;
+    ///
+    /// assert_eq!(value, hcl::value!({ ports = [80, 443] }));
+    ///
+    /// let mut value = Value::Null;
+    /// value.push("first")?;
+    ///
+    /// assert_eq!(value, hcl::value!(["first"]));
+    ///
+    /// let mut value = hcl::value!({ key = "value" });
+    /// assert!(value.push("second").is_err());
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn push<T>(&mut self, value: T) -> Result<()>
+    where
+        T: Into<Value>,
+    {
+        if self.is_null() {
+            *self = Value::Array(Vec::new());
+        }
+
+        match self {
+            Value::Array(array) => {
+                array.push(value.into());
+                Ok(())
+            }
+            other => Err(Error::new(format!(
+                "cannot push onto a non-array value of type `{}`",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Sorts the keys of all objects within the `Value` lexicographically, including objects
+    /// nested in arrays and other objects.
+    ///
+    /// This is useful to produce stable output independent of the order in which the value was
+    /// constructed. Arrays keep the order of their elements. See
+    /// [`canonicalize`][crate::format::canonicalize] for a fully canonical HCL representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Value;
+    ///
+    /// let mut value = hcl::value!({
+    ///     b = [{ z = 1, y = 2 }, 3]
+    ///     a = { d = 4, c = 5 }
+    /// });
+    ///
+    /// value.sort_keys_recursive();
+    ///
+    /// let object = value.as_object().unwrap();
+    /// assert!(object.keys().eq(["a", "b"]));
+    ///
+    /// let nested = object["b"].as_array().unwrap()[0].as_object().unwrap();
+    /// assert!(nested.keys().eq(["y", "z"]));
+    /// ```
+    pub fn sort_keys_recursive(&mut self) {
+        let mut stack = vec![self];
+
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Array(array) => stack.extend(array),
+                Value::Object(object) => {
+                    object.sort_keys();
+                    stack.extend(object.values_mut());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the total number of values contained in the `Value`, including the `Value` itself.
+    ///
+    /// Every array element and object value counts as a node. Object keys are not counted
+    /// separately.
+    ///
+    /// Together with [`max_depth`][Value::max_depth] this can be used to reject overly large
+    /// values, e.g. when processing untrusted input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Value;
+    ///
+    /// assert_eq!(Value::from(1).node_count(), 1);
+    ///
+    /// let value = Value::from_iter([("a", Value::from_iter([1, 2])), ("b", Value::Null)]);
+    /// assert_eq!(value.node_count(), 5);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut stack = vec![self];
+
+        while let Some(value) = stack.pop() {
+            count += 1;
+
+            match value {
+                Value::Array(array) => stack.extend(array),
+                Value::Object(object) => stack.extend(object.values()),
+                _ => {}
+            }
+        }
+
+        count
+    }
+
+    /// Returns the maximum nesting depth of arrays and objects within the `Value`.
+    ///
+    /// Scalar values (`null`, booleans, numbers and strings) have a depth of `0`. Each level of
+    /// array or object nesting increases the depth by one, so `[]` and `{}` have a depth of `1`
+    /// and `[[1]]` has a depth of `2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Value;
+    ///
+    /// assert_eq!(Value::from("foo").max_depth(), 0);
+    ///
+    /// let value = Value::from_iter([("a", Value::from_iter([1, 2])), ("b", Value::Null)]);
+    /// assert_eq!(value.max_depth(), 2);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = vec![(self, 0)];
+
+        while let Some((value, depth)) = stack.pop() {
+            let child_depth = depth + 1;
+
+            match value {
+                Value::Array(array) => stack.extend(array.iter().map(|v| (v, child_depth))),
+                Value::Object(object) => stack.extend(object.values().map(|v| (v, child_depth))),
+                _ => continue,
+            }
+
+            max_depth = max_depth.max(child_depth);
+        }
+
+        max_depth
+    }
+
+    /// Flattens nested arrays into a single array, similar to the `flatten` function of the
+    /// Terraform standard library.
+    ///
+    /// The `depth` controls how many levels of nesting are removed: `Some(1)` only flattens the
+    /// arrays directly contained in `self`, while `None` flattens arrays of any depth. `Some(0)`
+    /// leaves the value unchanged. Elements which are not arrays are kept in place, and empty
+    /// nested arrays within the flattened depth disappear from the result.
+    ///
+    /// If `self` is not an array, a clone of it is returned.
+    ///
+    /// Arrays of any depth can be flattened. Elements which are not flattened are cloned, which
+    /// like dropping a `Value` is recursive, see the [type-level documentation][Value].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hcl::Value;
+    ///
+    /// let value = hcl::value!([1, [2, [3, [4]]], [], { a = [5] }]);
+    ///
+    /// assert_eq!(value.flatten(Some(1)), hcl::value!([1, 2, [3, [4]], { a = [5] }]));
+    /// assert_eq!(value.flatten(None), hcl::value!([1, 2, 3, 4, { a = [5] }]));
+    ///
+    /// assert_eq!(Value::from("foo").flatten(None), Value::from("foo"));
+    /// ```
+    pub fn flatten(&self, depth: Option<usize>) -> Value {
+        let Value::Array(array) = self else {
+            return self.clone();
+        };
+
+        let depth = depth.unwrap_or(usize::MAX);
+        let mut flattened = Vec::with_capacity(array.len());
+        let mut stack: Vec<_> = array.iter().rev().map(|value| (value, 0)).collect();
+
+        while let Some((value, level)) = stack.pop() {
+            match value {
+                Value::Array(nested) if level < depth => {
+                    stack.extend(nested.iter().rev().map(|value| (value, level + 1)));
+                }
+                value => flattened.push(value.clone()),
+            }
+        }
+
+        Value::Array(flattened)
+    }
+
+    // Returns the name of the value's type for use in error messages.
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "bool",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+}
+
+/// Parses a single HCL expression and evaluates it into a `Value`.
+///
+/// The expression is evaluated with an empty [`Context`], so only constant expressions can be
+/// parsed successfully. Expressions that reference variables or call functions produce an error.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use hcl::Value;
+///
+/// let value: Value = "[1, 2, 3]".parse()?;
+/// assert_eq!(value, Value::Array(vec![1.into(), 2.into(), 3.into()]));
+///
+/// let value: Value = r#""Hello, ${"World"}!""#.parse()?;
+/// assert_eq!(value, Value::String("Hello, World!".into()));
+///
+/// let value: Value = "{ a = 1 + 1 }".parse()?;
+/// assert_eq!(value, Value::from_iter([("a", 2)]));
+///
+/// assert!("var.foo".parse::<Value>().is_err());
+/// #   Ok(())
+/// # }
+/// ```
+impl FromStr for Value {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expr: edit::expr::Expression = s.parse()?;
+        let value = Expression::from(expr).evaluate(&Context::new())?;
+        Ok(value)
+    }
 }
 
+/// Formats the value as HCL.
+///
+/// The output is the same as [`hcl::format::to_string`][crate::format::to_string] produces.
+/// Object entries are written in insertion order, which makes the output deterministic.
+///
+/// # Example
+///
+/// ```
+/// let value = hcl::value!({
+///     name = "app"
+///     ports = [80, 443]
+/// });
+///
+/// let expected = r#"
+/// {
+///   "name" = "app"
+///   "ports" = [
+///     80,
+///     443
+///   ]
+/// }
+/// "#.trim();
+///
+/// assert_eq!(value.to_string(), expected);
+/// assert_eq!(format!("{value}"), hcl::format::to_string(&value).unwrap());
+/// ```
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Formatting a `Value` as string cannot fail.
@@ -297,3 +690,124 @@ where
 {
     T::deserialize(ValueDeserializer::new(value))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_count_and_max_depth() {
+        let scalar = Value::from(true);
+        assert_eq!(scalar.node_count(), 1);
+        assert_eq!(scalar.max_depth(), 0);
+
+        let empty = Value::Array(Vec::new());
+        assert_eq!(empty.node_count(), 1);
+        assert_eq!(empty.max_depth(), 1);
+
+        let wide: Value = (0..1000).map(|i| (i.to_string(), Value::from(i))).collect();
+        assert_eq!(wide.node_count(), 1001);
+        assert_eq!(wide.max_depth(), 1);
+
+        let mut deep = Value::Null;
+        for _ in 0..100_000 {
+            deep = Value::Array(vec![deep]);
+        }
+        assert_eq!(deep.node_count(), 100_001);
+        assert_eq!(deep.max_depth(), 100_000);
+
+        // Dropping a `Value` is recursive, so take the deeply nested value apart iteratively to
+        // avoid overflowing the stack.
+        while let Value::Array(mut array) = deep {
+            deep = array.pop().unwrap();
+        }
+    }
+
+    #[test]
+    fn insert_into_array() {
+        let mut value = Value::Array(Vec::new());
+        assert_eq!(
+            value.insert("key", 1).unwrap_err().to_string(),
+            "cannot insert a key into a non-object value of type `array`"
+        );
+        assert_eq!(value, Value::Array(Vec::new()));
+    }
+
+    #[test]
+    fn push_onto_object() {
+        let mut value = Value::Object(Map::new());
+        assert_eq!(
+            value.push(1).unwrap_err().to_string(),
+            "cannot push onto a non-array value of type `object`"
+        );
+        assert_eq!(value, Value::Object(Map::new()));
+    }
+
+    #[test]
+    fn sort_keys_recursive() {
+        fn keys(value: &Value) -> Vec<&str> {
+            value
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .collect()
+        }
+
+        let mut value = crate::value!({
+            c = { z = [{ b = 1, a = 2 }], y = {} }
+            a = [3, 1, 2]
+            b = { e = { g = 1, f = 2 }, d = null }
+        });
+
+        value.sort_keys_recursive();
+
+        let object = value.as_object().unwrap();
+        let b = object["b"].as_object().unwrap();
+        let c = object["c"].as_object().unwrap();
+
+        assert_eq!(keys(&value), ["a", "b", "c"]);
+        assert_eq!(keys(&object["b"]), ["d", "e"]);
+        assert_eq!(keys(&b["e"]), ["f", "g"]);
+        assert_eq!(keys(&object["c"]), ["y", "z"]);
+        assert_eq!(keys(&c["z"].as_array().unwrap()[0]), ["a", "b"]);
+        assert_eq!(object["a"], crate::value!([3, 1, 2]));
+    }
+
+    #[test]
+    fn flatten() {
+        let value = crate::value!([[1, [2]], 3, [[[4, []]], "5"], [], null]);
+
+        assert_eq!(value.flatten(Some(0)), value);
+        assert_eq!(
+            value.flatten(Some(1)),
+            crate::value!([1, [2], 3, [[4, []]], "5", null])
+        );
+        assert_eq!(
+            value.flatten(Some(2)),
+            crate::value!([1, 2, 3, [4, []], "5", null])
+        );
+        assert_eq!(value.flatten(None), crate::value!([1, 2, 3, 4, "5", null]));
+        assert_eq!(value.flatten(Some(100)), value.flatten(None));
+
+        let object = crate::value!({ a = [[1]] });
+        assert_eq!(object.flatten(None), object);
+
+        let mut deep = Value::from(1);
+        for _ in 0..100_000 {
+            deep = Value::Array(vec![deep]);
+        }
+        assert_eq!(deep.flatten(None), crate::value!([1]));
+
+        // Dropping a `Value` is recursive, so take the deeply nested value apart iteratively to
+        // avoid overflowing the stack.
+        while let Value::Array(mut array) = deep {
+            deep = array.pop().unwrap();
+        }
+    }
+
+    #[test]
+    fn remove_from_non_object() {
+        assert_eq!(Value::from("key").remove("key"), None);
+    }
+}
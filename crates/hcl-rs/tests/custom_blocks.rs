@@ -283,3 +283,147 @@ fn custom_terraform_blocks() {
 
     assert_serialize(config, expected);
 }
+
+#[test]
+fn nested_config_with_block_wrapper_types() {
+    use hcl::ser::{Block, LabeledBlock};
+    use indexmap::IndexMap;
+
+    #[derive(Serialize)]
+    struct Config {
+        terraform: Block<Terraform>,
+        provider: LabeledBlock<IndexMap<String, Provider>>,
+        variable: LabeledBlock<IndexMap<String, InputVariable>>,
+        resource: LabeledBlock<IndexMap<String, LabeledBlock<IndexMap<String, Instance>>>>,
+    }
+
+    #[derive(Serialize)]
+    struct Terraform {
+        required_version: String,
+        required_providers: Block<IndexMap<String, ProviderRequirement>>,
+    }
+
+    #[derive(Serialize)]
+    struct ProviderRequirement {
+        source: String,
+        version: String,
+    }
+
+    #[derive(Serialize)]
+    struct Provider {
+        region: Expression,
+    }
+
+    #[derive(Serialize)]
+    struct InputVariable {
+        #[serde(rename = "type")]
+        type_: Expression,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Instance {
+        ami: String,
+        instance_type: Expression,
+        tags: IndexMap<String, String>,
+        ebs_block_device: Block<Vec<EbsBlockDevice>>,
+    }
+
+    #[derive(Serialize)]
+    struct EbsBlockDevice {
+        device_name: String,
+        volume_size: u32,
+    }
+
+    let var = |name: &str| -> Expression {
+        Traversal::builder(Variable::new("var").unwrap())
+            .attr(name)
+            .build()
+            .into()
+    };
+
+    let config = Config {
+        terraform: Block::new(Terraform {
+            required_version: ">= 1.5".into(),
+            required_providers: Block::new(indexmap! {
+                "aws".into() => ProviderRequirement {
+                    source: "hashicorp/aws".into(),
+                    version: "~> 5.0".into(),
+                },
+            }),
+        }),
+        provider: LabeledBlock::new(indexmap! {
+            "aws".into() => Provider { region: var("region") },
+        }),
+        variable: LabeledBlock::new(indexmap! {
+            "region".into() => InputVariable {
+                type_: Variable::new("string").unwrap().into(),
+                default: Some("eu-west-1".into()),
+            },
+            "instance_type".into() => InputVariable {
+                type_: Variable::new("string").unwrap().into(),
+                default: None,
+            },
+        }),
+        resource: LabeledBlock::new(indexmap! {
+            "aws_instance".into() => LabeledBlock::new(indexmap! {
+                "web".into() => Instance {
+                    ami: "ami-0123456789".into(),
+                    instance_type: var("instance_type"),
+                    tags: indexmap! { "Name".into() => "web".into() },
+                    ebs_block_device: Block::new(vec![
+                        EbsBlockDevice { device_name: "/dev/sdb".into(), volume_size: 10 },
+                        EbsBlockDevice { device_name: "/dev/sdc".into(), volume_size: 20 },
+                    ]),
+                },
+            }),
+        }),
+    };
+
+    let expected = indoc! {r#"
+        terraform {
+          required_version = ">= 1.5"
+
+          required_providers {
+            aws = {
+              "source" = "hashicorp/aws"
+              "version" = "~> 5.0"
+            }
+          }
+        }
+
+        provider "aws" {
+          region = var.region
+        }
+
+        variable "region" {
+          type = string
+          default = "eu-west-1"
+        }
+
+        variable "instance_type" {
+          type = string
+        }
+
+        resource "aws_instance" "web" {
+          ami = "ami-0123456789"
+          instance_type = var.instance_type
+          tags = {
+            "Name" = "web"
+          }
+
+          ebs_block_device {
+            device_name = "/dev/sdb"
+            volume_size = 10
+          }
+
+          ebs_block_device {
+            device_name = "/dev/sdc"
+            volume_size = 20
+          }
+        }
+    "#};
+
+    assert_serialize(config, expected);
+}
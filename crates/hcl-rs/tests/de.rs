@@ -1,12 +1,14 @@
 mod common;
 
 use common::assert_deserialize;
+use hcl::de::Deserializer;
 use hcl::expr::{
     BinaryOp, BinaryOperator, Expression, ForExpr, FuncCall, Heredoc, HeredocStripMode, ObjectKey,
     Operation, TemplateExpr, Traversal, UnaryOp, UnaryOperator, Variable,
 };
 use hcl::structure::{Block, Body};
 use hcl::{Identifier, Value};
+use indoc::indoc;
 use serde::Deserialize;
 use std::fmt::Debug;
 
@@ -458,3 +460,297 @@ fn terraform() {
 
     assert_deserialize(input, expected);
 }
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[test]
+fn rfc3339_timestamps() {
+    #[derive(Deserialize, Debug)]
+    #[serde(bound = "T: hcl::de::FromRfc3339")]
+    struct Config<T> {
+        #[serde(deserialize_with = "hcl::de::rfc3339")]
+        created_at: T,
+    }
+
+    let input = r#"created_at = "2024-01-31T12:30:00.5+01:00""#;
+
+    #[cfg(feature = "chrono")]
+    {
+        use chrono::{DateTime, FixedOffset, Utc};
+
+        let config: Config<DateTime<FixedOffset>> = hcl::from_str(input).unwrap();
+        assert_eq!(
+            config.created_at.to_rfc3339(),
+            "2024-01-31T12:30:00.500+01:00"
+        );
+
+        let config: Config<DateTime<Utc>> = hcl::from_str(input).unwrap();
+        assert_eq!(
+            config.created_at.to_rfc3339(),
+            "2024-01-31T11:30:00.500+00:00"
+        );
+
+        let err = hcl::from_str::<Config<DateTime<Utc>>>(r#"created_at = "2024-01-31""#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("invalid RFC 3339 timestamp `2024-01-31`"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "time")]
+    {
+        use time::OffsetDateTime;
+
+        let config: Config<OffsetDateTime> = hcl::from_str(input).unwrap();
+        assert_eq!(config.created_at.unix_timestamp(), 1_706_700_600);
+        assert_eq!(config.created_at.millisecond(), 500);
+        assert_eq!(config.created_at.offset().whole_hours(), 1);
+
+        let err = hcl::from_str::<Config<OffsetDateTime>>(r#"created_at = "yesterday""#)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("invalid RFC 3339 timestamp `yesterday`"),
+            "{err}"
+        );
+    }
+}
+
+#[test]
+fn unknown_fields() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Lenient {
+        name: String,
+        inner: Inner,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Inner {
+        a: u32,
+    }
+
+    let input = indoc! {r#"
+        name = "app"
+        nmae = "typo"
+        inner {
+          a = 1
+        }
+    "#};
+
+    let expected = Lenient {
+        name: "app".into(),
+        inner: Inner { a: 1 },
+    };
+
+    // Unknown attributes are ignored by default.
+    assert_deserialize(input, expected);
+
+    // Strict mode rejects unknown top-level attributes and blocks.
+    let strict = |input| Lenient::deserialize(Deserializer::from_str(input).unwrap().strict(true));
+
+    assert_eq!(
+        strict(input).unwrap_err().to_string(),
+        "unknown field `nmae`, expected `name` or `inner`"
+    );
+
+    assert_eq!(
+        strict("name = \"app\"\ninner {\n  a = 1\n}\nextra \"label\" {}\n")
+            .unwrap_err()
+            .to_string(),
+        "unknown field `extra`, expected `name` or `inner`"
+    );
+
+    // Nested structs are subject to `deny_unknown_fields`.
+    let err =
+        hcl::from_str::<Lenient>("name = \"app\"\ninner {\n  a = 1\n  b = 2\n}\n").unwrap_err();
+    assert_eq!(err.to_string(), "unknown field `b`, expected `a`");
+}
+
+#[test]
+fn map_targets() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let input = indoc! {r#"
+        a = 1
+        b = { c = [1, "two", null], d = { e = true } }
+
+        resource "aws_s3_bucket" "logs" {
+          tags = { env = "prod" }
+        }
+    "#};
+
+    let expected_a = Value::from(1);
+    let expected_b = hcl::value!({ c = [1, "two", null], d = { e = true } });
+    let expected_resource = hcl::value!({ aws_s3_bucket = { logs = { tags = { env = "prod" } } } });
+
+    let map: HashMap<String, Value> = hcl::from_str(input).unwrap();
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["a"], expected_a);
+    assert_eq!(map["b"], expected_b);
+    assert_eq!(map["resource"], expected_resource);
+
+    let map: BTreeMap<String, serde_json::Value> = hcl::from_str(input).unwrap();
+
+    let expected: BTreeMap<String, serde_json::Value> = BTreeMap::from([
+        (String::from("a"), serde_json::json!(1)),
+        (
+            String::from("b"),
+            serde_json::json!({ "c": [1, "two", null], "d": { "e": true } }),
+        ),
+        (
+            String::from("resource"),
+            serde_json::json!({ "aws_s3_bucket": { "logs": { "tags": { "env": "prod" } } } }),
+        ),
+    ]);
+
+    assert_eq!(map, expected);
+
+    // Maps with typed values work as long as all values have the expected type.
+    assert_deserialize(
+        "a = 1\nb = 2",
+        BTreeMap::from([(String::from("a"), 1), (String::from("b"), 2)]),
+    );
+    assert!(hcl::from_str::<HashMap<String, u32>>("a = 1\nb = \"two\"").is_err());
+}
+
+#[test]
+fn invalid_attribute_type() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+        port: u16,
+        server: Server,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    let err = hcl::from_str::<Config>(indoc! {r#"
+        name = "app"
+        port = "8080"
+        server {
+          host = "localhost"
+          port = 8080
+        }
+    "#})
+    .unwrap_err();
+
+    match &err {
+        hcl::Error::InvalidAttribute { key, source } => {
+            assert_eq!(key, "port");
+            assert_eq!(
+                source.to_string(),
+                "invalid type: string \"8080\", expected u16"
+            );
+        }
+        err => panic!("unexpected error: {err:?}"),
+    }
+
+    assert_eq!(
+        err.to_string(),
+        "invalid value for attribute `port`: invalid type: string \"8080\", expected u16"
+    );
+
+    // Errors within blocks are reported with the path of the attribute.
+    let err = hcl::from_str::<Config>(indoc! {r#"
+        name = "app"
+        port = 8080
+        server {
+          host = "localhost"
+          port = true
+        }
+    "#})
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid value for attribute `server.port`: invalid type: boolean `true`, expected u16"
+    );
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Services {
+        service: std::collections::HashMap<String, Vec<Server>>,
+    }
+
+    let err = hcl::from_str::<Services>(indoc! {r#"
+        service "web" {
+          host = "a"
+          port = 80
+        }
+
+        service "web" {
+          host = "b"
+          port = -1
+        }
+    "#})
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid value for attribute `service.web[1].port`: invalid value: integer `-1`, expected u16"
+    );
+}
+
+#[test]
+fn flatten() {
+    use std::collections::HashMap;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Config {
+        name: String,
+        #[serde(flatten)]
+        network: Network,
+        #[serde(flatten)]
+        extra: HashMap<String, Value>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Network {
+        host: String,
+        port: u16,
+    }
+
+    let input = indoc! {r#"
+        name = "app"
+        host = "localhost"
+        port = 8080
+        replicas = 1 + 2
+        labels = ["a", "b"]
+
+        volume "data" {
+          size = 10
+        }
+    "#};
+
+    let expected = Config {
+        name: "app".into(),
+        network: Network {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        extra: HashMap::from([
+            ("replicas".into(), Value::from("${1 + 2}")),
+            ("labels".into(), hcl::value!(["a", "b"])),
+            ("volume".into(), hcl::value!({ data = { size = 10 } })),
+        ]),
+    };
+
+    assert_eq!(hcl::from_str::<Config>(input).unwrap(), expected);
+
+    let err =
+        hcl::from_str::<Config>("name = \"app\"\nhost = \"localhost\"\nport = true\n").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid type: boolean `true`, expected u16"
+    );
+}
@@ -3,8 +3,8 @@ mod common;
 use common::{assert_eval, assert_eval_ctx, assert_eval_error};
 use hcl::eval::{Context, ErrorKind, EvalResult, Evaluate, FuncArgs, FuncDef, ParamType};
 use hcl::expr::{
-    BinaryOp, BinaryOperator, Conditional, Expression, ForExpr, FuncCall, TemplateExpr, Traversal,
-    TraversalOperator, Variable,
+    BinaryOp, BinaryOperator, Conditional, Expression, ForExpr, FuncCall, RawExpression,
+    TemplateExpr, Traversal, TraversalOperator, Variable,
 };
 use hcl::structure::Body;
 use hcl::template::Template;
@@ -21,7 +21,7 @@ fn eval_binary_op() {
             Mul,
             BinaryOp::new(3, Plus, BinaryOp::new(4, Div, 5)),
         ),
-        Value::from(2.3),
+        Value::from(1.9),
     );
     assert_eval(BinaryOp::new("foo", Eq, "foo"), Value::from(true));
     assert_eval(BinaryOp::new(false, Or, true), Value::from(true));
@@ -38,6 +38,99 @@ fn eval_binary_op() {
     );
 }
 
+#[test]
+fn eval_parsed_binary_op() {
+    let body = hcl::parse(indoc! {r#"
+        a = 10 - 3 - 2
+        b = 8 / 4 / 2
+        c = 1 + 2 * 3 - 4
+        d = 1 == 1 ? "yes" : "no"
+    "#})
+    .unwrap();
+
+    assert_eval(
+        body,
+        Body::builder()
+            .add_attribute(("a", 5))
+            .add_attribute(("b", 1))
+            .add_attribute(("c", 3))
+            .add_attribute(("d", "yes"))
+            .build(),
+    );
+}
+
+#[test]
+fn eval_comparison_ops() {
+    use BinaryOperator::*;
+
+    let ctx = Context::new();
+
+    let cases = [
+        ("1 == 1", true),
+        ("1 == 1.0", true),
+        ("1 == 2", false),
+        ("1 != 2", true),
+        ("1 != 1.0", false),
+        ("1 < 2", true),
+        ("2 < 2", false),
+        ("-1.5 < -1", true),
+        ("1 <= 2", true),
+        ("2 <= 2", true),
+        ("2.5 <= 2", false),
+        ("2 > 1", true),
+        ("2 > 2", false),
+        ("2 > 1.5", true),
+        ("2 >= 1", true),
+        ("2 >= 2.0", true),
+        ("1 >= 2", false),
+        // Operators without surrounding whitespace.
+        ("1<=2", true),
+        ("2>=3", false),
+        ("1<-1", false),
+        ("1>=-1", true),
+        ("1!=1", false),
+        // Comparisons bind weaker than arithmetic operators.
+        ("1 + 1 <= 2 * 1", true),
+        ("3 % 2 >= 1", true),
+        ("1 < 2 == 2 > 1", true),
+        // Equality is defined for values of any type.
+        (r#""1" == 1"#, false),
+        ("null != false", true),
+        ("[1, { a = 2 }] == [1, { a = 2 }]", true),
+    ];
+
+    for (expr, expected) in cases {
+        assert_eq!(
+            ctx.evaluate_str(expr).unwrap(),
+            Value::from(expected),
+            "unexpected result for `{expr}`"
+        );
+    }
+
+    // Ordering comparisons are only defined for numbers.
+    let errors = [
+        (r#""a" < "b""#, Value::from("a"), Less, Value::from("b")),
+        (r#"1 <= "2""#, Value::from(1), LessEq, Value::from("2")),
+        (
+            "true > false",
+            Value::from(true),
+            Greater,
+            Value::from(false),
+        ),
+        ("null >= 1", Value::Null, GreaterEq, Value::from(1)),
+        ("[1] < [2]", hcl::value!([1]), Less, hcl::value!([2])),
+    ];
+
+    for (expr, lhs, operator, rhs) in errors {
+        match ctx.evaluate_str(expr).unwrap_err() {
+            hcl::Error::Eval(err) => {
+                assert_eq!(err.kind(), &ErrorKind::BinaryOp(lhs, operator, rhs));
+            }
+            err => panic!("unexpected error for `{expr}`: {err}"),
+        }
+    }
+}
+
 #[test]
 fn eval_conditional() {
     assert_eval(Conditional::new(true, "yes", "no"), Value::from("yes"));
@@ -48,6 +141,151 @@ fn eval_conditional() {
     );
 }
 
+#[test]
+fn eval_nested_conditional() {
+    let mut ctx = Context::new();
+    ctx.declare_var("t", true);
+    ctx.declare_var("f", false);
+
+    let cases = [
+        // Nested conditionals are right-associative.
+        ("t ? t ? 1 : 2 : 3", 1),
+        ("t ? f ? 1 : 2 : 3", 2),
+        ("f ? f ? 1 : 2 : 3", 3),
+        ("f ? 1 : t ? 2 : 3", 2),
+        ("f ? 1 : f ? 2 : 3", 3),
+        ("f ? 1 : f ? 2 : t ? 3 : 4", 3),
+        ("t ? f ? 1 : t ? 2 : 3 : 4", 2),
+        ("(t ? f : t) ? 1 : 2", 2),
+        // Conditionals bind weaker than any other operator.
+        ("t && f ? 1 : 2", 2),
+        ("f || t ? 1 + 1 : 3", 2),
+        ("t ? 1 : 2 == 2 ? 3 : 4", 1),
+        ("f ? 1 : 2 == 2 ? 3 : 4", 3),
+    ];
+
+    for (expr, expected) in cases {
+        assert_eq!(
+            ctx.evaluate_str(expr).unwrap(),
+            Value::from(expected),
+            "unexpected result for `{expr}`"
+        );
+    }
+
+    // Only the selected branch is evaluated.
+    assert_eq!(
+        ctx.evaluate_str("t ? f ? undefined : 2 : undefined")
+            .unwrap(),
+        Value::from(2)
+    );
+}
+
+#[test]
+fn eval_conditional_non_bool_predicate() {
+    assert_eval(
+        Conditional::new(BinaryOp::new(1, BinaryOperator::Less, 2), "yes", "no"),
+        Value::from("yes"),
+    );
+    assert_eval_error(
+        Conditional::new(1, "yes", "no"),
+        ErrorKind::Unexpected(Value::from(1), "a boolean"),
+    );
+    assert_eval_error(
+        Conditional::new(BinaryOp::new(1, BinaryOperator::Plus, 1), "yes", "no"),
+        ErrorKind::Unexpected(Value::from(2), "a boolean"),
+    );
+    assert_eval_error(
+        Conditional::new("true", "yes", "no"),
+        ErrorKind::Unexpected(Value::from("true"), "a boolean"),
+    );
+    assert_eval_error(
+        Conditional::new(Expression::Null, "yes", "no"),
+        ErrorKind::Unexpected(Value::Null, "a boolean"),
+    );
+}
+
+#[test]
+fn eval_str() {
+    let mut ctx = Context::new();
+    ctx.declare_var("var", hcl::value!({ names = ["a", "b"] }));
+
+    assert_eq!(
+        ctx.evaluate_str(r#"join("-", [for n in var.names : upper(n)])"#)
+            .unwrap_err()
+            .to_string(),
+        "eval error: undefined function `join` in expression `join(\"-\", [for n in var.names : upper(n)])`",
+    );
+    assert_eq!(
+        ctx.evaluate_str("[for n in var.names : \"${n}!\"]")
+            .unwrap(),
+        hcl::value!(["a!", "b!"]),
+    );
+
+    // Bodies are not valid expressions.
+    assert!(ctx.evaluate_str("attr = 1").is_err());
+
+    // Numbers are interpolated in normalized form, regardless of how they were written.
+    assert_eq!(
+        ctx.evaluate_str(r#""${1.50} ${1e3} ${-2.50}""#).unwrap(),
+        Value::from("1.5 1000 -2.5"),
+    );
+}
+
+#[test]
+fn eval_raw_expr() {
+    let mut ctx = Context::new();
+    ctx.declare_var("var", hcl::value!({ names = ["a", "b"] }));
+
+    assert_eval_ctx(
+        &ctx,
+        Expression::from(RawExpression::new("[for n in var.names : \"${n}!\"]")),
+        hcl::value!(["a!", "b!"]),
+    );
+    assert!(Expression::from(RawExpression::new("attr = 1"))
+        .evaluate(&ctx)
+        .is_err());
+    assert_eq!(
+        Expression::from(RawExpression::new("var.names")).referenced_variables(),
+        [Traversal::builder(Variable::unchecked("var"))
+            .attr("names")
+            .build()
+            .into()],
+    );
+}
+
+#[test]
+fn eval_variadic_func() {
+    fn sum(args: FuncArgs) -> Result<Value, String> {
+        let mut sum = args.get(0).and_then(Value::as_number).copied().unwrap();
+
+        for arg in args.variadic_args() {
+            sum = sum + *arg.as_number().unwrap();
+        }
+
+        // All arguments are visible via `iter` and `len`.
+        assert_eq!(args.iter().count(), args.len());
+        assert!(args.get(args.len()).is_none());
+
+        Ok(Value::Number(sum))
+    }
+
+    let mut ctx = Context::new();
+    ctx.declare_func(
+        "sum",
+        FuncDef::builder()
+            .param(ParamType::Number)
+            .variadic_param(ParamType::Number)
+            .build(sum),
+    );
+
+    assert_eq!(ctx.evaluate_str("sum(1)").unwrap(), Value::from(1));
+    assert_eq!(
+        ctx.evaluate_str("sum(1, 2, 3, 4)").unwrap(),
+        Value::from(10)
+    );
+    assert_eq!(ctx.evaluate_str("sum(1.5, 2)").unwrap(), Value::from(3.5));
+}
+
 #[test]
 fn eval_for_expr() {
     assert_eval(
@@ -140,6 +378,84 @@ fn eval_for_expr() {
     );
 }
 
+#[test]
+fn eval_for_expr_ordering() {
+    let ctx = Context::new();
+
+    let keys =
+        |value: Value| -> Vec<String> { value.as_object().unwrap().keys().cloned().collect() };
+
+    // Object results follow the iteration order of the source collection.
+    let value = ctx
+        .evaluate_str("{for k, v in {b = 1, a = 2} : k => v}")
+        .unwrap();
+    assert_eq!(value, hcl::value!({ b = 1, a = 2 }));
+    assert_eq!(keys(value), ["b", "a"]);
+
+    let value = ctx
+        .evaluate_str(r#"{for v in ["z", "x", "y"] : v => v}"#)
+        .unwrap();
+    assert_eq!(keys(value), ["z", "x", "y"]);
+
+    // In grouping mode, keys are ordered by their first occurrence and the values of each group
+    // keep the iteration order.
+    let value = ctx
+        .evaluate_str(r#"{for k, v in {c = "odd", b = "even", a = "odd", d = "even"} : v => k...}"#)
+        .unwrap();
+    assert_eq!(value, hcl::value!({ odd = ["c", "a"], even = ["b", "d"] }));
+    assert_eq!(keys(value.clone()), ["odd", "even"]);
+    assert_eq!(
+        hcl::format::to_string(&value).unwrap(),
+        indoc! {r#"
+            {
+              "odd" = [
+                "c",
+                "a"
+              ]
+              "even" = [
+                "b",
+                "d"
+              ]
+            }"#}
+    );
+}
+
+#[test]
+fn eval_nested_for_expr() {
+    let mut ctx = Context::new();
+    ctx.declare_var("xs", hcl::value!([[1, 2], [3]]));
+    ctx.declare_var("b", 100);
+
+    // Inner loops can reference the variables of outer loops.
+    assert_eq!(
+        ctx.evaluate_str("[for i, a in xs : [for j, b in a : [i, j, b * a[0]]]]")
+            .unwrap(),
+        hcl::value!([[[0, 0, 1], [0, 1, 2]], [[1, 0, 9]]]),
+    );
+    assert_eq!(
+        ctx.evaluate_str("{ for i, a in xs : i => { for b in a : b => b + i } }")
+            .unwrap(),
+        hcl::value!({ "0" = { "1" = 1, "2" = 2 }, "1" = { "3" = 4 } }),
+    );
+
+    // Inner loop variables shadow outer ones only within the inner loop.
+    assert_eq!(
+        ctx.evaluate_str("[for a in xs : [for a in a : a * 2]]")
+            .unwrap(),
+        hcl::value!([[2, 4], [6]]),
+    );
+    assert_eq!(
+        ctx.evaluate_str("[for a in xs : [for x in a : b + x]]")
+            .unwrap(),
+        hcl::value!([[101, 102], [103]]),
+    );
+    assert_eq!(
+        ctx.evaluate_str("[for a in xs : [for b in a : b] if b > 0]")
+            .unwrap(),
+        hcl::value!([[1, 2], [3]]),
+    );
+}
+
 #[test]
 fn eval_traversal() {
     use TraversalOperator::*;
@@ -320,6 +636,186 @@ fn eval_func_call() {
     )
 }
 
+#[test]
+fn eval_func_call_param_names() {
+    fn substr(args: FuncArgs) -> EvalResult<Value, String> {
+        let string = args[0].as_str().unwrap();
+        let offset = args[1].as_u64().unwrap() as usize;
+        Ok(Value::from(&string[offset..]))
+    }
+
+    let mut ctx = Context::new();
+    ctx.declare_func(
+        "substr",
+        FuncDef::builder()
+            .param(ParamType::String)
+            .param_named("offset", ParamType::Number)
+            .build(substr),
+    );
+
+    assert_eq!(
+        ctx.evaluate_str(r#"substr("foobar", 3)"#).unwrap(),
+        Value::from("bar")
+    );
+
+    let assert_error = |expr: &str, msg: &str| match ctx.evaluate_str(expr).unwrap_err() {
+        hcl::Error::Eval(err) => assert_eq!(
+            err.kind(),
+            &ErrorKind::FuncCall("substr".into(), msg.to_owned())
+        ),
+        err => panic!("unexpected error: {err}"),
+    };
+
+    assert_error(
+        r#"substr("foobar", "3")"#,
+        "expected argument `offset` at position 1 to be of type `number`, got `\"3\"`",
+    );
+    // Unnamed parameters are still referred to by their position only.
+    assert_error(
+        "substr(1, 3)",
+        "expected argument at position 0 to be of type `string`, got `1`",
+    );
+}
+
+#[test]
+fn eval_binary_op_override() {
+    let mut ctx = Context::new();
+    ctx.declare_var("items", Value::from_iter(["a", "b"]));
+    ctx.override_binary_op(BinaryOperator::Plus, |lhs, rhs| match (lhs, rhs) {
+        (Value::String(lhs), Value::String(rhs)) => Some(Ok(Value::from(format!("{lhs}{rhs}")))),
+        (Value::String(_), Value::Number(_)) => Some(Err(String::from("cannot add a number"))),
+        _ => None,
+    });
+
+    assert_eq!(
+        ctx.evaluate_str(r#""foo" + "bar" + "baz""#).unwrap(),
+        Value::from("foobarbaz")
+    );
+
+    // Overrides apply within nested scopes as well.
+    assert_eq!(
+        ctx.evaluate_str(r#"[for item in items : item + "!"]"#)
+            .unwrap(),
+        Value::from_iter(["a!", "b!"])
+    );
+
+    // Unhandled operand types and other operators keep their default semantics.
+    assert_eq!(ctx.evaluate_str("1 + 2").unwrap(), Value::from(3));
+    assert_eq!(ctx.evaluate_str("3 - 2").unwrap(), Value::from(1));
+    assert_eq!(
+        ctx.evaluate_str("true + 1").unwrap_err().to_string(),
+        "eval error: binary operator `+` is not applicable to `true` and `1` in expression `true + 1`"
+    );
+
+    // Errors returned by the override are propagated.
+    assert_eq!(
+        ctx.evaluate_str(r#""foo" + 1"#).unwrap_err().to_string(),
+        "eval error: error evaluating binary operator `+`: cannot add a number in expression `\"foo\" + 1`"
+    );
+
+    // Without the override, strings cannot be added.
+    assert!(Context::new().evaluate_str(r#""foo" + "bar""#).is_err());
+}
+
+#[test]
+fn eval_var_resolver() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = AtomicUsize::new(0);
+
+    let mut ctx = Context::new();
+    ctx.declare_var("declared", 1);
+    ctx.set_var_resolver(|name| {
+        calls.fetch_add(1, Ordering::Relaxed);
+
+        match name.as_str() {
+            "declared" => Some(Value::from(100)),
+            "resolved" => Some(Value::from(2)),
+            "items" => Some(Value::from_iter([3, 4])),
+            _ => None,
+        }
+    });
+
+    assert_eval_ctx(
+        &ctx,
+        BinaryOp::new(
+            Variable::unchecked("declared"),
+            BinaryOperator::Plus,
+            Variable::unchecked("resolved"),
+        ),
+        Value::from(3),
+    );
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    // Variables are resolved on demand from within nested scopes as well.
+    assert_eval_ctx(
+        &ctx,
+        ForExpr::new(
+            Identifier::unchecked("item"),
+            Variable::unchecked("items"),
+            BinaryOp::new(
+                Variable::unchecked("item"),
+                BinaryOperator::Mul,
+                Variable::unchecked("resolved"),
+            ),
+        ),
+        Value::from_iter([6, 8]),
+    );
+    assert_eq!(calls.load(Ordering::Relaxed), 4);
+
+    let err = Expression::from(Variable::unchecked("undefined"))
+        .evaluate(&ctx)
+        .unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::UndefinedVar(Identifier::unchecked("undefined"))
+    );
+}
+
+#[test]
+fn eval_var_dyn() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = AtomicUsize::new(0);
+
+    let mut ctx = Context::new();
+    ctx.declare_var_dyn("items", || {
+        calls.fetch_add(1, Ordering::Relaxed);
+        Value::from_iter([1, 2])
+    });
+    ctx.declare_var_dyn("item", || Value::from(100));
+    ctx.declare_var("replaced", 1);
+    ctx.declare_var_dyn("replaced", || Value::from(2));
+
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+    // Dynamic variables are found from within nested scopes and are shadowed by the variables of
+    // `for` expressions. The value is only computed once.
+    assert_eval_ctx(
+        &ctx,
+        ForExpr::new(
+            Identifier::unchecked("item"),
+            Variable::unchecked("items"),
+            BinaryOp::new(
+                Variable::unchecked("item"),
+                BinaryOperator::Mul,
+                Variable::unchecked("replaced"),
+            ),
+        ),
+        Value::from_iter([2, 4]),
+    );
+    assert_eq!(ctx.evaluate_str("items").unwrap(), Value::from_iter([1, 2]));
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    let err = Expression::from(Variable::unchecked("undefined"))
+        .evaluate(&ctx)
+        .unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::UndefinedVar(Identifier::unchecked("undefined"))
+    );
+}
+
 #[test]
 fn eval_template() {
     use std::str::FromStr;
@@ -365,6 +861,189 @@ fn eval_template() {
     );
 }
 
+#[test]
+fn eval_template_for_directive_strip() {
+    use std::str::FromStr;
+
+    #[track_caller]
+    fn assert_render(template: &str, items: &[&str], expected: &str) {
+        let mut ctx = Context::new();
+        ctx.declare_var("items", items.to_vec());
+        let template = Template::from_str(template).unwrap();
+        assert_eval_ctx(&ctx, template, expected.to_owned());
+    }
+
+    // The example from the module level documentation.
+    let bill_of_materials = indoc! {r#"
+
+        Bill of materials:
+        %{ for item in items ~}
+        - ${item}
+        %{ endfor ~}
+    "#};
+
+    assert_render(
+        bill_of_materials,
+        &["time", "code", "sweat"],
+        "\nBill of materials:\n- time\n- code\n- sweat\n",
+    );
+    assert_render(
+        bill_of_materials,
+        &["time"],
+        "\nBill of materials:\n- time\n",
+    );
+
+    // No blank lines are left behind for empty collections.
+    assert_render(bill_of_materials, &[], "\nBill of materials:\n");
+
+    // Stripping the whitespace before the `for` and `endfor` directives as well.
+    assert_render(
+        indoc! {r#"
+            Items:
+            %{~ for item in items ~}
+            ${item}
+            %{~ endfor ~}
+        "#},
+        &["a", "b"],
+        "Items:ab",
+    );
+    assert_render(
+        indoc! {r#"
+            Items:
+            %{~ for item in items ~}
+            ${item}
+            %{~ endfor ~}
+        "#},
+        &[],
+        "Items:",
+    );
+
+    // Without strip markers all whitespace is preserved for every iteration.
+    assert_render(
+        "%{ for item in items }\n- ${item}\n%{ endfor }",
+        &["a", "b"],
+        "\n- a\n\n- b\n",
+    );
+
+    // Inline loops with key variables.
+    assert_render(
+        "[%{ for i, item in items }${i}=${item},%{ endfor }]",
+        &["a", "b"],
+        "[0=a,1=b,]",
+    );
+    assert_render(
+        "[%{ for i, item in items }${i}=${item},%{ endfor }]",
+        &[],
+        "[]",
+    );
+}
+
+#[test]
+fn eval_template_if_directive_complex_cond() {
+    use std::str::FromStr;
+
+    fn length(args: FuncArgs) -> Result<Value, String> {
+        Ok(Value::from(args[0].as_array().unwrap().len()))
+    }
+
+    fn upper(args: FuncArgs) -> Result<Value, String> {
+        Ok(Value::from(args[0].as_str().unwrap().to_uppercase()))
+    }
+
+    let mut ctx = Context::new();
+    ctx.declare_func(
+        "length",
+        FuncDef::builder()
+            .param(ParamType::array_of(ParamType::Any))
+            .build(length),
+    );
+    ctx.declare_func(
+        "upper",
+        FuncDef::builder().param(ParamType::String).build(upper),
+    );
+
+    let template = Template::from_str(indoc! {r#"
+        %{ if length(items) > 0 ~}
+        ${upper(items[0])} and ${length(items) - 1} more
+        %{~ else ~}
+        none
+        %{~ endif ~}
+    "#})
+    .unwrap();
+
+    ctx.declare_var("items", vec!["foo", "bar", "baz"]);
+    assert_eval_ctx(&ctx, template.clone(), String::from("FOO and 2 more"));
+
+    ctx.declare_var("items", Vec::<&str>::new());
+    assert_eval_ctx(&ctx, template, String::from("none"));
+
+    // The same via a template expression in an attribute.
+    let input = indoc! {r#"
+        summary = "%{ if length(items) > 0 && upper(items[0]) == "A" }starts with a%{ endif }"
+    "#};
+
+    ctx.declare_var("items", vec!["a", "b"]);
+    let body: Body = hcl::eval::from_str(input, &ctx).unwrap();
+    assert_eq!(
+        body,
+        Body::builder()
+            .add_attribute(("summary", "starts with a"))
+            .build()
+    );
+
+    let mut body = hcl::parse(input).unwrap();
+    body.evaluate_in_place(&ctx).unwrap();
+    assert_eq!(
+        body,
+        Body::builder()
+            .add_attribute(("summary", "starts with a"))
+            .build()
+    );
+}
+
+#[test]
+fn eval_template_escaped_markers() {
+    let mut ctx = Context::new();
+    ctx.declare_var("value", 1);
+
+    let input = indoc! {r#"
+        quoted = "$${value} is ${value}, %%{ if x }"
+        heredoc = <<EOT
+        $${value} is ${value}, %%{ if x }
+        EOT
+        indented = <<-EOT
+          $${value} is ${value}, %%{ if x }
+          EOT
+        dollars = "$$${value} $$"
+    "#};
+
+    let expected = Body::builder()
+        .add_attribute(("quoted", "${value} is 1, %{ if x }"))
+        .add_attribute(("heredoc", "${value} is 1, %{ if x }\n"))
+        .add_attribute(("indented", "${value} is 1, %{ if x }\n"))
+        .add_attribute(("dollars", "$${value} $$"))
+        .build();
+
+    let body: Body = hcl::eval::from_str(input, &ctx).unwrap();
+    assert_eq!(body, expected);
+
+    // Literal markers are escaped again when the evaluated body is formatted.
+    let formatted = hcl::format::to_string(&body).unwrap();
+    assert_eq!(
+        formatted,
+        indoc! {r#"
+            quoted = "$${value} is 1, %%{ if x }"
+            heredoc = "$${value} is 1, %%{ if x }\n"
+            indented = "$${value} is 1, %%{ if x }\n"
+            dollars = "$$${value} $$"
+        "#}
+    );
+
+    // Evaluating the formatted output yields the same values.
+    let reparsed: Body = hcl::eval::from_str(&formatted, &ctx).unwrap();
+    assert_eq!(reparsed, expected);
+}
+
 #[test]
 fn expr_error_context() {
     let input = indoc! {r#"
@@ -515,3 +1194,106 @@ fn interpolation_unwrapping() {
         Value::from("true"),
     );
 }
+
+#[test]
+fn eval_with_functions_only() {
+    fn upper(args: FuncArgs) -> Result<Value, String> {
+        Ok(Value::from(args[0].as_str().unwrap().to_uppercase()))
+    }
+
+    let mut ctx = Context::new();
+    ctx.declare_func(
+        "upper",
+        FuncDef::builder().param(ParamType::String).build(upper),
+    );
+    ctx.declare_var("var", Value::from_iter([("b", "ignored")]));
+
+    let input = indoc! {r#"
+        folded  = upper("a")
+        kept    = var.b
+        partial = [upper("a"), upper(var.b), unknown("c")]
+    "#};
+
+    let expected = indoc! {r#"
+        folded = "A"
+        kept = var.b
+        partial = [
+          "A",
+          upper(var.b),
+          unknown("c")
+        ]
+    "#};
+
+    let mut body = hcl::parse(input).unwrap();
+
+    for attr in body.attributes_mut() {
+        attr.expr = attr.expr.evaluate_with_functions_only(&ctx);
+    }
+
+    assert_eq!(hcl::format::to_string(&body).unwrap(), expected);
+}
+
+#[test]
+fn eval_try_into_value() {
+    fn parse_expr(input: &str) -> Expression {
+        Expression::from(input.parse::<hcl::edit::expr::Expression>().unwrap())
+    }
+
+    let expr = parse_expr(
+        r#"{ a = 1 + 2, b = "${var.prefix}-${upper("x")}", c = [true, var.count * 2] }"#,
+    );
+
+    // Stage 1: only functions are known. Templates are resolved as a whole, so `upper("x")` is
+    // kept until `var.prefix` is known as well.
+    let mut ctx = Context::new();
+    ctx.declare_func(
+        "upper",
+        FuncDef::builder()
+            .param(ParamType::String)
+            .build(|args: FuncArgs| Ok(Value::from(args[0].as_str().unwrap().to_uppercase()))),
+    );
+
+    let partial = expr.try_into_value(&ctx).unwrap_err();
+
+    let expected = indoc! {r#"
+        {
+          a = 3
+          b = "${var.prefix}-${upper("x")}"
+          c = [
+            true,
+            var.count * 2
+          ]
+        }"#};
+
+    assert_eq!(hcl::format::to_string(&partial).unwrap(), expected);
+
+    // Stage 2: some variables are known, but not all.
+    ctx.declare_var("var", hcl::value!({ prefix = "web" }));
+    let partial = partial.try_into_value(&ctx).unwrap_err();
+
+    let expected = indoc! {r#"
+        {
+          a = 3
+          b = "web-X"
+          c = [
+            true,
+            var.count * 2
+          ]
+        }"#};
+
+    assert_eq!(hcl::format::to_string(&partial).unwrap(), expected);
+
+    // Stage 3: everything is known.
+    ctx.declare_var("var", hcl::value!({ prefix = "web", count = 2 }));
+
+    assert_eq!(
+        partial.try_into_value(&ctx),
+        Ok(hcl::value!({ a = 3, b = "web-X", c = [true, 4] }))
+    );
+
+    // Constant expressions are fully resolved without any context.
+    assert_eq!(
+        parse_expr("[1, \"two\", null]").try_into_value(&Context::new()),
+        Ok(hcl::value!([1, "two", null]))
+    );
+}
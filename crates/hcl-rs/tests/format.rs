@@ -2,12 +2,13 @@ mod common;
 
 use common::{assert_format, assert_format_builder};
 use hcl::expr::{
-    BinaryOp, BinaryOperator, Conditional, Expression, ForExpr, FuncCall, FuncName, Heredoc,
-    HeredocStripMode, Traversal, TraversalOperator, Variable,
+    self, BinaryOp, BinaryOperator, Conditional, Expression, ForExpr, FuncCall, FuncName, Heredoc,
+    HeredocStripMode, RawExpression, Traversal, TraversalOperator, UnaryOp, UnaryOperator,
+    Variable,
 };
-use hcl::format::Formatter;
-use hcl::template::{ForDirective, IfDirective, Strip, Template};
-use hcl::Identifier;
+use hcl::format::{Commented, Format, Formatter, Newline, StructureOrder};
+use hcl::template::{ForDirective, IfDirective, Interpolation, Strip, Template};
+use hcl::{Attribute, Block, Identifier, Structure};
 use indoc::indoc;
 
 #[test]
@@ -56,6 +57,44 @@ fn compact_arrays() {
     );
 }
 
+#[test]
+fn crlf_newlines() {
+    let body = hcl::body!({
+        attr = [1, 2]
+        object = { foo = "bar" }
+
+        content {
+            heredoc = (
+                Heredoc::new(
+                    Identifier::unchecked("HEREDOC"),
+                    "foo\n  bar\r\nbaz\n"
+                )
+            )
+        }
+    });
+
+    let expected = indoc! {r#"
+        attr = [
+          1,
+          2
+        ]
+        object = {
+          foo = "bar"
+        }
+
+        content {
+          heredoc = <<HEREDOC
+        foo
+          bar
+        baz
+        HEREDOC
+        }
+    "#}
+    .replace('\n', "\r\n");
+
+    assert_format_builder(Formatter::builder().newline(Newline::Crlf), body, &expected);
+}
+
 #[test]
 fn compact_objects() {
     assert_format_builder(
@@ -161,6 +200,22 @@ fn for_object_expr() {
     );
 }
 
+#[test]
+fn for_expr_roundtrip() {
+    for input in [
+        "[for x in xs : x]",
+        "[for i, x in xs : x if i > 0]",
+        "{for k, v in m : k => v}",
+        "{for k, v in m : v => k...}",
+        "{for k, v in m : v => k... if k != \"\"}",
+    ] {
+        let parsed: hcl::edit::expr::Expression = input.parse().unwrap();
+        let expr = Expression::from(parsed);
+        assert!(matches!(expr, Expression::ForExpr(_)), "{input}");
+        assert_eq!(hcl::format::to_string(&expr).unwrap(), input);
+    }
+}
+
 #[test]
 fn conditional() {
     assert_format(
@@ -169,11 +224,164 @@ fn conditional() {
     );
 }
 
+#[test]
+fn nested_conditional() {
+    let cond = |cond, true_expr, false_expr| {
+        Expression::from(Conditional::new(
+            Variable::unchecked(cond),
+            true_expr,
+            false_expr,
+        ))
+    };
+
+    // The conditional operator is right-associative, nested conditionals within the branches do
+    // not need parenthesis.
+    let cases = [
+        (
+            cond("a", cond("b", 1.into(), 2.into()), 3.into()),
+            "a ? b ? 1 : 2 : 3",
+        ),
+        (
+            cond("a", 1.into(), cond("b", 2.into(), 3.into())),
+            "a ? 1 : b ? 2 : 3",
+        ),
+        (
+            Conditional::new(cond("a", 1.into(), 2.into()), 3, 4).into(),
+            "(a ? 1 : 2) ? 3 : 4",
+        ),
+        (
+            Traversal::builder(cond("a", 1.into(), 2.into()))
+                .attr("b")
+                .build()
+                .into(),
+            "(a ? 1 : 2).b",
+        ),
+        (
+            BinaryOp::new(cond("a", 1.into(), 2.into()), BinaryOperator::Plus, 3).into(),
+            "(a ? 1 : 2) + 3",
+        ),
+    ];
+
+    for (expr, expected) in cases {
+        let formatted = hcl::format::to_string(&expr).unwrap();
+        assert_eq!(formatted, expected);
+
+        // Apart from the added parenthesis, parsing the formatted expression yields the original
+        // structure.
+        let parsed: hcl::edit::expr::Expression = formatted.parse().unwrap();
+        let mut parsed = Expression::from(parsed);
+
+        parsed.walk_mut(|expr| {
+            if let Expression::Parenthesis(inner) = expr {
+                *expr = (**inner).clone();
+            }
+        });
+
+        assert_eq!(parsed, expr);
+    }
+}
+
 #[test]
 fn operation() {
     assert_format(BinaryOp::new(1, BinaryOperator::Plus, 2), "1 + 2");
 }
 
+#[test]
+fn operation_precedence() {
+    use BinaryOperator::*;
+
+    let a = || Variable::unchecked("a");
+    let b = || Variable::unchecked("b");
+    let c = || Variable::unchecked("c");
+
+    assert_format(
+        BinaryOp::new(a(), Plus, BinaryOp::new(b(), Mul, c())),
+        "a + b * c",
+    );
+    assert_format(
+        BinaryOp::new(BinaryOp::new(a(), Plus, b()), Mul, c()),
+        "(a + b) * c",
+    );
+    assert_format(
+        BinaryOp::new(BinaryOp::new(a(), Mul, b()), Plus, c()),
+        "a * b + c",
+    );
+    assert_format(
+        BinaryOp::new(a(), Mul, BinaryOp::new(b(), Plus, c())),
+        "a * (b + c)",
+    );
+    assert_format(
+        BinaryOp::new(BinaryOp::new(a(), Minus, b()), Minus, c()),
+        "a - b - c",
+    );
+    assert_format(
+        BinaryOp::new(a(), Minus, BinaryOp::new(b(), Minus, c())),
+        "a - (b - c)",
+    );
+    assert_format(
+        BinaryOp::new(a(), Div, BinaryOp::new(b(), Mul, c())),
+        "a / (b * c)",
+    );
+    assert_format(
+        BinaryOp::new(BinaryOp::new(a(), Eq, 1), Or, BinaryOp::new(b(), And, c())),
+        "a == 1 || b && c",
+    );
+    assert_format(
+        BinaryOp::new(BinaryOp::new(a(), Or, b()), And, c()),
+        "(a || b) && c",
+    );
+    assert_format(
+        BinaryOp::new(
+            BinaryOp::new(a(), Plus, 1),
+            LessEq,
+            BinaryOp::new(b(), Mod, 2),
+        ),
+        "a + 1 <= b % 2",
+    );
+    assert_format(
+        BinaryOp::new(Conditional::new(a(), 1, 2), Plus, 3),
+        "(a ? 1 : 2) + 3",
+    );
+}
+
+#[test]
+fn unary_operation_precedence() {
+    assert_format(
+        UnaryOp::new(
+            UnaryOperator::Neg,
+            BinaryOp::new(1, BinaryOperator::Plus, 2),
+        ),
+        "-(1 + 2)",
+    );
+    assert_format(
+        BinaryOp::new(
+            UnaryOp::new(UnaryOperator::Not, Variable::unchecked("a")),
+            BinaryOperator::And,
+            Variable::unchecked("b"),
+        ),
+        "!a && b",
+    );
+    assert_format(
+        UnaryOp::new(UnaryOperator::Not, Conditional::new(true, false, true)),
+        "!(true ? false : true)",
+    );
+}
+
+#[test]
+fn operation_roundtrip() {
+    for input in [
+        "a = 1 + 2 * 3 - 4\n",
+        "a = (1 + 2) * 3\n",
+        "a = 10 - 3 - 2\n",
+        "a = 1 - (2 - 3)\n",
+        "a = -(1 + 2) * 3\n",
+        "a = x == 1 && y != 2 || !z\n",
+    ] {
+        let body = hcl::parse(input).unwrap();
+        assert_format(body, input);
+    }
+}
+
 #[test]
 fn template() {
     let template = Template::new().add_directive(
@@ -213,6 +421,137 @@ fn template() {
     assert_format(template, expected);
 }
 
+#[test]
+fn space_interpolations() {
+    let template = Template::new()
+        .add_literal("Hello, ")
+        .add_interpolation(Variable::unchecked("name"))
+        .add_literal("! ")
+        .add_interpolation(
+            Interpolation::new(
+                FuncCall::builder("upper")
+                    .arg(Variable::unchecked("x"))
+                    .build(),
+            )
+            .with_strip(Strip::Both),
+        )
+        .add_directive(IfDirective::new(
+            true,
+            Template::new().add_interpolation(Variable::unchecked("y")),
+        ));
+
+    assert_format_builder(
+        Formatter::builder(),
+        &template,
+        "Hello, ${name}! ${~upper(x)~}%{ if true }${y}%{ endif }",
+    );
+
+    assert_format_builder(
+        Formatter::builder().space_interpolations(true),
+        template,
+        "Hello, ${ name }! ${~ upper(x) ~}%{ if true }${ y }%{ endif }",
+    );
+}
+
+#[test]
+fn space_interpolations_parsed() {
+    let input = indoc! {r#"
+        a = "Hello, ${var.name}! $${escaped} ${~upper("x")~}"
+        b = <<EOT
+        %{ for x in xs }${x}%{ endfor }
+        ${ y }
+        EOT
+    "#};
+
+    let body = hcl::parse(input).unwrap();
+
+    let expected = indoc! {r#"
+        a = "Hello, ${ var.name }! $${escaped} ${~ upper("x") ~}"
+        b = <<EOT
+        %{ for x in xs }${ x }%{ endfor }
+        ${ y }
+        EOT
+    "#};
+
+    assert_format_builder(
+        Formatter::builder().space_interpolations(true),
+        body.clone(),
+        expected,
+    );
+
+    assert_format(body, input);
+}
+
+#[test]
+fn quoted_attr_keys() {
+    let input = indoc! {r#"
+        "aws:region" = "eu-west-1"
+
+        provider "aws" {
+          "aws:max_retries" = 3
+          "region" = "eu-west-1"
+        }
+    "#};
+
+    let options = hcl::edit::parser::ParseOptions::new().quoted_attr_keys(true);
+    let body: hcl::Body = options.parse_body(input).unwrap().into();
+
+    // Keys which are not valid identifiers are written quoted, all others are written as
+    // identifiers.
+    let expected = indoc! {r#"
+        "aws:region" = "eu-west-1"
+
+        provider "aws" {
+          "aws:max_retries" = 3
+          region = "eu-west-1"
+        }
+    "#};
+
+    let formatted = hcl::format::to_string(&body).unwrap();
+    assert_eq!(formatted, expected);
+
+    let reparsed: hcl::Body = options.parse_body(&formatted).unwrap().into();
+    assert_eq!(reparsed, body);
+
+    // The same applies when converting back into an `hcl-edit` body.
+    let edit_body: hcl::edit::structure::Body = body.into();
+    assert!(edit_body
+        .to_string()
+        .starts_with("\"aws:region\" = \"eu-west-1\"\n"));
+}
+
+#[test]
+fn number_literals() {
+    let input = indoc! {r#"
+        a = 007
+        b = 1.0e+02
+        c = -0
+        d = -0.0
+        e = 1.50
+    "#};
+
+    // The original text of number literals is only retained by `hcl-edit`.
+    let edit_body: hcl::edit::structure::Body = input.parse().unwrap();
+    assert_eq!(edit_body.to_string(), input);
+
+    let attr = edit_body.get_attribute("e").unwrap();
+    let hcl::edit::expr::Expression::Number(number) = &attr.value else {
+        panic!("expected a number");
+    };
+    assert_eq!(number.as_repr().map(|repr| &**repr), Some("1.50"));
+
+    // Formatting always writes numbers in normalized form.
+    let expected = indoc! {r#"
+        a = 7
+        b = 100
+        c = 0
+        d = 0
+        e = 1.5
+    "#};
+
+    assert_format(hcl::parse(input).unwrap(), expected);
+}
+
 #[test]
 fn parenthesis() {
     assert_format(
@@ -274,6 +613,42 @@ fn indented_heredoc() {
     assert_format(body, expected);
 }
 
+#[test]
+fn raw_expression() {
+    let body = hcl::body!({
+        resource "aws_iam_policy" "policy" {
+            description = (RawExpression::new("\"quoted \\\"as is\\\"\""))
+            policy = (RawExpression::new("<<-EOT\n{\n  \"Version\": \"2012-10-17\"\n}\r\n\nEOT"))
+        }
+    });
+
+    let expected = indoc! {r#"
+        resource "aws_iam_policy" "policy" {
+          description = "quoted \"as is\""
+          policy = <<-EOT
+          {
+            "Version": "2012-10-17"
+          }
+
+          EOT
+        }
+    "#};
+
+    assert_format(body.clone(), expected);
+    assert_format_builder(
+        Formatter::builder().newline(Newline::Crlf),
+        body,
+        &expected.replace('\n', "\r\n"),
+    );
+
+    // Converting into `hcl-edit` expressions parses the raw text and falls back to a string
+    // literal if it is not a valid expression.
+    let parsed = hcl::edit::expr::Expression::from(Expression::from(RawExpression::new("a.b")));
+    assert!(parsed.is_traversal());
+    let lossy = hcl::edit::expr::Expression::from(Expression::from(RawExpression::new("a (")));
+    assert_eq!(lossy.as_str(), Some("a ("));
+}
+
 #[test]
 fn traversal() {
     assert_format(
@@ -293,7 +668,663 @@ fn traversal() {
     );
 }
 
+#[test]
+fn traversal_builder() {
+    let expr: Expression = expr::traversal("var")
+        .attr("foo")
+        .full_splat()
+        .attr("bar")
+        .index(1)
+        .index("key")
+        .attr_splat()
+        .attr("baz")
+        .legacy_index(42)
+        .into();
+
+    assert_format(expr, r#"var.foo[*].bar[1]["key"].*.baz.42"#);
+}
+
 #[test]
 fn empty_block() {
     assert_format(hcl::block!(empty {}), "empty {}\n");
 }
+
+#[test]
+fn literal_string() {
+    let expr = Expression::literal_string("${x} and %{ if y }y%{ endif }");
+    let body = hcl::body!({ literal = (expr.clone()) });
+
+    let expected = indoc! {r#"
+        literal = "$${x} and %%{ if y }y%%{ endif }"
+    "#};
+
+    assert_format(body, expected);
+
+    let parsed = hcl::parse(expected).unwrap();
+    assert_eq!(parsed.attributes().next().unwrap().expr, expr);
+}
+
+#[test]
+fn scalar_arrays() {
+    let body = hcl::body!({
+        block {
+            scalars = [null, true, 1, 1.5]
+            nested = {
+                empty = []
+                single = [1]
+                mixed = [1, [2], "three"]
+            }
+        }
+    });
+
+    assert_format(
+        &body,
+        indoc! {r#"
+            block {
+              scalars = [
+                null,
+                true,
+                1,
+                1.5
+              ]
+              nested = {
+                empty = []
+                single = [
+                  1
+                ]
+                mixed = [
+                  1,
+                  [
+                    2
+                  ],
+                  "three"
+                ]
+              }
+            }
+        "#},
+    );
+
+    assert_format_builder(
+        Formatter::builder().compact_arrays(true),
+        &body,
+        indoc! {r#"
+            block {
+              scalars = [null, true, 1, 1.5]
+              nested = {
+                empty = []
+                single = [1]
+                mixed = [1, [2], "three"]
+              }
+            }
+        "#},
+    );
+
+    assert_format_builder(
+        Formatter::builder().newline(Newline::Crlf),
+        hcl::body!({ scalars = [1, 2] }),
+        "scalars = [\r\n  1,\r\n  2\r\n]\r\n",
+    );
+}
+
+#[test]
+fn commented_attribute() {
+    let attr = Commented::new(Attribute::new("region", "eu-west-1"))
+        .with_leading_comment("The AWS region.")
+        .with_leading_comment("Multi-line\n\ncomment   ")
+        .with_trailing_comment("Must support S3.");
+
+    assert_format(
+        &attr,
+        indoc! {r#"
+            # The AWS region.
+            # Multi-line
+            #
+            # comment
+            region = "eu-west-1" # Must support S3.
+        "#},
+    );
+
+    assert_format(
+        Commented::new(Attribute::new("count", 1)).with_trailing_comment("trailing"),
+        "count = 1 # trailing\n",
+    );
+
+    let err = hcl::format::to_string(
+        &Commented::new(Attribute::new("count", 1)).with_trailing_comment("multi\nline"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "trailing comments must not contain line breaks"
+    );
+}
+
+#[test]
+fn commented_structures() {
+    let mut formatter = Formatter::default();
+
+    let structures = [
+        Commented::new(Structure::from(Attribute::new("a", 1))).with_leading_comment("first"),
+        Commented::new(Structure::from(
+            Block::builder("b").add_attribute(("c", 2)).build(),
+        ))
+        .with_leading_comment("second")
+        .with_trailing_comment("end of b"),
+        Commented::new(Structure::from(Attribute::new("d", 3))).with_trailing_comment("last"),
+    ];
+
+    let mut formatted = String::new();
+
+    for structure in &structures {
+        formatted.push_str(&structure.format_string(&mut formatter).unwrap());
+    }
+
+    let expected = indoc! {r#"
+        # first
+        a = 1
+
+        # second
+        b {
+          c = 2
+        } # end of b
+
+        d = 3 # last
+    "#};
+
+    assert_eq!(formatted, expected);
+}
+
+#[test]
+fn commented_nested_structures() {
+    let block = Commented::new(
+        Block::builder("resource")
+            .add_label("aws_instance")
+            .add_label("web")
+            .add_attribute(("ami", "ami-123"))
+            .build(),
+    )
+    .with_leading_comment("The web server.")
+    .with_structure(
+        Commented::new(Attribute::new("instance_type", "t3.micro"))
+            .with_leading_comment("Burstable.")
+            .with_trailing_comment("cheap"),
+    )
+    .with_structure(
+        Commented::new(Block::builder("ebs_block_device").build())
+            .with_leading_comment("Data volume.")
+            .with_trailing_comment("end of volume")
+            .with_structure(Commented::new(
+                Block::builder("tags")
+                    .add_attribute(("Name", "data"))
+                    .build(),
+            ))
+            .with_structure(
+                Commented::new(Attribute::new("volume_size", 100))
+                    .with_leading_comment("In GiB.\n\nMust be at least 10."),
+            ),
+    )
+    .with_trailing_comment("end of web");
+
+    assert_format(
+        block,
+        indoc! {r#"
+            # The web server.
+            resource "aws_instance" "web" {
+              ami = "ami-123"
+              # Burstable.
+              instance_type = "t3.micro" # cheap
+
+              # Data volume.
+              ebs_block_device {
+                tags {
+                  Name = "data"
+                }
+
+                # In GiB.
+                #
+                # Must be at least 10.
+                volume_size = 100
+              } # end of volume
+            } # end of web
+        "#},
+    );
+}
+
+#[test]
+fn commented_existing_structures() {
+    let block = Block::builder("service")
+        .add_attribute(("zone", "b"))
+        .add_block(
+            Block::builder("sidecar")
+                .add_attribute(("port", 8080))
+                .build(),
+        )
+        .add_attribute(("name", "api"))
+        .build();
+
+    let block = Commented::new(block)
+        .map_structure(0, |zone| zone.with_trailing_comment("zone"))
+        .map_structure(1, |sidecar| {
+            sidecar
+                .with_leading_comment("The sidecar.")
+                .with_trailing_comment("end of sidecar")
+        })
+        .map_structure(2, |name| name.with_leading_comment("The name."))
+        .map_structure(0, |zone| zone.with_leading_comment("The zone."));
+
+    assert_format(
+        &block,
+        indoc! {r#"
+            service {
+              # The zone.
+              zone = "b" # zone
+
+              # The sidecar.
+              sidecar {
+                port = 8080
+              } # end of sidecar
+
+              # The name.
+              name = "api"
+            }
+        "#},
+    );
+
+    // Comments stay attached to their structures when these are reordered.
+    assert_format_builder(
+        Formatter::builder()
+            .dense(true)
+            .order(StructureOrder::AttributesFirst)
+            .sort_attributes(true),
+        &block,
+        indoc! {r#"
+            service {
+              # The name.
+              name = "api"
+              # The zone.
+              zone = "b" # zone
+              # The sidecar.
+              sidecar {
+                port = 8080
+              } # end of sidecar
+            }
+        "#},
+    );
+}
+
+#[test]
+fn traversal_address_roundtrip() {
+    let addresses = [
+        "aws_instance.web",
+        "aws_instance.web[0].id",
+        r#"module.vpc["us-east-1"].subnets[*].id"#,
+        "aws_instance.web.*.id",
+        "aws_instance.web.0.id",
+        r#"local.map["key with spaces"]["nested"]"#,
+    ];
+
+    for address in addresses {
+        let traversal: Traversal = address.parse().unwrap();
+        assert_eq!(traversal.to_string(), address);
+        assert_eq!(
+            traversal.to_string().parse::<Traversal>().unwrap(),
+            traversal
+        );
+    }
+
+    let traversal: Traversal = "aws_instance.web[0].id".parse().unwrap();
+    assert_eq!(
+        traversal,
+        Traversal::builder(Variable::unchecked("aws_instance"))
+            .attr("web")
+            .index(0)
+            .attr("id")
+            .build()
+    );
+
+    for invalid in [
+        "aws_instance",
+        "foo()[0]",
+        "var.map[var.key]",
+        "1 + 2",
+        "a.b[",
+    ] {
+        assert!(invalid.parse::<Traversal>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn canonical_form() {
+    let a = hcl::parse(indoc! {r#"
+        resource "aws_s3_bucket" "b" {
+          tags   = { "Team" = "infra", Env = "prod" }
+          bucket = "b"
+        }
+
+        provider "aws" {
+          region = "eu-west-1"
+        }
+
+        locals {
+          ports = [80.0, 443]
+          lookup = lookup({ b = 2, a = 1 }, "a")
+        }
+
+        resource "aws_s3_bucket" "a" {
+          bucket = "a"
+        }
+    "#})
+    .unwrap();
+
+    let b = hcl::parse(indoc! {r#"
+        locals {
+          // Ports.
+          lookup = lookup({
+            a = 1
+            b = 2
+          }, "a")
+          ports = [
+            80,
+            443,
+          ]
+        }
+        provider "aws" { region = "eu-west-1" }
+        resource "aws_s3_bucket" "b" {
+          bucket = "b"
+          tags = {
+            Env  = "prod"
+            Team = "infra"
+          }
+        }
+        resource "aws_s3_bucket" "a" {
+          bucket = "a"
+        }
+    "#})
+    .unwrap();
+
+    let expected = indoc! {r#"
+        locals {
+          lookup = lookup({ a = 1, b = 2 }, "a")
+          ports = [80, 443]
+        }
+        provider "aws" {
+          region = "eu-west-1"
+        }
+        resource "aws_s3_bucket" "b" {
+          bucket = "b"
+          tags = { Env = "prod", Team = "infra" }
+        }
+        resource "aws_s3_bucket" "a" {
+          bucket = "a"
+        }
+    "#};
+
+    assert_eq!(hcl::format::canonicalize(&a).unwrap(), expected);
+    assert_eq!(hcl::format::canonicalize(&b).unwrap(), expected);
+
+    // Blocks with the same identifier keep their relative order.
+    let c = hcl::parse("b {}\na { x = 1 }\na { x = 0 }").unwrap();
+    assert_eq!(
+        hcl::format::canonicalize(&c).unwrap(),
+        "a {\n  x = 1\n}\na {\n  x = 0\n}\nb {}\n"
+    );
+
+    let value = hcl::value!({ b = [{ z = 1, y = 2 }], a = null });
+    assert_eq!(
+        hcl::format::canonicalize(&value).unwrap(),
+        "{ a = null, b = [{ y = 2, z = 1 }] }"
+    );
+
+    // Colliding identifier and string keys are merged, the last entry wins.
+    let expr = Expression::Object(expr::Object::from([
+        (expr::ObjectKey::from("a"), Expression::from(2)),
+        (
+            expr::ObjectKey::from(Identifier::unchecked("b")),
+            Expression::from(3),
+        ),
+        (
+            expr::ObjectKey::from(Identifier::unchecked("a")),
+            Expression::from(1),
+        ),
+    ]));
+    assert_eq!(
+        hcl::format::canonicalize(&expr).unwrap(),
+        "{ a = 1, b = 3 }"
+    );
+
+    let expr = Expression::Object(expr::Object::from([
+        (
+            expr::ObjectKey::from(Identifier::unchecked("a")),
+            Expression::from(1),
+        ),
+        (expr::ObjectKey::from("a"), Expression::from(2)),
+    ]));
+    assert_eq!(hcl::format::canonicalize(&expr).unwrap(), "{ a = 2 }");
+}
+
+#[test]
+fn empty_collections() {
+    let value = hcl::value!({
+        empty_array = []
+        empty_object = {}
+        nested = [[], {}, [[]], { a = [] }]
+        last = 1
+    });
+
+    let expected = indoc! {r#"
+        {
+          "empty_array" = []
+          "empty_object" = {}
+          "nested" = [
+            [],
+            {},
+            [
+              []
+            ],
+            {
+              "a" = []
+            }
+          ]
+          "last" = 1
+        }"#};
+
+    let mut formatter = Formatter::default();
+    assert_eq!(value.format_string(&mut formatter).unwrap(), expected);
+
+    let mut formatter = Formatter::builder().compact(true).build_vec();
+    assert_eq!(
+        value.format_string(&mut formatter).unwrap(),
+        r#"{ "empty_array" = [], "empty_object" = {}, "nested" = [[], {}, [[]], { "a" = [] }], "last" = 1 }"#
+    );
+}
+
+#[test]
+fn structure_order() {
+    let body = hcl::parse(indoc! {r#"
+        resource "a" {
+          inner {
+            y = 3
+          }
+          x = 1
+        }
+        count = 2
+        resource "b" {}
+        name = "foo"
+    "#})
+    .unwrap();
+
+    assert_format_builder(
+        Formatter::builder().dense(true),
+        &body,
+        indoc! {r#"
+            resource "a" {
+              inner {
+                y = 3
+              }
+              x = 1
+            }
+            count = 2
+            resource "b" {}
+            name = "foo"
+        "#},
+    );
+
+    assert_format_builder(
+        Formatter::builder()
+            .dense(true)
+            .order(StructureOrder::AttributesFirst),
+        &body,
+        indoc! {r#"
+            count = 2
+            name = "foo"
+            resource "a" {
+              x = 1
+              inner {
+                y = 3
+              }
+            }
+            resource "b" {}
+        "#},
+    );
+
+    assert_format_builder(
+        Formatter::builder()
+            .dense(true)
+            .order(StructureOrder::BlocksFirst),
+        body,
+        indoc! {r#"
+            resource "a" {
+              inner {
+                y = 3
+              }
+              x = 1
+            }
+            resource "b" {}
+            count = 2
+            name = "foo"
+        "#},
+    );
+}
+
+#[test]
+fn sort_attributes() {
+    let body = hcl::parse(indoc! {r#"
+        zone = "b"
+        name = "app"
+        service "api" {
+          timeout = 30
+          image = "api:latest"
+          sidecar {
+            port = 8080
+            env = "prod"
+          }
+          replicas = 2
+        }
+        version = 2
+        labels = { z = 1, a = 2 }
+    "#})
+    .unwrap();
+
+    // Blocks keep their position and object keys are not sorted.
+    assert_format_builder(
+        Formatter::builder().dense(true).sort_attributes(true),
+        &body,
+        indoc! {r#"
+            name = "app"
+            zone = "b"
+            service "api" {
+              image = "api:latest"
+              timeout = 30
+              sidecar {
+                env = "prod"
+                port = 8080
+              }
+              replicas = 2
+            }
+            labels = {
+              z = 1
+              a = 2
+            }
+            version = 2
+        "#},
+    );
+
+    assert_format_builder(
+        Formatter::builder()
+            .dense(true)
+            .sort_attributes(true)
+            .order(StructureOrder::AttributesFirst),
+        body,
+        indoc! {r#"
+            labels = {
+              z = 1
+              a = 2
+            }
+            name = "app"
+            version = 2
+            zone = "b"
+            service "api" {
+              image = "api:latest"
+              replicas = 2
+              timeout = 30
+              sidecar {
+                env = "prod"
+                port = 8080
+              }
+            }
+        "#},
+    );
+}
+
+#[test]
+fn custom_literals() {
+    let body = hcl::parse(indoc! {r#"
+        enabled = true
+        disabled = false
+        unset = null
+        list = [true, null, "true"]
+        cond = var.x ? false : null
+        nested {
+          flags = { a = true, b = false }
+        }
+    "#})
+    .unwrap();
+
+    let builder = || {
+        Formatter::builder()
+            .compact(true)
+            .bool_literals("yes", "no")
+            .null_literal("nil")
+    };
+
+    // Only literals are affected, strings and identifiers are left alone.
+    assert_format_builder(
+        builder(),
+        &body,
+        indoc! {r#"
+            enabled = yes
+            disabled = no
+            unset = nil
+            list = [yes, nil, "true"]
+            cond = var.x ? no : nil
+
+            nested {
+              flags = { a = yes, b = no }
+            }
+        "#},
+    );
+
+    // Values are affected as well.
+    assert_format_builder(
+        builder(),
+        hcl::value!({ a = [true, false, null] }),
+        r#"{ "a" = [yes, no, nil] }"#,
+    );
+
+    // The defaults are the standard HCL keywords.
+    assert_format_builder(
+        Formatter::builder().compact(true),
+        hcl::value!([true, false, null]),
+        "[true, false, null]",
+    );
+}
@@ -1,5 +1,5 @@
 use hcl::expr::{TemplateExpr, Variable};
-use hcl::template::{IfDirective, Strip, Template};
+use hcl::template::{IfDirective, Interpolation, Strip, Template};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -21,3 +21,42 @@ fn from_expr() {
 
     assert_eq!(Template::from_expr(&expr).unwrap(), expected);
 }
+
+#[test]
+fn strip_markers_roundtrip() {
+    let combinations = [
+        ("", "", Strip::None),
+        ("~", "", Strip::Start),
+        ("", "~", Strip::End),
+        ("~", "~", Strip::Both),
+    ];
+
+    for (start, end, strip) in combinations {
+        let input = format!("a ${{{start}x{end}}} b");
+        let expected = Template::new()
+            .add_literal("a ")
+            .add_interpolation(Interpolation::new(Variable::unchecked("x")).with_strip(strip))
+            .add_literal(" b");
+
+        let template: Template = input.parse().unwrap();
+        assert_eq!(template, expected);
+        assert_eq!(template.to_string(), input);
+
+        let input =
+            format!("a %{{{start} if x {end}}}y%{{{start} else {end}}}n%{{{start} endif {end}}} b");
+        let expected = Template::new()
+            .add_literal("a ")
+            .add_directive(
+                IfDirective::new(Variable::unchecked("x"), Template::new().add_literal("y"))
+                    .with_false_template(Template::new().add_literal("n"))
+                    .with_if_strip(strip)
+                    .with_else_strip(strip)
+                    .with_endif_strip(strip),
+            )
+            .add_literal(" b");
+
+        let template: Template = input.parse().unwrap();
+        assert_eq!(template, expected);
+        assert_eq!(template.to_string(), input);
+    }
+}